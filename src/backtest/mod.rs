@@ -0,0 +1,238 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    kucoin::{
+        trading::spot::{
+            order::{self, Details},
+            Kline, Ticker,
+        },
+        KuCoin,
+    },
+    strategies::{
+        engine::Recorder,
+        strategy::{ActionKind, Condition, Product, Strategy},
+        Strategies,
+    },
+};
+
+mod report;
+pub use report::Report;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    time: u64,
+    #[serde(rename = "ticker")]
+    tickers: Vec<Ticker>,
+}
+
+impl Snapshot {
+    pub fn new(time: u64, tickers: Vec<Ticker>) -> Self {
+        Self { time, tickers }
+    }
+
+    pub fn from_klines(klines: HashMap<String, Vec<Kline>>, fee_rate: f64) -> Vec<Self> {
+        let mut snapshots: BTreeMap<u64, Vec<Ticker>> = BTreeMap::new();
+
+        for (symbol, klines) in klines {
+            for (index, kline) in klines.iter().enumerate() {
+                let interval = klines
+                    .get(index + 1)
+                    .map_or(60, |next| next.time() - kline.time())
+                    * 1000;
+
+                let prices = match kline.close() >= kline.open() {
+                    true => [kline.open(), kline.low(), kline.high(), kline.close()],
+                    false => [kline.open(), kline.high(), kline.low(), kline.close()],
+                };
+
+                for (step, price) in prices.into_iter().enumerate() {
+                    snapshots
+                        .entry(kline.time() * 1000 + interval * step as u64 / 4)
+                        .or_default()
+                        .push(Ticker::new(symbol.clone(), price, fee_rate, fee_rate));
+                }
+            }
+        }
+
+        snapshots
+            .into_iter()
+            .map(|(time, tickers)| Snapshot::new(time, tickers))
+            .collect()
+    }
+
+    pub fn record(kucoin: &KuCoin, time: u64, symbols: &[String]) -> Self {
+        let tickers = kucoin
+            .spot()
+            .tickers()
+            .values()
+            .filter(|ticker| symbols.is_empty() || symbols.iter().any(|s| s == ticker.symbol()))
+            .cloned()
+            .collect();
+
+        Snapshot::new(time, tickers)
+    }
+}
+
+pub struct Backtest {
+    kucoin: KuCoin,
+    strategies: Strategies,
+    symbols: Vec<String>,
+    quote: String,
+}
+
+impl Backtest {
+    pub fn new(kucoin: KuCoin, strategy: Strategy, quote: String) -> Result<Self> {
+        match kucoin.is_simulated() {
+            true => {
+                let symbols = Self::symbols(&strategy);
+                let strategies = Strategies::in_memory();
+                strategies.add(strategy);
+
+                Ok(Self {
+                    kucoin,
+                    strategies,
+                    symbols,
+                    quote,
+                })
+            }
+            false => Err(anyhow!("Backtest requires a simulated exchange")),
+        }
+    }
+
+    pub fn symbols(strategy: &Strategy) -> Vec<String> {
+        let mut symbols: Vec<String> = strategy
+            .actions()
+            .iter()
+            .filter(|action| {
+                matches!(
+                    action.kind(),
+                    ActionKind::SpotOrder { .. }
+                        | ActionKind::TrailingStop { .. }
+                        | ActionKind::StopOrder { .. }
+                        | ActionKind::Oco { .. }
+                        | ActionKind::MarginOrder { .. }
+                        | ActionKind::CancelOrder { .. }
+                )
+            })
+            .map(|action| action.symbol().to_string())
+            .collect();
+
+        let others = match strategy.condition() {
+            Some(Condition::Cross { other, .. }) => other.products(),
+            _ => Vec::new(),
+        };
+
+        for product in strategy.product().into_iter().chain(others) {
+            if let Product::SpotTradingPair(symbol) = product {
+                symbols.push(symbol.clone());
+            }
+        }
+
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
+    pub async fn run<I>(self, snapshots: I) -> Report
+    where
+        I: IntoIterator<Item = Snapshot>,
+    {
+        let paper = self.kucoin.paper().cloned().unwrap();
+        let recorder = Recorder::default();
+
+        let mut prices = HashMap::new();
+        let mut report = Report::new(self.quote.clone());
+
+        for snapshot in snapshots {
+            paper.set_clock(snapshot.time);
+
+            {
+                let mut tickers = self.kucoin.spot().tickers();
+
+                for ticker in snapshot.tickers {
+                    if let Some(last) = ticker.last() {
+                        prices.insert(ticker.symbol().to_string(), last);
+                    }
+
+                    tickers.insert(ticker.symbol().to_string(), ticker);
+                }
+            }
+
+            paper.r#match(&self.kucoin);
+
+            self.kucoin.spot().active().replace(
+                self.symbols
+                    .iter()
+                    .filter_map(|symbol| paper.execute(&order::active(symbol), &self.kucoin).ok())
+                    .filter_map(|value| serde_json::from_value::<Vec<Details>>(value).ok())
+                    .flatten()
+                    .collect(),
+            );
+
+            self.kucoin.spot().tracker().refresh(&self.kucoin).await;
+            self.strategies.tick(&self.kucoin, &recorder).await;
+
+            for event in recorder.take() {
+                report.execution(
+                    snapshot.time,
+                    event.action().to_string(),
+                    event.result().clone().err(),
+                );
+            }
+
+            report.equity(self.equity(&prices));
+        }
+
+        let fees = paper
+            .fills()
+            .iter()
+            .filter_map(|fill| {
+                self.kucoin
+                    .spot()
+                    .symbols()
+                    .get(fill.symbol())
+                    .and_then(|symbol| self.value(symbol.quote_currency(), fill.fee(), &prices))
+            })
+            .fold(0.0, |fees, fee| fees + fee);
+
+        report.finish(paper.fills(), fees);
+
+        report
+    }
+
+    fn equity(&self, prices: &HashMap<String, f64>) -> f64 {
+        let mut balances = self.kucoin.accounts().balances();
+
+        for order in self.kucoin.lending().orders().values() {
+            *balances.entry(order.currency().to_string()).or_default() += order.purchase_size();
+        }
+
+        if let Some(paper) = self.kucoin.paper() {
+            for (currency, liability) in paper.liabilities() {
+                *balances.entry(currency).or_default() -= liability;
+            }
+        }
+
+        balances
+            .iter()
+            .filter_map(|(currency, amount)| self.value(currency, *amount, prices))
+            .sum()
+    }
+
+    fn value(&self, currency: &str, amount: f64, prices: &HashMap<String, f64>) -> Option<f64> {
+        if currency == self.quote {
+            return Some(amount);
+        }
+
+        prices
+            .get(&format!("{currency}-{}", self.quote))
+            .map(|price| amount * price)
+            .or_else(|| {
+                prices
+                    .get(&format!("{}-{currency}", self.quote))
+                    .map(|price| amount / price)
+            })
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Serialize;
+use std::fmt;
+
+use crate::kucoin::paper::Fill;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    quote: String,
+    executions: Vec<Execution>,
+    trades: Vec<Fill>,
+    initial_equity: Option<f64>,
+    final_equity: f64,
+    peak_equity: f64,
+    max_drawdown: f64,
+    fees: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct Execution {
+    time: u64,
+    action: String,
+    error: Option<String>,
+}
+
+impl Report {
+    pub fn new(quote: String) -> Self {
+        Self {
+            quote,
+            executions: Default::default(),
+            trades: Default::default(),
+            initial_equity: Default::default(),
+            final_equity: Default::default(),
+            peak_equity: Default::default(),
+            max_drawdown: Default::default(),
+            fees: Default::default(),
+        }
+    }
+
+    pub fn pnl(&self) -> f64 {
+        self.final_equity - self.initial_equity.unwrap_or_default()
+    }
+
+    pub fn max_drawdown(&self) -> f64 {
+        self.max_drawdown
+    }
+
+    pub fn fees(&self) -> f64 {
+        self.fees
+    }
+
+    pub fn trades(&self) -> &[Fill] {
+        &self.trades
+    }
+
+    pub(super) fn execution(&mut self, time: u64, action: String, error: Option<String>) {
+        self.executions.push(Execution {
+            time,
+            action,
+            error,
+        });
+    }
+
+    pub(super) fn equity(&mut self, equity: f64) {
+        self.initial_equity.get_or_insert(equity);
+        self.final_equity = equity;
+        self.peak_equity = self.peak_equity.max(equity);
+
+        if self.peak_equity > 0.0 {
+            self.max_drawdown = self
+                .max_drawdown
+                .max((self.peak_equity - equity) / self.peak_equity * 100.0);
+        }
+    }
+
+    pub(super) fn finish(&mut self, trades: Vec<Fill>, fees: f64) {
+        self.trades = trades;
+        self.fees = fees;
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Executions: {}", self.executions.len())?;
+
+        for execution in &self.executions {
+            match &execution.error {
+                Some(error) => writeln!(f, "  {} {} ❌ {error}", execution.time, execution.action)?,
+                None => writeln!(f, "  {} {} ✅", execution.time, execution.action)?,
+            }
+        }
+
+        writeln!(f, "\nTrades: {}", self.trades.len())?;
+
+        for trade in &self.trades {
+            writeln!(
+                f,
+                "  {} {} {} {} @ {} (fee {})",
+                trade.time(),
+                trade.side(),
+                trade.symbol(),
+                trade.size(),
+                trade.price(),
+                trade.fee()
+            )?;
+        }
+
+        let initial_equity = self.initial_equity.unwrap_or_default();
+
+        let pnl_percentage = match initial_equity > 0.0 {
+            true => self.pnl() / initial_equity * 100.0,
+            false => 0.0,
+        };
+
+        write!(
+            f,
+            "\nInitial equity: {initial_equity} {quote}\n\
+            Final equity: {} {quote}\n\
+            PnL: {} {quote} ({pnl_percentage:.2}%)\n\
+            Max drawdown: {:.2}%\n\
+            Fees: {} {quote}",
+            self.final_equity,
+            self.pnl(),
+            self.max_drawdown,
+            self.fees,
+            quote = self.quote,
+        )
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{BufRead as _, BufReader, Write as _},
+    time::{Duration, UNIX_EPOCH},
+};
+use tokio::time::interval;
+
+use kucoin_strategies::{
+    backtest::{Backtest, Snapshot},
+    kucoin::{account::AccountType, task::Poller as _, trading::spot::Kline, KuCoin},
+    strategies::document::{self, Format},
+};
+
+const USAGE: &str = "\
+Usage:
+  backtest --strategy <JSON|YAML FILE> --klines <TYPE> --start <UNIX> [--end <UNIX>] [OPTIONS]
+  backtest --strategy <JSON|YAML FILE> --snapshots <FILE> [OPTIONS]
+  backtest record --out <FILE> [--symbols <A,B,...>] [--period <SECS>]
+
+Options:
+  --balance [ACCOUNT:]CURRENCY=AMOUNT  initial balance, trade account by default (repeatable)
+  --quote <CURRENCY>                   currency used to value balances (default: USDT)
+  --fee <RATE>                         fee rate applied to kline replays (default: 0.001)";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut args = env::args().skip(1).peekable();
+
+    if args.peek().is_some_and(|arg| arg == "record") {
+        args.next();
+        return record(parse(args)?).await;
+    }
+
+    let options = parse(args)?;
+
+    let path = options.get("strategy").context(USAGE)?;
+    let strategy = match document::import(&fs::read_to_string(path)?, Format::from_file_name(path))?
+        .as_slice()
+    {
+        [strategy] => strategy.clone(),
+        _ => bail!("Strategy file must contain exactly one strategy"),
+    };
+
+    let kucoin = KuCoin::simulated();
+    let client = kucoin.client().clone();
+
+    kucoin.spot().symbols_ref().poll(&client).await;
+    kucoin.spot().currencies_ref().poll(&client).await;
+    kucoin.lending().currencies_ref().poll(&client).await;
+
+    for balance in options.get_all("balance") {
+        let (r#type, currency, amount) = parse_balance(balance)?;
+        kucoin.accounts().adjust(r#type, &currency, amount, 0.0)?;
+    }
+
+    let snapshots = match (options.get("klines"), options.get("snapshots")) {
+        (Some(r#type), None) => {
+            let start = options.get("start").context(USAGE)?.parse()?;
+            let end = match options.get("end") {
+                Some(end) => end.parse()?,
+                None => UNIX_EPOCH.elapsed()?.as_secs(),
+            };
+            let fee_rate = options.get("fee").map_or(Ok(0.001), |fee| fee.parse())?;
+
+            let mut klines = HashMap::new();
+
+            for symbol in Backtest::symbols(&strategy) {
+                let history = Kline::history(&client, &symbol, r#type, start, end).await?;
+                klines.insert(symbol, history);
+            }
+
+            Snapshot::from_klines(klines, fee_rate)
+        }
+        (None, Some(path)) => BufReader::new(fs::File::open(path)?)
+            .lines()
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect::<Result<_>>()?,
+        _ => bail!(USAGE),
+    };
+
+    let quote = options.get("quote").unwrap_or("USDT").to_string();
+
+    println!(
+        "{}",
+        Backtest::new(kucoin, strategy, quote)?.run(snapshots).await
+    );
+
+    Ok(())
+}
+
+async fn record(options: Options) -> Result<()> {
+    let path = options.get("out").context(USAGE)?;
+    let period = options
+        .get("period")
+        .map_or(Ok(5), |period| period.parse())?;
+    let symbols: Vec<String> = options
+        .get("symbols")
+        .map(|symbols| symbols.split(',').map(|s| s.to_uppercase()).collect())
+        .unwrap_or_default();
+
+    let kucoin = KuCoin::simulated();
+    let tickers = kucoin.spot().tickers_ref().clone();
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    let mut interval = interval(Duration::from_secs(period));
+
+    loop {
+        interval.tick().await;
+        tickers.poll(kucoin.client()).await;
+
+        let snapshot =
+            Snapshot::record(&kucoin, UNIX_EPOCH.elapsed()?.as_millis() as u64, &symbols);
+
+        writeln!(file, "{}", serde_json::to_string(&snapshot)?)?;
+    }
+}
+
+struct Options(Vec<(String, String)>);
+
+impl Options {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut options = vec![];
+
+    while let Some(arg) = args.next() {
+        let key = arg
+            .strip_prefix("--")
+            .ok_or_else(|| anyhow!("Unexpected argument: {arg}\n\n{USAGE}"))?;
+
+        let value = args
+            .next()
+            .ok_or_else(|| anyhow!("Missing value for --{key}\n\n{USAGE}"))?;
+
+        options.push((key.to_string(), value));
+    }
+
+    Ok(Options(options))
+}
+
+fn parse_balance(balance: &str) -> Result<(AccountType, String, f64)> {
+    let (account, balance) = match balance.split_once(':') {
+        Some((account, balance)) => (
+            serde_json::from_value(Value::String(account.to_lowercase()))?,
+            balance,
+        ),
+        None => (AccountType::Trade, balance),
+    };
+
+    let (currency, amount) = balance
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid balance: {balance}\n\n{USAGE}"))?;
+
+    Ok((account, currency.to_uppercase(), amount.parse()?))
+}
@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -10,6 +11,9 @@ use tracing::error;
 
 use crate::kucoin::{constants::ACCOUNTS, task::Poller, Request};
 
+mod sub;
+pub use sub::{SubAccount, SubAccounts};
+
 mod transfer;
 pub use transfer::Transfer;
 
@@ -26,6 +30,81 @@ impl Accounts {
         })
     }
 
+    pub fn total(&self, r#type: &AccountType, currency: &str) -> Option<f64> {
+        self.lock().get(r#type).and_then(|accounts| {
+            accounts
+                .iter()
+                .find(|account| account.currency() == currency)
+                .map(|account| account.available() + account.holds())
+        })
+    }
+
+    pub fn currencies(&self, r#type: &AccountType) -> Vec<String> {
+        self.lock().get(r#type).map_or_else(Vec::new, |accounts| {
+            accounts
+                .iter()
+                .map(|account| account.currency().to_string())
+                .collect()
+        })
+    }
+
+    pub fn balances(&self) -> HashMap<String, f64> {
+        let mut balances = HashMap::new();
+
+        for account in self.lock().values().flatten() {
+            *balances.entry(account.currency.clone()).or_default() +=
+                account.balance.parse::<f64>().unwrap();
+        }
+
+        balances
+    }
+
+    pub fn adjust(
+        &self,
+        r#type: AccountType,
+        currency: &str,
+        available: f64,
+        holds: f64,
+    ) -> Result<()> {
+        let mut lock = self.lock();
+
+        let accounts = lock.entry(r#type).or_default();
+
+        let index = match accounts
+            .iter()
+            .position(|account| account.currency() == currency)
+        {
+            Some(index) => index,
+            None => {
+                accounts.push(Account::new(currency.to_string(), r#type));
+                accounts.len() - 1
+            }
+        };
+
+        let account = &mut accounts[index];
+
+        let available = account.available() + available;
+        let holds = account.holds() + holds;
+
+        if available < -f64::EPSILON || holds < -f64::EPSILON {
+            if account.balance() == 0.0 {
+                accounts.remove(index);
+            }
+
+            return Err(anyhow!("Insufficient {currency} balance in {type} account"));
+        }
+
+        account.available = available.max(0.0).to_string();
+        account.holds = holds.max(0.0).to_string();
+        account.balance = (available + holds).max(0.0).to_string();
+
+        if account.balance() == 0.0 {
+            accounts.remove(index);
+        }
+
+        Ok(())
+    }
+
     fn set(&self, accounts: Vec<Account>) {
         let mut lock = self.lock();
 
@@ -50,12 +129,8 @@ impl fmt::Display for Accounts {
         let lock = self.lock();
 
         if !lock.is_empty() {
-            const TYPES: [AccountType; 4] = [
-                AccountType::Main,
-                AccountType::Trade,
-                AccountType::Contract,
-                AccountType::Margin,
-            ];
+            const TYPES: [AccountType; 3] =
+                [AccountType::Main, AccountType::Trade, AccountType::Contract];
 
             for r#type in TYPES {
                 if let Some(accounts) = lock.get(&r#type) {
@@ -92,6 +167,17 @@ pub struct Account {
 }
 
 impl Account {
+    fn new(currency: String, r#type: AccountType) -> Self {
+        Self {
+            id: Default::default(),
+            currency,
+            r#type,
+            balance: "0".to_string(),
+            available: "0".to_string(),
+            holds: "0".to_string(),
+        }
+    }
+
     pub fn currency(&self) -> &str {
         &self.currency
     }
@@ -107,6 +193,10 @@ impl Account {
     pub fn available(&self) -> f64 {
         self.available.parse().unwrap()
     }
+
+    pub fn holds(&self) -> f64 {
+        self.holds.parse().unwrap()
+    }
 }
 
 impl fmt::Display for Account {
@@ -144,15 +234,23 @@ impl fmt::Display for Account {
 #[strum(serialize_all = "title_case")]
 pub enum AccountType {
     #[default]
+    #[serde(alias = "MAIN")]
     #[strum(to_string = "Funding")]
     Main,
+    #[serde(alias = "TRADE")]
     #[strum(to_string = "Trading")]
     Trade,
+    #[serde(alias = "CONTRACT")]
     #[strum(to_string = "Futures")]
     Contract,
+    #[serde(alias = "MARGIN")]
     Margin,
+    #[serde(alias = "ISOLATED")]
     Isolated,
+    #[serde(alias = "MARGIN_V2")]
     MarginV2,
+    #[serde(alias = "ISOLATED_V2")]
     IsolatedV2,
+    #[serde(alias = "OPTION")]
     Option,
 }
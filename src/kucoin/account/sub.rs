@@ -0,0 +1,162 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::error;
+
+use crate::kucoin::{account::AccountType, constants::SUB_ACCOUNTS, task::Poller, Request};
+
+#[derive(Debug, Default, Clone)]
+pub struct SubAccounts(Arc<Mutex<Vec<SubAccount>>>);
+
+impl SubAccounts {
+    pub fn names(&self) -> Vec<String> {
+        self.lock()
+            .iter()
+            .map(|sub_account| sub_account.sub_name.clone())
+            .collect()
+    }
+
+    pub fn user_id(&self, name: &str) -> Option<String> {
+        self.lock()
+            .iter()
+            .find(|sub_account| sub_account.sub_name == name)
+            .map(|sub_account| sub_account.sub_user_id.clone())
+    }
+
+    pub fn available(&self, name: &str, r#type: &AccountType, currency: &str) -> Option<f64> {
+        self.balance(name, r#type, currency)
+            .map(|balance| balance.available())
+    }
+
+    pub fn total(&self, name: &str, r#type: &AccountType, currency: &str) -> Option<f64> {
+        self.balance(name, r#type, currency)
+            .map(|balance| balance.total())
+    }
+
+    fn balance(&self, name: &str, r#type: &AccountType, currency: &str) -> Option<Balance> {
+        self.lock()
+            .iter()
+            .find(|sub_account| sub_account.sub_name == name)
+            .and_then(|sub_account| sub_account.balances(r#type))
+            .and_then(|balances| {
+                balances
+                    .iter()
+                    .find(|balance| balance.currency == currency)
+                    .cloned()
+            })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<SubAccount>> {
+        self.0.lock().unwrap()
+    }
+}
+
+impl fmt::Display for SubAccounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for sub_account in self.lock().iter() {
+            write!(f, "{sub_account}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Poller for SubAccounts {
+    async fn poll(&self, client: &Client) {
+        #[derive(Debug, Deserialize)]
+        struct Response {
+            items: Vec<SubAccount>,
+        }
+
+        match Request::get(format!("{SUB_ACCOUNTS}?currentPage=1&pageSize=100"))
+            .send::<Response>(client)
+            .await
+        {
+            Ok(response) => *self.lock() = response.items,
+            Err(e) => error!("{e}"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccount {
+    sub_user_id: String,
+    sub_name: String,
+    #[serde(default)]
+    main_accounts: Vec<Balance>,
+    #[serde(default)]
+    trade_accounts: Vec<Balance>,
+    #[serde(default)]
+    margin_accounts: Vec<Balance>,
+}
+
+impl SubAccount {
+    fn balances(&self, r#type: &AccountType) -> Option<&Vec<Balance>> {
+        match r#type {
+            AccountType::Main => Some(&self.main_accounts),
+            AccountType::Trade => Some(&self.trade_accounts),
+            AccountType::Margin => Some(&self.margin_accounts),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SubAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const TYPES: [AccountType; 3] =
+            [AccountType::Main, AccountType::Trade, AccountType::Margin];
+
+        for r#type in TYPES {
+            let balances: Vec<&Balance> = self
+                .balances(&r#type)
+                .into_iter()
+                .flatten()
+                .filter(|balance| balance.total() > 0.0)
+                .collect();
+
+            if balances.is_empty() {
+                continue;
+            }
+
+            write!(f, "<b>{} {type} Account</b>\n\n", self.sub_name)?;
+
+            for balance in balances {
+                write!(f, "{balance}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balance {
+    currency: String,
+    balance: String,
+    available: String,
+    holds: String,
+}
+
+impl Balance {
+    pub fn available(&self) -> f64 {
+        self.available.parse().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> f64 {
+        self.balance.parse().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Balance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Coin:</b> {}\n<b>Total:</b> {}\n<b>Available:</b> {}\n<b>In orders:</b> {}\n\n",
+            self.currency, self.balance, self.available, self.holds
+        )
+    }
+}
@@ -22,7 +22,7 @@ pub struct Transfer {
     r#type: TransferType,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 enum TransferType {
     Internal,
@@ -52,6 +52,54 @@ impl Transfer {
             r#type: TransferType::Internal,
         }
     }
+
+    pub fn parent_to_sub(
+        currency: String,
+        amount: f64,
+        from: AccountType,
+        to: AccountType,
+        sub_user_id: String,
+    ) -> Self {
+        Self {
+            to_user_id: Some(sub_user_id),
+            r#type: TransferType::ParentToSub,
+            ..Transfer::internal(currency, amount, from, to, None, None)
+        }
+    }
+
+    pub fn sub_to_parent(
+        currency: String,
+        amount: f64,
+        from: AccountType,
+        to: AccountType,
+        sub_user_id: String,
+    ) -> Self {
+        Self {
+            from_user_id: Some(sub_user_id),
+            r#type: TransferType::SubToParent,
+            ..Transfer::internal(currency, amount, from, to, None, None)
+        }
+    }
+
+    /// Transfer moving the same amount back to where it came from.
+    pub fn reversed(&self) -> Self {
+        Self {
+            amount: self.amount.clone(),
+            client_oid: Uuid::new_v4().simple().to_string(),
+            currency: self.currency.clone(),
+            from_account_tag: self.to_account_tag.clone(),
+            from_account_type: self.to_account_type,
+            from_user_id: self.to_user_id.clone(),
+            to_account_tag: self.from_account_tag.clone(),
+            to_account_type: self.from_account_type,
+            to_user_id: self.from_user_id.clone(),
+            r#type: match self.r#type {
+                TransferType::Internal => TransferType::Internal,
+                TransferType::ParentToSub => TransferType::SubToParent,
+                TransferType::SubToParent => TransferType::ParentToSub,
+            },
+        }
+    }
 }
 
 impl From<Transfer> for Request {
@@ -1,16 +1,48 @@
 pub const BASE_URL: &str = "https://api.kucoin.com";
+pub const FUTURES_BASE_URL: &str = "https://api-futures.kucoin.com";
 
 pub const ANNOUNCEMENTS: &str = "/api/v3/announcements";
 
 pub const ACCOUNTS: &str = "/api/v1/accounts";
 pub const TRANSFER: &str = "/api/v3/accounts/universal-transfer";
+pub const SUB_ACCOUNTS: &str = "/api/v2/sub-accounts";
+pub const WITHDRAWALS: &str = "/api/v3/withdrawals";
+pub const WITHDRAWAL_HISTORY: &str = "/api/v1/withdrawals";
+pub const DEPOSITS: &str = "/api/v1/deposits";
+pub const DEPOSIT_ADDRESSES: &str = "/api/v3/deposit-addresses";
+pub const DEPOSIT_ADDRESS_CREATE: &str = "/api/v3/deposit-address/create";
 
 pub const LENDING_CURRENCIES: &str = "/api/v3/project/list";
 pub const LENDING_ORDERS: &str = "/api/v3/purchase/orders?status=PENDING";
+pub const LENDING_HISTORY: &str = "/api/v3/purchase/orders";
 pub const LEND: &str = "/api/v3/purchase";
 pub const REDEEM: &str = "/api/v3/redeem";
+pub const REDEEM_HISTORY: &str = "/api/v3/redeem/orders";
+
+pub const MARGIN_ACCOUNTS: &str = "/api/v3/margin/accounts";
+pub const MARGIN_BORROW: &str = "/api/v3/margin/borrow";
+pub const MARGIN_REPAY: &str = "/api/v3/margin/repay";
+pub const MARGIN_ORDER: &str = "/api/v3/hf/margin/order";
+pub const MARGIN_ORDERS: &str = "/api/v3/hf/margin/orders";
+pub const MARGIN_CLIENT_ORDER: &str = "/api/v3/hf/margin/orders/client-order";
+
+pub const FUTURES_CONTRACTS: &str = "/api/v1/contracts/active";
+pub const FUTURES_ACCOUNT_OVERVIEW: &str = "/api/v1/account-overview";
+pub const FUTURES_POSITIONS: &str = "/api/v1/positions";
+pub const FUTURES_ORDER: &str = "/api/v1/orders";
+pub const FUTURES_CLIENT_ORDER: &str = "/api/v1/orders/byClientOid";
 
 pub const SPOT_CURRENCIES: &str = "/api/v3/currencies";
 pub const SPOT_SYMBOLS: &str = "/api/v2/symbols";
 pub const SPOT_TICKERS: &str = "/api/v1/market/allTickers";
+pub const SPOT_KLINES: &str = "/api/v1/market/candles";
 pub const SPOT_ORDER: &str = "/api/v1/hf/orders";
+pub const SPOT_TRIGGERED_ORDER: &str = "/api/v1/orders";
+pub const SPOT_CLIENT_ORDER: &str = "/api/v1/hf/orders/client-order";
+pub const SPOT_ACTIVE_ORDERS: &str = "/api/v1/hf/orders/active";
+pub const SPOT_ACTIVE_SYMBOLS: &str = "/api/v1/hf/orders/active/symbols";
+pub const SPOT_STOP_ORDER: &str = "/api/v1/stop-order";
+pub const SPOT_STOP_CLIENT_ORDER: &str = "/api/v1/stop-order/queryOrderByClientOid";
+pub const SPOT_OCO_ORDER: &str = "/api/v3/oco/order";
+pub const SPOT_OCO_CLIENT_ORDER: &str = "/api/v3/oco/client-order";
+pub const SPOT_OCO_DETAILS: &str = "/api/v3/oco/order/details";
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::kucoin::{
+    constants::{DEPOSIT_ADDRESSES, DEPOSIT_ADDRESS_CREATE},
+    Request,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositAddress {
+    address: String,
+    memo: Option<String>,
+    chain_name: Option<String>,
+    to: Option<String>,
+}
+
+impl DepositAddress {
+    pub fn list(currency: &str, chain: &str) -> Request {
+        Request::get(format!(
+            "{DEPOSIT_ADDRESSES}?currency={currency}&chain={chain}"
+        ))
+    }
+
+    pub fn create(currency: &str, chain: &str) -> Request {
+        Request::post(DEPOSIT_ADDRESS_CREATE)
+            .json(&serde_json::json!({ "currency": currency, "chain": chain }))
+    }
+}
+
+impl fmt::Display for DepositAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<b>Address:</b> <code>{}</code>", self.address)?;
+
+        if let Some(memo) = self.memo.as_deref().filter(|memo| !memo.is_empty()) {
+            writeln!(f, "<b>Memo:</b> <code>{memo}</code>")?;
+        }
+
+        if let Some(chain_name) = &self.chain_name {
+            writeln!(f, "<b>Chain:</b> {chain_name}")?;
+        }
+
+        if let Some(to) = &self.to {
+            writeln!(f, "<b>Credited to:</b> {to} account")?;
+        }
+
+        writeln!(f)
+    }
+}
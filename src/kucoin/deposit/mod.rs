@@ -0,0 +1,234 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use strum::Display;
+use teloxide::{
+    payloads::SendMessageSetters as _, prelude::Requester as _, types::ParseMode::Html, Bot,
+};
+use tracing::error;
+
+use crate::kucoin::{constants::DEPOSITS, response::Paginated, task::Poller, KuCoin, Request};
+
+mod address;
+pub use address::DepositAddress;
+
+const HISTORY: u64 = 24 * 60 * 60 * 1000;
+const RETENTION: u64 = 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Status {
+    Processing,
+    Success,
+    Failure,
+}
+
+#[derive(Debug, Clone)]
+struct Credited {
+    key: String,
+    currency: String,
+    amount: f64,
+    time: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Deposits {
+    seen: Arc<Mutex<Option<HashMap<String, Status>>>>,
+    credited: Arc<Mutex<Vec<Credited>>>,
+    consumed: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+}
+
+impl Deposits {
+    /// Amount credited recently that the strategy hasn't reacted to yet.
+    pub fn received(&self, strategy: &str, currency: &str) -> f64 {
+        let consumed = self.consumed();
+        let consumed = consumed.get(strategy);
+
+        self.credited()
+            .iter()
+            .filter(|credited| credited.currency == currency)
+            .filter(|credited| consumed.is_none_or(|keys| !keys.contains(&credited.key)))
+            .map(|credited| credited.amount)
+            .sum()
+    }
+
+    pub fn consume(&self, strategy: &str, currency: &str) {
+        let keys: Vec<String> = self
+            .credited()
+            .iter()
+            .filter(|credited| credited.currency == currency)
+            .map(|credited| credited.key.clone())
+            .collect();
+
+        self.consumed()
+            .entry(strategy.to_string())
+            .or_default()
+            .extend(keys);
+    }
+
+    fn credited(&self) -> MutexGuard<'_, Vec<Credited>> {
+        self.credited.lock().unwrap()
+    }
+
+    fn consumed(&self) -> MutexGuard<'_, HashMap<String, HashSet<String>>> {
+        self.consumed.lock().unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deposit {
+    currency: String,
+    chain: Option<String>,
+    status: Status,
+    address: String,
+    memo: Option<String>,
+    is_inner: bool,
+    amount: String,
+    fee: String,
+    wallet_tx_id: Option<String>,
+    created_at: u64,
+    updated_at: u64,
+}
+
+impl Deposit {
+    pub fn history(currency: &str) -> Request {
+        Request::get(format!("{DEPOSITS}?currency={currency}&pageSize=5"))
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    fn key(&self) -> String {
+        match self.wallet_tx_id.as_deref() {
+            Some(wallet_tx_id) if !wallet_tx_id.is_empty() => wallet_tx_id.to_string(),
+            _ => format!("{}{}", self.currency, self.created_at),
+        }
+    }
+
+    fn confirmations(&self, kucoin: &KuCoin) -> Option<String> {
+        let currencies = kucoin.spot().currencies();
+
+        let chain = currencies
+            .get(&self.currency)
+            .zip(self.chain.as_deref())
+            .and_then(|(currency, chain)| currency.chain(chain))?;
+
+        Some(format!(
+            "<b>Confirmations:</b> credited after {}, withdrawable after {}\n",
+            chain.pre_confirms(),
+            chain.confirms()
+        ))
+    }
+}
+
+impl fmt::Display for Deposit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Coin:</b> {}\n<b>Amount:</b> {}\n<b>Chain:</b> {}\n<b>Status:</b> {}\n",
+            self.currency,
+            self.amount,
+            self.chain
+                .as_deref()
+                .unwrap_or(if self.is_inner { "INTERNAL" } else { "-" }),
+            self.status
+        )?;
+
+        if let Some(wallet_tx_id) = self.wallet_tx_id.as_deref() {
+            writeln!(f, "<b>Tx:</b> {wallet_tx_id}")?;
+        }
+
+        writeln!(f)
+    }
+}
+
+impl Poller for (Deposits, KuCoin, Bot) {
+    async fn poll(&self, _: &Client) {
+        let (deposits, kucoin, bot) = (&self.0, &self.1, &self.2);
+
+        let now = kucoin.now();
+
+        let path = format!(
+            "{DEPOSITS}?startAt={}&pageSize=100",
+            now.saturating_sub(HISTORY)
+        );
+
+        let paginated = match kucoin.send::<Paginated<Deposit>>(Request::get(path)).await {
+            Ok(paginated) => paginated,
+            Err(e) => {
+                error!("{e}");
+                return;
+            }
+        };
+
+        let changed: Vec<Deposit> = {
+            let mut seen = deposits.seen.lock().unwrap();
+            let is_first = seen.is_none();
+            let seen = seen.get_or_insert_with(Default::default);
+
+            let mut changed = Vec::new();
+            let mut latest = HashMap::new();
+
+            for deposit in paginated {
+                let key = deposit.key();
+
+                latest.insert(key.clone(), deposit.status);
+
+                if !is_first && seen.get(&key) != Some(&deposit.status) {
+                    changed.push(deposit);
+                }
+            }
+
+            *seen = latest;
+
+            changed
+        };
+
+        deposits
+            .credited()
+            .retain(|credited| now - credited.time < RETENTION);
+
+        {
+            let credited = deposits.credited();
+
+            for keys in deposits.consumed().values_mut() {
+                keys.retain(|key| credited.iter().any(|credited| credited.key == *key));
+            }
+        }
+
+        for deposit in changed {
+            let text = match deposit.status {
+                Status::Processing => format!(
+                    "📥 <b>Deposit pending</b>\n\n{deposit}{}",
+                    deposit.confirmations(kucoin).unwrap_or_default()
+                ),
+                Status::Success => {
+                    deposits.credited().push(Credited {
+                        key: deposit.key(),
+                        currency: deposit.currency.clone(),
+                        amount: deposit.amount.parse().unwrap_or_default(),
+                        time: now,
+                    });
+
+                    format!("✅ <b>Deposit credited</b>\n\n{deposit}")
+                }
+                Status::Failure => format!("❌ <b>Deposit failed</b>\n\n{deposit}"),
+            };
+
+            if let Err(e) = bot
+                .send_message(env!("USER_ID").to_string(), text)
+                .parse_mode(Html)
+                .await
+            {
+                error!("{e}")
+            }
+        }
+    }
+}
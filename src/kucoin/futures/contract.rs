@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contract {
+    symbol: String,
+    base_currency: String,
+    quote_currency: String,
+    settle_currency: String,
+    multiplier: f64,
+    lot_size: f64,
+    tick_size: f64,
+    max_order_qty: f64,
+    max_leverage: f64,
+    mark_price: Option<f64>,
+    funding_fee_rate: Option<f64>,
+    predicted_funding_fee_rate: Option<f64>,
+}
+
+impl Contract {
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn settle_currency(&self) -> &str {
+        &self.settle_currency
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    pub fn lot_size(&self) -> f64 {
+        self.lot_size
+    }
+
+    /// Order size for the given number of contracts, rounded down to whole lots.
+    pub fn lots(&self, contracts: f64) -> u64 {
+        let lot_size = self.lot_size.max(1.0);
+
+        ((contracts / lot_size).floor() * lot_size) as u64
+    }
+
+    pub fn max_order_qty(&self) -> f64 {
+        self.max_order_qty
+    }
+
+    pub fn max_leverage(&self) -> u8 {
+        self.max_leverage as u8
+    }
+
+    pub fn mark_price(&self) -> Option<f64> {
+        self.mark_price
+    }
+
+    /// Current funding rate in percent.
+    pub fn funding_rate(&self) -> Option<f64> {
+        self.funding_fee_rate.map(|rate| rate * 100.0)
+    }
+}
@@ -0,0 +1,190 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::error;
+
+use crate::kucoin::{
+    constants::{FUTURES_ACCOUNT_OVERVIEW, FUTURES_CONTRACTS, FUTURES_POSITIONS},
+    task::Poller,
+    KuCoin, Request,
+};
+
+mod contract;
+pub use contract::Contract;
+
+mod order;
+pub use order::AddFutures;
+
+mod position;
+pub use position::Position;
+
+pub const SETTLE_CURRENCY: &str = "USDT";
+
+#[derive(Debug, Default, Clone)]
+pub struct Futures {
+    contracts: Arc<Mutex<Contracts>>,
+    overview: Arc<Mutex<Option<Overview>>>,
+    positions: Arc<Mutex<Positions>>,
+}
+
+impl Futures {
+    pub fn contracts_ref(&self) -> &Arc<Mutex<Contracts>> {
+        &self.contracts
+    }
+
+    pub fn contracts(&self) -> MutexGuard<'_, Contracts> {
+        self.contracts_ref().lock().unwrap()
+    }
+
+    pub fn overview(&self) -> MutexGuard<'_, Option<Overview>> {
+        self.overview.lock().unwrap()
+    }
+
+    pub fn positions(&self) -> MutexGuard<'_, Positions> {
+        self.positions.lock().unwrap()
+    }
+}
+
+impl fmt::Display for Futures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(overview) = self.overview().as_ref() {
+            write!(f, "{overview}")?;
+        }
+
+        let positions = self.positions();
+
+        if !positions.is_empty() {
+            write!(f, "<b>Futures Positions</b>\n\n")?;
+
+            for position in positions.values() {
+                write!(f, "{position}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Contracts(HashMap<String, Contract>);
+
+impl Deref for Contracts {
+    type Target = HashMap<String, Contract>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Poller for Arc<Mutex<Contracts>> {
+    async fn poll(&self, client: &Client) {
+        match Request::get(FUTURES_CONTRACTS)
+            .futures()
+            .send::<Vec<Contract>>(client)
+            .await
+        {
+            Ok(contracts) => {
+                self.lock().unwrap().0 = contracts
+                    .into_iter()
+                    .map(|contract| (contract.symbol().to_string(), contract))
+                    .collect()
+            }
+            Err(e) => error!("{e}"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Positions(HashMap<String, Position>);
+
+impl Deref for Positions {
+    type Target = HashMap<String, Position>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Overview {
+    currency: String,
+    account_equity: f64,
+    #[serde(rename = "unrealisedPNL")]
+    unrealised_pnl: f64,
+    margin_balance: f64,
+    position_margin: f64,
+    order_margin: f64,
+    available_balance: f64,
+}
+
+impl Overview {
+    pub fn request() -> Request {
+        Request::get(format!(
+            "{FUTURES_ACCOUNT_OVERVIEW}?currency={SETTLE_CURRENCY}"
+        ))
+        .futures()
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub fn account_equity(&self) -> f64 {
+        self.account_equity
+    }
+
+    pub fn available_balance(&self) -> f64 {
+        self.available_balance
+    }
+}
+
+impl fmt::Display for Overview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Futures Overview</b>\n\n\
+            <b>Equity:</b> {} {}\n\
+            <b>Available:</b> {}\n\
+            <b>Position margin:</b> {}\n\
+            <b>Order margin:</b> {}\n\
+            <b>Unrealised PnL:</b> {}\n\n",
+            self.account_equity,
+            self.currency,
+            self.available_balance,
+            self.position_margin,
+            self.order_margin,
+            self.unrealised_pnl
+        )
+    }
+}
+
+impl Poller for (Futures, KuCoin) {
+    async fn poll(&self, _: &Client) {
+        let (futures, kucoin) = (&self.0, &self.1);
+
+        match kucoin.send::<Overview>(Overview::request()).await {
+            Ok(overview) => *futures.overview() = Some(overview),
+            Err(e) => error!("{e}"),
+        }
+
+        match kucoin
+            .send::<Vec<Position>>(Request::get(FUTURES_POSITIONS).futures())
+            .await
+        {
+            Ok(positions) => {
+                futures.positions().0 = positions
+                    .into_iter()
+                    .filter(|position| position.is_open())
+                    .map(|position| (position.symbol().to_string(), position))
+                    .collect()
+            }
+            Err(e) => error!("{e}"),
+        }
+    }
+}
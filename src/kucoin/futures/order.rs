@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::kucoin::{
+    constants::{FUTURES_CLIENT_ORDER, FUTURES_ORDER},
+    trading::spot::order::{Side, Type},
+    Request,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddFutures {
+    client_oid: String,
+    symbol: String,
+    side: Side,
+    r#type: Type,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    leverage: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    close_order: bool,
+}
+
+impl AddFutures {
+    pub fn market(symbol: String, side: Side, leverage: u8, size: u64) -> Self {
+        Self {
+            client_oid: Uuid::new_v4().simple().to_string(),
+            symbol,
+            side,
+            r#type: Type::Market,
+            leverage: Some(leverage),
+            size: Some(size),
+            close_order: false,
+        }
+    }
+
+    /// Closes the whole position at market price.
+    pub fn close(symbol: String, side: Side) -> Self {
+        Self {
+            client_oid: Uuid::new_v4().simple().to_string(),
+            symbol,
+            side,
+            r#type: Type::Market,
+            leverage: None,
+            size: None,
+            close_order: true,
+        }
+    }
+
+    pub fn by_client_oid(client_oid: &str) -> Request {
+        Request::get(format!("{FUTURES_CLIENT_ORDER}?clientOid={client_oid}")).futures()
+    }
+}
+
+impl From<AddFutures> for Request {
+    fn from(value: AddFutures) -> Self {
+        Request::post(FUTURES_ORDER).json(&value).futures()
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::kucoin::trading::spot::order::Side;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    symbol: String,
+    is_open: bool,
+    current_qty: f64,
+    avg_entry_price: f64,
+    mark_price: f64,
+    liquidation_price: f64,
+    real_leverage: f64,
+    unrealised_pnl: f64,
+    realised_pnl: f64,
+    settle_currency: String,
+}
+
+impl Position {
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open && self.current_qty != 0.0
+    }
+
+    pub fn current_qty(&self) -> f64 {
+        self.current_qty
+    }
+
+    pub fn unrealised_pnl(&self) -> f64 {
+        self.unrealised_pnl
+    }
+
+    /// Side of the order that closes the position.
+    pub fn closing_side(&self) -> Side {
+        match self.current_qty > 0.0 {
+            true => Side::Sell,
+            false => Side::Buy,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Contract:</b> {}\n<b>Side:</b> {}\n<b>Size:</b> {} lots\n<b>Entry price:</b> {}\n\
+            <b>Mark price:</b> {}\n<b>Liquidation price:</b> {}\n<b>Leverage:</b> {:.2}x\n\
+            <b>Unrealised PnL:</b> {} {}\n\n",
+            self.symbol,
+            if self.current_qty > 0.0 {
+                "LONG"
+            } else {
+                "SHORT"
+            },
+            self.current_qty.abs(),
+            self.avg_entry_price,
+            self.mark_price,
+            self.liquidation_price,
+            self.real_leverage,
+            self.unrealised_pnl,
+            self.settle_currency
+        )
+    }
+}
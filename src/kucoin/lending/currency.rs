@@ -1,3 +1,4 @@
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -49,6 +50,28 @@ impl Currency {
     pub fn interest_increment(&self) -> f64 {
         self.interest_increment.parse().unwrap()
     }
+
+    /// Interest rate in percent.
+    pub fn check_lend(&self, interest_rate: f64) -> Result<()> {
+        if !self.purchase_enable {
+            bail!("lending is disabled for {}", self.currency);
+        }
+
+        let (min, max) = (self.min_interest_rate(), self.max_interest_rate());
+
+        if !(min..=max).contains(&interest_rate) {
+            bail!("interest rate {interest_rate}% is outside the allowed range {min}% - {max}%");
+        }
+
+        Ok(())
+    }
+
+    pub fn check_redeem(&self) -> Result<()> {
+        match self.redeem_enable {
+            true => Ok(()),
+            false => bail!("redemption is disabled for {}", self.currency),
+        }
+    }
 }
 
 impl fmt::Display for Currency {
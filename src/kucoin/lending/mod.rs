@@ -2,7 +2,7 @@ use reqwest::Client;
 use std::{
     collections::HashMap,
     fmt,
-    ops::Deref,
+    ops::{Deref, DerefMut},
     sync::{Arc, Mutex, MutexGuard},
 };
 use tracing::error;
@@ -98,6 +98,12 @@ impl Deref for Orders {
     }
 }
 
+impl DerefMut for Orders {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl Poller for Arc<Mutex<Orders>> {
     async fn poll(&self, client: &Client) {
         match Request::get(LENDING_ORDERS)
@@ -15,6 +15,19 @@ pub struct Order {
 }
 
 impl Order {
+    pub fn new(currency: String, purchase_order_no: String, size: f64, interest_rate: f64) -> Self {
+        Self {
+            currency,
+            purchase_order_no,
+            purchase_size: size.to_string(),
+            match_size: "0".to_string(),
+            interest_rate: (interest_rate / 100.0).to_string(),
+            income_size: "0".to_string(),
+            apply_time: Default::default(),
+            status: "PENDING".to_string(),
+        }
+    }
+
     pub fn currency(&self) -> &str {
         &self.currency
     }
@@ -27,6 +40,10 @@ impl Order {
         self.purchase_size.parse().unwrap()
     }
 
+    pub fn set_purchase_size(&mut self, size: f64) {
+        self.purchase_size = size.to_string();
+    }
+
     fn interest_rate(&self) -> f32 {
         self.interest_rate.parse::<f32>().unwrap() * 100.0
     }
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::kucoin::{constants::REDEEM, Request};
+use crate::kucoin::{
+    constants::{REDEEM, REDEEM_HISTORY},
+    Request,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +21,12 @@ impl Redeem {
             size: size.to_string(),
         }
     }
+
+    pub fn history(currency: &str, status: &str) -> Request {
+        Request::get(format!(
+            "{REDEEM_HISTORY}?currency={currency}&status={status}"
+        ))
+    }
 }
 
 impl From<Redeem> for Request {
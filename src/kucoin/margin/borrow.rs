@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::kucoin::{
+    constants::{MARGIN_BORROW, MARGIN_REPAY},
+    Request,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Borrow {
+    currency: String,
+    size: String,
+    time_in_force: String,
+    is_isolated: bool,
+    is_hf: bool,
+}
+
+impl Borrow {
+    pub fn new(currency: String, size: f64) -> Self {
+        Self {
+            currency,
+            size: size.to_string(),
+            time_in_force: "FOK".to_string(),
+            is_isolated: false,
+            is_hf: true,
+        }
+    }
+
+    pub fn history(currency: &str, since: u64) -> Request {
+        Request::get(format!(
+            "{MARGIN_BORROW}?currency={currency}&startTime={since}"
+        ))
+    }
+}
+
+impl From<Borrow> for Request {
+    fn from(value: Borrow) -> Self {
+        Request::post(MARGIN_BORROW).json(&value)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repay {
+    currency: String,
+    size: String,
+    is_isolated: bool,
+    is_hf: bool,
+}
+
+impl Repay {
+    pub fn new(currency: String, size: f64) -> Self {
+        Self {
+            currency,
+            size: size.to_string(),
+            is_isolated: false,
+            is_hf: true,
+        }
+    }
+
+    pub fn history(currency: &str, since: u64) -> Request {
+        Request::get(format!(
+            "{MARGIN_REPAY}?currency={currency}&startTime={since}"
+        ))
+    }
+}
+
+impl From<Repay> for Request {
+    fn from(value: Repay) -> Self {
+        Request::post(MARGIN_REPAY).json(&value)
+    }
+}
@@ -0,0 +1,141 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::error;
+
+use crate::kucoin::{constants::MARGIN_ACCOUNTS, task::Poller, KuCoin, Request};
+
+mod borrow;
+pub use borrow::{Borrow, Repay};
+
+mod order;
+pub use order::AddMargin;
+
+pub const QUOTE_CURRENCY: &str = "USDT";
+
+#[derive(Debug, Default, Clone)]
+pub struct Margin {
+    account: Arc<Mutex<Account>>,
+}
+
+impl Margin {
+    pub fn account(&self) -> MutexGuard<'_, Account> {
+        self.account.lock().unwrap()
+    }
+}
+
+impl fmt::Display for Margin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let account = self.account();
+
+        let assets: Vec<&Asset> = account
+            .accounts
+            .iter()
+            .filter(|asset| asset.total() > 0.0 || asset.liability() > 0.0)
+            .collect();
+
+        if assets.is_empty() {
+            return Ok(());
+        }
+
+        write!(
+            f,
+            "<b>Margin Account</b>\n\n\
+            <b>Debt ratio:</b> {}%\n\
+            <b>Total assets:</b> {} {QUOTE_CURRENCY}\n\
+            <b>Total debt:</b> {} {QUOTE_CURRENCY}\n\n",
+            account.debt_ratio().unwrap_or_default(),
+            account.total_asset_of_quote_currency,
+            account.total_liability_of_quote_currency
+        )?;
+
+        for asset in assets {
+            write!(f, "{asset}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    total_asset_of_quote_currency: String,
+    total_liability_of_quote_currency: String,
+    debt_ratio: String,
+    accounts: Vec<Asset>,
+}
+
+impl Account {
+    pub fn request() -> Request {
+        Request::get(format!(
+            "{MARGIN_ACCOUNTS}?quoteCurrency={QUOTE_CURRENCY}&queryType=MARGIN"
+        ))
+    }
+
+    pub fn debt_ratio(&self) -> Option<f64> {
+        self.debt_ratio
+            .parse::<f64>()
+            .ok()
+            .map(|debt_ratio| debt_ratio * 100.0)
+    }
+
+    pub fn asset(&self, currency: &str) -> Option<&Asset> {
+        self.accounts
+            .iter()
+            .find(|asset| asset.currency == currency)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Asset {
+    currency: String,
+    total: String,
+    available: String,
+    hold: String,
+    liability: String,
+    max_borrow_size: String,
+}
+
+impl Asset {
+    pub fn total(&self) -> f64 {
+        self.total.parse().unwrap_or_default()
+    }
+
+    pub fn available(&self) -> f64 {
+        self.available.parse().unwrap_or_default()
+    }
+
+    pub fn liability(&self) -> f64 {
+        self.liability.parse().unwrap_or_default()
+    }
+
+    pub fn max_borrow_size(&self) -> f64 {
+        self.max_borrow_size.parse().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Coin:</b> {}\n<b>Total:</b> {}\n<b>Available:</b> {}\n<b>In orders:</b> {}\n<b>Debt:</b> {}\n\n",
+            self.currency, self.total, self.available, self.hold, self.liability
+        )
+    }
+}
+
+impl Poller for (Margin, KuCoin) {
+    async fn poll(&self, _: &Client) {
+        let (margin, kucoin) = (&self.0, &self.1);
+
+        match kucoin.send::<Account>(Account::request()).await {
+            Ok(account) => *margin.account() = account,
+            Err(e) => error!("{e}"),
+        }
+    }
+}
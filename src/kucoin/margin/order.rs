@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::kucoin::{
+    constants::{MARGIN_CLIENT_ORDER, MARGIN_ORDER},
+    trading::spot::order::Add,
+    Request,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddMargin {
+    #[serde(flatten)]
+    order: Add,
+    is_isolated: bool,
+    auto_borrow: bool,
+    auto_repay: bool,
+}
+
+impl AddMargin {
+    pub fn new(order: Add, auto_borrow: bool, auto_repay: bool) -> Self {
+        Self {
+            order: order.client_oid(Uuid::new_v4().simple().to_string()),
+            is_isolated: false,
+            auto_borrow,
+            auto_repay,
+        }
+    }
+
+    pub fn by_client_oid(client_oid: &str, symbol: &str) -> Request {
+        Request::get(format!(
+            "{MARGIN_CLIENT_ORDER}/{client_oid}?symbol={symbol}"
+        ))
+    }
+}
+
+impl From<AddMargin> for Request {
+    fn from(value: AddMargin) -> Self {
+        Request::post(MARGIN_ORDER).json(&value)
+    }
+}
@@ -1,21 +1,41 @@
+use anyhow::Result;
 use reqwest::{header::HeaderMap, Client, ClientBuilder};
-use std::{collections::HashMap, time::Duration};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::HashMap,
+    time::{Duration, UNIX_EPOCH},
+};
 use teloxide::Bot;
 
 mod constants;
 
 pub mod account;
-pub use account::Accounts;
+pub use account::{Accounts, SubAccounts};
 
 pub mod announcements;
 pub use announcements::Announcements;
 
+pub mod deposit;
+pub use deposit::Deposits;
+
+pub mod futures;
+pub use futures::Futures;
+
 pub mod lending;
 pub use lending::Lending;
 
+pub mod margin;
+pub use margin::Margin;
+
+pub mod paper;
+pub use paper::Paper;
+
 pub mod trading;
 pub use trading::SpotTrading;
 
+pub mod withdrawal;
+pub use withdrawal::Whitelist;
+
 pub mod request;
 pub use request::Request;
 
@@ -29,8 +49,14 @@ use task::{Poller as _, Spawnable as _};
 pub struct KuCoin {
     announcements: Announcements,
     accounts: Accounts,
+    sub_accounts: SubAccounts,
+    deposits: Deposits,
+    futures: Futures,
     lending: Lending,
+    margin: Margin,
     spot: SpotTrading,
+    whitelist: Whitelist,
+    paper: Option<Paper>,
     client: Client,
 }
 
@@ -46,10 +72,18 @@ impl Default for KuCoin {
         KuCoin {
             announcements: Default::default(),
             accounts: Default::default(),
+            sub_accounts: Default::default(),
+            deposits: Default::default(),
+            futures: Default::default(),
             lending: Default::default(),
+            margin: Default::default(),
             spot: Default::default(),
+            whitelist: Default::default(),
+            paper: matches!(option_env!("PAPER_TRADING"), Some("true" | "1"))
+                .then(Default::default),
             client: ClientBuilder::new()
                 .default_headers(headers)
+                .timeout(Duration::from_secs(10))
                 .build()
                 .unwrap(),
         }
@@ -57,6 +91,13 @@ impl Default for KuCoin {
 }
 
 impl KuCoin {
+    pub fn simulated() -> Self {
+        Self {
+            paper: Some(Default::default()),
+            ..Default::default()
+        }
+    }
+
     pub fn announcements(&self) -> &Announcements {
         &self.announcements
     }
@@ -65,27 +106,117 @@ impl KuCoin {
         &self.accounts
     }
 
+    pub fn sub_accounts(&self) -> &SubAccounts {
+        &self.sub_accounts
+    }
+
+    pub fn deposits(&self) -> &Deposits {
+        &self.deposits
+    }
+
+    pub fn futures(&self) -> &Futures {
+        &self.futures
+    }
+
     pub fn lending(&self) -> &Lending {
         &self.lending
     }
 
+    pub fn margin(&self) -> &Margin {
+        &self.margin
+    }
+
     pub fn spot(&self) -> &SpotTrading {
         &self.spot
     }
 
+    pub fn whitelist(&self) -> &Whitelist {
+        &self.whitelist
+    }
+
+    pub fn paper(&self) -> Option<&Paper> {
+        self.paper.as_ref()
+    }
+
+    pub fn now(&self) -> u64 {
+        match self.paper() {
+            Some(paper) => paper.now(),
+            None => UNIX_EPOCH.elapsed().unwrap().as_millis() as u64,
+        }
+    }
+
+    pub fn is_simulated(&self) -> bool {
+        self.paper.is_some()
+    }
+
+    /// Prefix marking messages about simulated activity.
+    pub fn mode_tag(&self) -> &'static str {
+        match self.is_simulated() {
+            true => paper::SIMULATED,
+            false => "",
+        }
+    }
+
     pub fn client(&self) -> &Client {
         &self.client
     }
 
+    pub async fn send<T: DeserializeOwned>(&self, request: Request) -> Result<T> {
+        match self.paper() {
+            Some(paper) => Ok(serde_json::from_value(paper.execute(&request, self)?)?),
+            None => request.send(self.client()).await,
+        }
+    }
+
     pub fn run(self, bot: Bot) {
-        (self.announcements().clone(), bot)
+        (self.announcements().clone(), bot.clone())
             .poller(self.client().clone(), self.announcements().period())
             .spawn();
 
-        self.accounts()
-            .clone()
-            .poller(self.client().clone(), Duration::from_secs(15))
-            .spawn();
+        match self.paper() {
+            Some(paper) => {
+                let (accounts, sub_accounts, client) = (
+                    self.accounts().clone(),
+                    self.sub_accounts().clone(),
+                    self.client().clone(),
+                );
+
+                async move {
+                    accounts.poll(&client).await;
+                    sub_accounts.poll(&client).await;
+                }
+                .spawn();
+
+                (paper.clone(), self.clone(), bot.clone())
+                    .poller(self.client().clone(), Duration::from_secs(5))
+                    .spawn();
+            }
+            None => {
+                self.accounts()
+                    .clone()
+                    .poller(self.client().clone(), Duration::from_secs(15))
+                    .spawn();
+
+                self.sub_accounts()
+                    .clone()
+                    .poller(self.client().clone(), Duration::from_secs(60))
+                    .spawn();
+
+                self.lending()
+                    .orders_ref()
+                    .clone()
+                    .poller(self.client().clone(), Duration::from_secs(20))
+                    .spawn();
+
+                (self.futures().clone(), self.clone())
+                    .poller(self.client().clone(), Duration::from_secs(15))
+                    .spawn();
+
+                (self.deposits().clone(), self.clone(), bot.clone())
+                    .poller(self.client().clone(), Duration::from_secs(30))
+                    .spawn();
+            }
+        }
 
         self.lending()
             .currencies_ref()
@@ -93,10 +224,10 @@ impl KuCoin {
             .poller(self.client().clone(), Duration::from_secs(60))
             .spawn();
 
-        self.lending()
-            .orders_ref()
+        self.futures()
+            .contracts_ref()
             .clone()
-            .poller(self.client().clone(), Duration::from_secs(20))
+            .poller(self.client().clone(), Duration::from_secs(15))
             .spawn();
 
         self.spot()
@@ -116,6 +247,26 @@ impl KuCoin {
             .clone()
             .poller(self.client().clone(), Duration::from_secs(5))
             .spawn();
+
+        (self.margin().clone(), self.clone())
+            .poller(self.client().clone(), Duration::from_secs(15))
+            .spawn();
+
+        (self.spot().active().clone(), self.clone())
+            .poller(self.client().clone(), Duration::from_secs(5))
+            .spawn();
+
+        (self.spot().stops().clone(), self.clone())
+            .poller(self.client().clone(), Duration::from_secs(5))
+            .spawn();
+
+        (self.spot().tracker().clone(), self.clone(), bot.clone())
+            .poller(self.client().clone(), Duration::from_secs(5))
+            .spawn();
+
+        (self.spot().oco().clone(), self.clone(), bot)
+            .poller(self.client().clone(), Duration::from_secs(5))
+            .spawn();
     }
 }
 
@@ -140,6 +291,14 @@ impl<T> WithRecent<T> {
         })
     }
 
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.inner.values()
+    }
+
+    pub fn insert(&mut self, k: String, v: T) {
+        self.inner.insert(k, v);
+    }
+
     pub fn recent(&self) -> Vec<String> {
         self.recent.iter().rev().cloned().collect()
     }
@@ -153,3 +312,14 @@ impl<T> Default for WithRecent<T> {
         }
     }
 }
+
+/// Whether the value is a multiple of the increment, allowing for floating-point error.
+pub fn is_on_increment(value: f64, increment: f64) -> bool {
+    ((value / increment).round() * increment - value).abs() <= increment / 1e6
+}
+
+pub fn with_increment(value: f64, increment: f64) -> f64 {
+    let decimals = increment.log10().abs() as i32;
+    let precision = 10_f64.powi(decimals);
+    (value * precision).trunc() / precision
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::kucoin::trading::spot::order::Side;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    order_id: String,
+    symbol: String,
+    side: Side,
+    price: f64,
+    size: f64,
+    fee: f64,
+    time: u64,
+}
+
+impl Fill {
+    pub fn new(
+        order_id: String,
+        symbol: String,
+        side: Side,
+        price: f64,
+        size: f64,
+        fee: f64,
+        time: u64,
+    ) -> Self {
+        Self {
+            order_id,
+            symbol,
+            side,
+            price,
+            size,
+            fee,
+            time,
+        }
+    }
+
+    pub fn order_id(&self) -> &str {
+        &self.order_id
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price
+    }
+
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    pub fn funds(&self) -> f64 {
+        self.price * self.size
+    }
+
+    pub fn fee(&self) -> f64 {
+        self.fee
+    }
+
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+}
+
+impl fmt::Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Order ID:</b> {}\n\n\
+            <b>Pair:</b> {}\n\
+            <b>Side:</b> {}\n\
+            <b>Price:</b> {}\n\
+            <b>Size:</b> {}\n\
+            <b>Fee:</b> {}",
+            self.order_id, self.symbol, self.side, self.price, self.size, self.fee
+        )
+    }
+}
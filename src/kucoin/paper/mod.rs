@@ -0,0 +1,1203 @@
+use anyhow::{anyhow, bail, Result};
+use reqwest::{Client, Method};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::UNIX_EPOCH,
+};
+use teloxide::{
+    payloads::SendMessageSetters as _, prelude::Requester as _, types::ParseMode::Html, Bot,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::kucoin::{
+    account::AccountType,
+    constants::{
+        LEND, MARGIN_ACCOUNTS, MARGIN_BORROW, MARGIN_ORDER, MARGIN_ORDERS, MARGIN_REPAY, REDEEM,
+        SPOT_ACTIVE_ORDERS, SPOT_ACTIVE_SYMBOLS, SPOT_OCO_DETAILS, SPOT_OCO_ORDER, SPOT_ORDER,
+        SPOT_STOP_ORDER, SPOT_TRIGGERED_ORDER, TRANSFER, WITHDRAWALS,
+    },
+    lending,
+    task::Poller,
+    trading::spot::{
+        oco::OcoStatus,
+        order::{Side, Type},
+        stop::Stop,
+    },
+    KuCoin, Request,
+};
+
+mod fill;
+pub use fill::Fill;
+
+pub const SIMULATED: &str = "🧪 <b>SIMULATED</b>\n\n";
+
+const MAX_LEVERAGE: f64 = 5.0;
+
+#[derive(Debug, Default, Clone)]
+pub struct Paper {
+    orders: Arc<Mutex<Vec<Order>>>,
+    stops: Arc<Mutex<Vec<(String, StopBody)>>>,
+    ocos: Arc<Mutex<Vec<Oco>>>,
+    liabilities: Arc<Mutex<HashMap<String, f64>>>,
+    fills: Arc<Mutex<Vec<Fill>>>,
+    clock: Arc<AtomicU64>,
+}
+
+#[derive(Debug, Clone)]
+struct Order {
+    id: String,
+    client_oid: Option<String>,
+    symbol: String,
+    side: Side,
+    price: f64,
+    size: f64,
+    hold: f64,
+    account: AccountType,
+    auto_repay: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Oco {
+    id: String,
+    legs: [(String, OcoStatus); 2],
+    body: OcoBody,
+    status: OcoStatus,
+    hold: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddBody {
+    r#type: Type,
+    symbol: String,
+    side: Side,
+    client_oid: Option<String>,
+    price: Option<String>,
+    size: Option<String>,
+    funds: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StopBody {
+    #[serde(flatten)]
+    order: AddBody,
+    stop: Stop,
+    stop_price: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarginBody {
+    #[serde(flatten)]
+    order: AddBody,
+    auto_borrow: bool,
+    auto_repay: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoanBody {
+    currency: String,
+    size: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OcoBody {
+    symbol: String,
+    side: Side,
+    price: String,
+    size: String,
+    stop_price: String,
+    limit_price: String,
+    client_oid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LendBody {
+    currency: String,
+    interest_rate: String,
+    size: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RedeemBody {
+    currency: String,
+    purchase_order_no: String,
+    size: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WithdrawBody {
+    currency: String,
+    amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferBody {
+    amount: String,
+    client_oid: String,
+    currency: String,
+    from_account_type: String,
+    to_account_type: String,
+    r#type: String,
+}
+
+impl Paper {
+    pub fn now(&self) -> u64 {
+        match self.clock.load(Ordering::Relaxed) {
+            0 => UNIX_EPOCH.elapsed().unwrap().as_millis() as u64,
+            time => time,
+        }
+    }
+
+    pub fn set_clock(&self, time: u64) {
+        self.clock.store(time, Ordering::Relaxed);
+    }
+
+    pub fn fills(&self) -> Vec<Fill> {
+        self.fills.lock().unwrap().clone()
+    }
+
+    pub fn liabilities(&self) -> HashMap<String, f64> {
+        self.liabilities.lock().unwrap().clone()
+    }
+
+    pub fn execute(&self, request: &Request, kucoin: &KuCoin) -> Result<Value> {
+        if request.is_futures() {
+            bail!("Futures are not supported in paper trading");
+        }
+
+        // Margin and triggered orders share the order book of spot orders.
+        let order_id = [SPOT_ORDER, MARGIN_ORDERS, SPOT_TRIGGERED_ORDER]
+            .iter()
+            .find_map(|prefix| request.path().strip_prefix(prefix))
+            .and_then(|path| path.strip_prefix('/'))
+            .and_then(|path| path.split('?').next());
+
+        let stop_id = request
+            .path()
+            .strip_prefix(SPOT_STOP_ORDER)
+            .and_then(|path| path.strip_prefix('/'));
+
+        let oco_id = request
+            .path()
+            .strip_prefix(SPOT_OCO_ORDER)
+            .and_then(|path| path.strip_prefix('/'));
+
+        let oco_details_id = request
+            .path()
+            .strip_prefix(SPOT_OCO_DETAILS)
+            .and_then(|path| path.strip_prefix('/'));
+
+        match (request.method(), request.path(), order_id) {
+            (&Method::POST, SPOT_ORDER, _) => self.add(request.body()?, kucoin),
+            (&Method::POST, SPOT_OCO_ORDER, _) => self.add_oco(request.body()?, kucoin),
+            (&Method::GET, _, _) if oco_details_id.is_some() => {
+                self.oco_details(oco_details_id.unwrap_or_default())
+            }
+            (&Method::DELETE, _, _) if oco_id.is_some() => {
+                self.cancel_oco(oco_id.unwrap_or_default(), kucoin)
+            }
+            (&Method::POST, SPOT_STOP_ORDER, _) => self.add_stop(request.body()?, kucoin),
+            (&Method::GET, SPOT_STOP_ORDER, _) => Ok(self.stops()),
+            (&Method::DELETE, _, _) if stop_id.is_some() => {
+                self.cancel_stop(stop_id.unwrap_or_default())
+            }
+            (&Method::POST, MARGIN_ORDER, _) => self.add_margin(request.body()?, kucoin),
+            (&Method::POST, MARGIN_BORROW, _) => self.borrow(request.body()?, kucoin),
+            (&Method::POST, MARGIN_REPAY, _) => self.repay(request.body()?, kucoin),
+            (&Method::GET, path, _) if path.starts_with(MARGIN_ACCOUNTS) => {
+                Ok(self.margin_account(kucoin))
+            }
+            (&Method::POST, LEND, _) => self.lend(request.body()?, kucoin),
+            (&Method::POST, REDEEM, _) => self.redeem(request.body()?, kucoin),
+            (&Method::POST, TRANSFER, _) => self.transfer(request.body()?, kucoin),
+            (&Method::POST, WITHDRAWALS, _) => self.withdraw(request.body()?, kucoin),
+            (&Method::GET, SPOT_ACTIVE_SYMBOLS, _) => Ok(self.active_symbols()),
+            (&Method::GET, path, _) if path.starts_with(SPOT_ACTIVE_ORDERS) => {
+                Ok(self.active(path.split_once("symbol=").map(|(_, symbol)| symbol)))
+            }
+            (&Method::GET, _, Some(order_id)) => self.details(order_id),
+            (&Method::DELETE, _, Some(order_id)) => self.cancel(order_id, kucoin),
+            (method, path, _) => bail!("{method} {path} is not supported in paper trading"),
+        }
+    }
+
+    pub fn r#match(&self, kucoin: &KuCoin) -> Vec<Fill> {
+        self.trigger_oco(kucoin);
+
+        let mut filled = self.trigger(kucoin);
+        let triggered = filled.len();
+        let mut repaying = vec![];
+
+        self.orders().retain(|order| {
+            let Some((base, quote)) = currencies(kucoin, &order.symbol) else {
+                return true;
+            };
+
+            let Some((crossed, fee)) =
+                kucoin
+                    .spot()
+                    .tickers()
+                    .get(&order.symbol, false)
+                    .map(|ticker| match order.side {
+                        Side::Buy => (
+                            ticker.best_ask().is_some_and(|ask| ask <= order.price),
+                            ticker.maker_fee(),
+                        ),
+                        Side::Sell => (
+                            ticker.best_bid().is_some_and(|bid| bid >= order.price),
+                            ticker.maker_fee(),
+                        ),
+                    })
+            else {
+                return true;
+            };
+
+            if !crossed {
+                return true;
+            }
+
+            let funds = order.price * order.size;
+            let fee = funds * fee;
+
+            let result = match order.side {
+                Side::Buy => kucoin
+                    .accounts()
+                    .adjust(order.account, &quote, order.hold - funds - fee, -order.hold)
+                    .and_then(|_| {
+                        kucoin
+                            .accounts()
+                            .adjust(order.account, &base, order.size, 0.0)
+                    }),
+                Side::Sell => kucoin
+                    .accounts()
+                    .adjust(order.account, &base, 0.0, -order.size)
+                    .and_then(|_| {
+                        kucoin
+                            .accounts()
+                            .adjust(order.account, &quote, funds - fee, 0.0)
+                    }),
+            };
+
+            match result {
+                Ok(_) => {
+                    let fill = Fill::new(
+                        order.id.clone(),
+                        order.symbol.clone(),
+                        order.side,
+                        order.price,
+                        order.size,
+                        fee,
+                        self.now(),
+                    );
+
+                    if order.auto_repay {
+                        repaying.push(fill.clone());
+                    }
+
+                    filled.push(fill);
+
+                    false
+                }
+                Err(e) => {
+                    error!("{e}");
+                    true
+                }
+            }
+        });
+
+        self.fills
+            .lock()
+            .unwrap()
+            .extend(filled[triggered..].iter().cloned());
+
+        for fill in repaying {
+            self.auto_repay(&fill, kucoin);
+        }
+
+        filled
+    }
+
+    fn trigger(&self, kucoin: &KuCoin) -> Vec<Fill> {
+        let triggered: Vec<(String, StopBody)> = {
+            let mut stops = self.stops.lock().unwrap();
+
+            let (triggered, remaining) = stops.drain(..).partition(|(_, body)| {
+                let last = kucoin
+                    .spot()
+                    .tickers()
+                    .get(&body.order.symbol, false)
+                    .and_then(|ticker| ticker.last());
+
+                match (last, body.stop_price.parse()) {
+                    (Some(last), Ok(stop_price)) => body.stop.is_triggered(last, stop_price),
+                    _ => false,
+                }
+            });
+
+            *stops = remaining;
+
+            triggered
+        };
+
+        let mut filled = vec![];
+
+        for (id, body) in triggered {
+            let result = self.place(id.clone(), body.order, AccountType::Trade, kucoin);
+
+            match result {
+                Ok(_) => filled.extend(
+                    self.fills
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .filter(|fill| fill.order_id() == id)
+                        .cloned(),
+                ),
+                Err(e) => error!("{e}"),
+            }
+        }
+
+        filled
+    }
+
+    fn trigger_oco(&self, kucoin: &KuCoin) {
+        let mut ocos = self.ocos.lock().unwrap();
+
+        for oco in ocos.iter_mut().filter(|oco| oco.status == OcoStatus::New) {
+            let Some((bid, ask, last)) = kucoin
+                .spot()
+                .tickers()
+                .get(&oco.body.symbol, false)
+                .map(|ticker| (ticker.best_bid(), ticker.best_ask(), ticker.last()))
+            else {
+                continue;
+            };
+
+            let (Ok(price), Ok(stop_price)) = (
+                oco.body.price.parse::<f64>(),
+                oco.body.stop_price.parse::<f64>(),
+            ) else {
+                continue;
+            };
+
+            let (is_limit, is_stop) = match oco.body.side {
+                Side::Sell => (
+                    bid.is_some_and(|bid| bid >= price),
+                    last.is_some_and(|last| last <= stop_price),
+                ),
+                Side::Buy => (
+                    ask.is_some_and(|ask| ask <= price),
+                    last.is_some_and(|last| last >= stop_price),
+                ),
+            };
+
+            let (index, price) = match (is_limit, is_stop) {
+                (true, _) => (0, oco.body.price.clone()),
+                (_, true) => (1, oco.body.limit_price.clone()),
+                _ => continue,
+            };
+
+            if let Err(e) = self.release(kucoin, &oco.body.symbol, oco.body.side, oco.hold) {
+                error!("{e}");
+                continue;
+            }
+
+            let body = AddBody {
+                r#type: Type::Limit,
+                symbol: oco.body.symbol.clone(),
+                side: oco.body.side,
+                client_oid: oco.body.client_oid.clone(),
+                price: Some(price),
+                size: Some(oco.body.size.clone()),
+                funds: None,
+            };
+
+            let (status, leg_status) =
+                match self.place(oco.legs[index].0.clone(), body, AccountType::Trade, kucoin) {
+                    Ok(_) if index == 0 => (OcoStatus::Done, OcoStatus::Done),
+                    Ok(_) => (OcoStatus::Triggered, OcoStatus::Triggered),
+                    Err(e) => {
+                        error!("{e}");
+                        (OcoStatus::Cancelled, OcoStatus::Cancelled)
+                    }
+                };
+
+            oco.status = status;
+            oco.legs[index].1 = leg_status;
+            oco.legs[1 - index].1 = OcoStatus::Cancelled;
+        }
+    }
+
+    fn add(&self, body: AddBody, kucoin: &KuCoin) -> Result<Value> {
+        self.place(
+            Uuid::new_v4().simple().to_string(),
+            body,
+            AccountType::Trade,
+            kucoin,
+        )
+    }
+
+    fn add_stop(&self, body: StopBody, kucoin: &KuCoin) -> Result<Value> {
+        currencies(kucoin, &body.order.symbol)
+            .ok_or_else(|| anyhow!("Symbol {} not found", body.order.symbol))?;
+
+        body.stop_price.parse::<f64>()?;
+
+        let id = Uuid::new_v4().simple().to_string();
+
+        self.stops.lock().unwrap().push((id.clone(), body));
+
+        Ok(json!({ "orderId": id }))
+    }
+
+    fn add_oco(&self, body: OcoBody, kucoin: &KuCoin) -> Result<Value> {
+        let (base, quote) = currencies(kucoin, &body.symbol)
+            .ok_or_else(|| anyhow!("Symbol {} not found", body.symbol))?;
+
+        let maker_fee = kucoin
+            .spot()
+            .tickers()
+            .get(&body.symbol, false)
+            .map(|ticker| ticker.maker_fee())
+            .ok_or_else(|| anyhow!("Ticker {} not found", body.symbol))?;
+
+        let price: f64 = body.price.parse()?;
+        let size: f64 = body.size.parse()?;
+        let stop_price: f64 = body.stop_price.parse()?;
+        let limit_price: f64 = body.limit_price.parse()?;
+
+        let is_valid = match body.side {
+            Side::Sell => price > stop_price,
+            Side::Buy => price < stop_price,
+        };
+
+        if !is_valid {
+            bail!("OCO price and stop price are on the wrong sides of the market");
+        }
+
+        let (currency, hold) = match body.side {
+            Side::Buy => (quote, price.max(limit_price) * size * (1.0 + maker_fee)),
+            Side::Sell => (base, size),
+        };
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Trade, &currency, -hold, hold)?;
+
+        let id = Uuid::new_v4().simple().to_string();
+
+        self.ocos.lock().unwrap().push(Oco {
+            id: id.clone(),
+            legs: [
+                (Uuid::new_v4().simple().to_string(), OcoStatus::New),
+                (Uuid::new_v4().simple().to_string(), OcoStatus::New),
+            ],
+            body,
+            status: OcoStatus::New,
+            hold,
+        });
+
+        Ok(json!({ "orderId": id }))
+    }
+
+    fn place(
+        &self,
+        id: String,
+        body: AddBody,
+        account: AccountType,
+        kucoin: &KuCoin,
+    ) -> Result<Value> {
+        let (base, quote) = currencies(kucoin, &body.symbol)
+            .ok_or_else(|| anyhow!("Symbol {} not found", body.symbol))?;
+
+        let (bid, ask, taker_fee, maker_fee) = kucoin
+            .spot()
+            .tickers()
+            .get(&body.symbol, false)
+            .map(|ticker| {
+                (
+                    ticker.best_bid(),
+                    ticker.best_ask(),
+                    ticker.taker_fee(),
+                    ticker.maker_fee(),
+                )
+            })
+            .ok_or_else(|| anyhow!("Ticker {} not found", body.symbol))?;
+
+        let size = parse(body.size.as_deref())?;
+        let funds = parse(body.funds.as_deref())?;
+
+        match body.r#type {
+            Type::Market => {
+                let price = match body.side {
+                    Side::Buy => ask,
+                    Side::Sell => bid,
+                }
+                .ok_or_else(|| anyhow!("No liquidity for {}", body.symbol))?;
+
+                let (size, debit) = match (body.side, size, funds) {
+                    (Side::Buy, _, Some(funds)) => (funds / (price * (1.0 + taker_fee)), funds),
+                    (Side::Buy, Some(size), _) => (size, size * price * (1.0 + taker_fee)),
+                    (Side::Sell, Some(size), _) => (size, size),
+                    (Side::Sell, _, Some(funds)) => (funds / price, funds / price),
+                    _ => bail!("Order size is missing"),
+                };
+
+                let fee = size * price * taker_fee;
+
+                match body.side {
+                    Side::Buy => {
+                        kucoin.accounts().adjust(account, &quote, -debit, 0.0)?;
+                        kucoin.accounts().adjust(account, &base, size, 0.0)?;
+                    }
+                    Side::Sell => {
+                        kucoin.accounts().adjust(account, &base, -debit, 0.0)?;
+                        kucoin
+                            .accounts()
+                            .adjust(account, &quote, size * price - fee, 0.0)?;
+                    }
+                }
+
+                self.fills.lock().unwrap().push(Fill::new(
+                    id.clone(),
+                    body.symbol,
+                    body.side,
+                    price,
+                    size,
+                    fee,
+                    self.now(),
+                ));
+            }
+            Type::Limit => {
+                let price = parse(body.price.as_deref())?
+                    .ok_or_else(|| anyhow!("Order price is missing"))?;
+                let size = size.ok_or_else(|| anyhow!("Order size is missing"))?;
+
+                let hold = match body.side {
+                    Side::Buy => {
+                        let hold = price * size * (1.0 + maker_fee);
+
+                        kucoin.accounts().adjust(account, &quote, -hold, hold)?;
+
+                        hold
+                    }
+                    Side::Sell => {
+                        kucoin.accounts().adjust(account, &base, -size, size)?;
+
+                        size
+                    }
+                };
+
+                self.orders().push(Order {
+                    id: id.clone(),
+                    client_oid: body.client_oid.clone(),
+                    symbol: body.symbol,
+                    side: body.side,
+                    price,
+                    size,
+                    hold,
+                    account,
+                    auto_repay: false,
+                });
+            }
+        }
+
+        Ok(json!({ "orderId": id, "clientOid": body.client_oid }))
+    }
+
+    fn details(&self, order_id: &str) -> Result<Value> {
+        if let Some(order) = self.orders().iter().find(|order| order.id == order_id) {
+            return Ok(order.details());
+        }
+
+        self.fills
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|fill| fill.order_id() == order_id)
+            .map(|fill| {
+                json!({
+                    "id": fill.order_id(),
+                    "symbol": fill.symbol(),
+                    "type": Type::Market,
+                    "side": fill.side(),
+                    "price": fill.price().to_string(),
+                    "size": fill.size().to_string(),
+                    "dealSize": fill.size().to_string(),
+                    "dealFunds": fill.funds().to_string(),
+                    "fee": fill.fee().to_string(),
+                    "active": false,
+                })
+            })
+            .ok_or_else(|| anyhow!("Order {order_id} not found"))
+    }
+
+    fn active(&self, symbol: Option<&str>) -> Value {
+        self.orders()
+            .iter()
+            .filter(|order| symbol.is_none_or(|symbol| order.symbol == symbol))
+            .map(Order::details)
+            .collect()
+    }
+
+    fn active_symbols(&self) -> Value {
+        let mut symbols: Vec<String> = self
+            .orders()
+            .iter()
+            .map(|order| order.symbol.clone())
+            .collect();
+
+        symbols.sort();
+        symbols.dedup();
+
+        json!({ "symbols": symbols })
+    }
+
+    fn stops(&self) -> Value {
+        let items: Vec<Value> = self
+            .stops
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, body)| {
+                json!({
+                    "id": id,
+                    "symbol": body.order.symbol,
+                    "type": body.order.r#type,
+                    "side": body.order.side,
+                    "price": body.order.price,
+                    "size": body.order.size,
+                    "funds": body.order.funds,
+                    "stop": body.stop,
+                    "stopPrice": body.stop_price,
+                })
+            })
+            .collect();
+
+        json!({
+            "currentPage": 1,
+            "pageSize": items.len(),
+            "totalNum": items.len(),
+            "totalPage": 1,
+            "items": items,
+        })
+    }
+
+    fn cancel_stop(&self, order_id: &str) -> Result<Value> {
+        let mut stops = self.stops.lock().unwrap();
+
+        let index = stops
+            .iter()
+            .position(|(id, _)| id == order_id)
+            .ok_or_else(|| anyhow!("Stop order {order_id} not found"))?;
+
+        stops.remove(index);
+
+        Ok(json!({ "cancelledOrderIds": [order_id] }))
+    }
+
+    fn oco_details(&self, order_id: &str) -> Result<Value> {
+        self.ocos
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|oco| oco.id == order_id)
+            .map(|oco| {
+                json!({
+                    "orderId": oco.id,
+                    "symbol": oco.body.symbol,
+                    "clientOid": oco.body.client_oid,
+                    "status": oco.status,
+                    "orders": [
+                        {
+                            "id": oco.legs[0].0,
+                            "symbol": oco.body.symbol,
+                            "side": oco.body.side,
+                            "price": oco.body.price,
+                            "stopPrice": oco.body.price,
+                            "size": oco.body.size,
+                            "status": oco.legs[0].1,
+                        },
+                        {
+                            "id": oco.legs[1].0,
+                            "symbol": oco.body.symbol,
+                            "side": oco.body.side,
+                            "price": oco.body.limit_price,
+                            "stopPrice": oco.body.stop_price,
+                            "size": oco.body.size,
+                            "status": oco.legs[1].1,
+                        },
+                    ],
+                })
+            })
+            .ok_or_else(|| anyhow!("OCO order {order_id} not found"))
+    }
+
+    fn cancel_oco(&self, order_id: &str, kucoin: &KuCoin) -> Result<Value> {
+        let mut ocos = self.ocos.lock().unwrap();
+
+        let oco = ocos
+            .iter_mut()
+            .find(|oco| oco.id == order_id && oco.status == OcoStatus::New)
+            .ok_or_else(|| anyhow!("OCO order {order_id} not found"))?;
+
+        self.release(kucoin, &oco.body.symbol, oco.body.side, oco.hold)?;
+
+        oco.status = OcoStatus::Cancelled;
+
+        for leg in oco.legs.iter_mut() {
+            leg.1 = OcoStatus::Cancelled;
+        }
+
+        Ok(json!({ "cancelledOrderIds": [&oco.legs[0].0, &oco.legs[1].0] }))
+    }
+
+    fn release(&self, kucoin: &KuCoin, symbol: &str, side: Side, hold: f64) -> Result<()> {
+        let (base, quote) =
+            currencies(kucoin, symbol).ok_or_else(|| anyhow!("Symbol {symbol} not found"))?;
+
+        let currency = match side {
+            Side::Buy => quote,
+            Side::Sell => base,
+        };
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Trade, &currency, hold, -hold)
+    }
+
+    fn cancel(&self, order_id: &str, kucoin: &KuCoin) -> Result<Value> {
+        let mut orders = self.orders();
+
+        let index = orders
+            .iter()
+            .position(|order| order.id == order_id)
+            .ok_or_else(|| anyhow!("Order {order_id} not found"))?;
+
+        let order = &orders[index];
+
+        let (base, quote) = currencies(kucoin, &order.symbol)
+            .ok_or_else(|| anyhow!("Symbol {} not found", order.symbol))?;
+
+        let currency = match order.side {
+            Side::Buy => quote,
+            Side::Sell => base,
+        };
+
+        kucoin
+            .accounts()
+            .adjust(order.account, &currency, order.hold, -order.hold)?;
+
+        orders.remove(index);
+
+        Ok(json!({ "orderId": order_id }))
+    }
+
+    fn add_margin(&self, body: MarginBody, kucoin: &KuCoin) -> Result<Value> {
+        let order = body.order;
+
+        let (base, quote) = currencies(kucoin, &order.symbol)
+            .ok_or_else(|| anyhow!("Symbol {} not found", order.symbol))?;
+
+        if body.auto_borrow {
+            let (ask, taker_fee, maker_fee) = kucoin
+                .spot()
+                .tickers()
+                .get(&order.symbol, false)
+                .map(|ticker| (ticker.best_ask(), ticker.taker_fee(), ticker.maker_fee()))
+                .ok_or_else(|| anyhow!("Ticker {} not found", order.symbol))?;
+
+            let size = parse(order.size.as_deref())?;
+            let funds = parse(order.funds.as_deref())?;
+            let price = parse(order.price.as_deref())?;
+
+            let (currency, needed) = match (order.side, order.r#type) {
+                (Side::Buy, Type::Market) => (
+                    &quote,
+                    funds
+                        .or_else(|| {
+                            size.zip(ask)
+                                .map(|(size, ask)| size * ask * (1.0 + taker_fee))
+                        })
+                        .unwrap_or_default(),
+                ),
+                (Side::Buy, Type::Limit) => (
+                    &quote,
+                    price
+                        .zip(size)
+                        .map(|(price, size)| price * size * (1.0 + maker_fee))
+                        .unwrap_or_default(),
+                ),
+                (Side::Sell, _) => (&base, size.unwrap_or_default()),
+            };
+
+            let available = kucoin
+                .accounts()
+                .available(&AccountType::Margin, currency)
+                .unwrap_or_default();
+
+            if needed > available {
+                self.borrow(
+                    LoanBody {
+                        currency: currency.clone(),
+                        size: (needed - available).to_string(),
+                    },
+                    kucoin,
+                )?;
+            }
+        }
+
+        let id = Uuid::new_v4().simple().to_string();
+        let result = self.place(id.clone(), order, AccountType::Margin, kucoin)?;
+
+        if body.auto_repay {
+            let fill = self
+                .fills
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|fill| fill.order_id() == id)
+                .cloned();
+
+            match fill {
+                Some(fill) => self.auto_repay(&fill, kucoin),
+                None => {
+                    if let Some(order) = self.orders().iter_mut().find(|order| order.id == id) {
+                        order.auto_repay = true;
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn auto_repay(&self, fill: &Fill, kucoin: &KuCoin) {
+        let Some((base, quote)) = currencies(kucoin, fill.symbol()) else {
+            return;
+        };
+
+        let (currency, amount) = match fill.side() {
+            Side::Buy => (base, fill.size()),
+            Side::Sell => (quote, fill.funds() - fill.fee()),
+        };
+
+        let liability = self
+            .liabilities
+            .lock()
+            .unwrap()
+            .get(&currency)
+            .copied()
+            .unwrap_or_default();
+
+        if liability <= 0.0 {
+            return;
+        }
+
+        let body = LoanBody {
+            currency,
+            size: amount.min(liability).to_string(),
+        };
+
+        if let Err(e) = self.repay(body, kucoin) {
+            error!("{e}");
+        }
+    }
+
+    fn borrow(&self, body: LoanBody, kucoin: &KuCoin) -> Result<Value> {
+        let size: f64 = body.size.parse()?;
+
+        if size > self.max_borrow_size(&body.currency, kucoin) {
+            bail!("Insufficient margin to borrow {size} {}", body.currency);
+        }
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Margin, &body.currency, size, 0.0)?;
+
+        *self
+            .liabilities
+            .lock()
+            .unwrap()
+            .entry(body.currency)
+            .or_default() += size;
+
+        Ok(json!({
+            "orderNo": Uuid::new_v4().simple().to_string(),
+            "actualSize": size.to_string(),
+        }))
+    }
+
+    fn repay(&self, body: LoanBody, kucoin: &KuCoin) -> Result<Value> {
+        let mut liabilities = self.liabilities.lock().unwrap();
+
+        let liability = liabilities
+            .get(&body.currency)
+            .copied()
+            .filter(|liability| *liability > 0.0)
+            .ok_or_else(|| anyhow!("No {} liability to repay", body.currency))?;
+
+        let size = body.size.parse::<f64>()?.min(liability);
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Margin, &body.currency, -size, 0.0)?;
+
+        match liability - size {
+            remaining if remaining > f64::EPSILON => {
+                liabilities.insert(body.currency, remaining);
+            }
+            _ => {
+                liabilities.remove(&body.currency);
+            }
+        }
+
+        Ok(json!({
+            "orderNo": Uuid::new_v4().simple().to_string(),
+            "actualSize": size.to_string(),
+        }))
+    }
+
+    /// Cross margin allows borrowing up to (MAX_LEVERAGE - 1) times the account equity.
+    fn max_borrow_size(&self, currency: &str, kucoin: &KuCoin) -> f64 {
+        let (assets, liabilities) = self.margin_totals(kucoin);
+
+        let Some(price) = kucoin.spot().quote_price(currency) else {
+            return 0.0;
+        };
+
+        (((assets - liabilities) * (MAX_LEVERAGE - 1.0) - liabilities) / price).max(0.0)
+    }
+
+    fn margin_totals(&self, kucoin: &KuCoin) -> (f64, f64) {
+        let assets = kucoin
+            .accounts()
+            .currencies(&AccountType::Margin)
+            .iter()
+            .filter_map(|currency| {
+                let total = kucoin.accounts().total(&AccountType::Margin, currency)?;
+
+                kucoin
+                    .spot()
+                    .quote_price(currency)
+                    .map(|price| total * price)
+            })
+            .sum();
+
+        let liabilities = self
+            .liabilities()
+            .iter()
+            .filter_map(|(currency, liability)| {
+                kucoin
+                    .spot()
+                    .quote_price(currency)
+                    .map(|price| liability * price)
+            })
+            .sum();
+
+        (assets, liabilities)
+    }
+
+    fn margin_account(&self, kucoin: &KuCoin) -> Value {
+        let (assets, liabilities) = self.margin_totals(kucoin);
+
+        let mut currencies = kucoin.accounts().currencies(&AccountType::Margin);
+        currencies.extend(self.liabilities().into_keys());
+        currencies.sort();
+        currencies.dedup();
+
+        let accounts: Vec<Value> = currencies
+            .iter()
+            .map(|currency| {
+                let available = kucoin
+                    .accounts()
+                    .available(&AccountType::Margin, currency)
+                    .unwrap_or_default();
+                let total = kucoin
+                    .accounts()
+                    .total(&AccountType::Margin, currency)
+                    .unwrap_or_default();
+
+                json!({
+                    "currency": currency,
+                    "total": total.to_string(),
+                    "available": available.to_string(),
+                    "hold": (total - available).to_string(),
+                    "liability": self.liabilities().get(currency).copied().unwrap_or_default().to_string(),
+                    "maxBorrowSize": self.max_borrow_size(currency, kucoin).to_string(),
+                })
+            })
+            .collect();
+
+        let debt_ratio = match assets > 0.0 {
+            true => liabilities / assets,
+            false => 0.0,
+        };
+
+        json!({
+            "totalAssetOfQuoteCurrency": assets.to_string(),
+            "totalLiabilityOfQuoteCurrency": liabilities.to_string(),
+            "debtRatio": debt_ratio.to_string(),
+            "accounts": accounts,
+        })
+    }
+
+    fn lend(&self, body: LendBody, kucoin: &KuCoin) -> Result<Value> {
+        let size: f64 = body.size.parse()?;
+        let interest_rate = body.interest_rate.parse::<f64>()? * 100.0;
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Main, &body.currency, -size, 0.0)?;
+
+        let mut orders = kucoin.lending().orders();
+
+        let id = match orders.get_mut(&body.currency) {
+            Some(order) => {
+                order.set_purchase_size(order.purchase_size() + size);
+                order.purchase_order_no().to_string()
+            }
+            None => {
+                let id = Uuid::new_v4().simple().to_string();
+
+                orders.insert(
+                    body.currency.clone(),
+                    lending::Order::new(body.currency, id.clone(), size, interest_rate),
+                );
+
+                id
+            }
+        };
+
+        Ok(json!({ "orderNo": id }))
+    }
+
+    fn redeem(&self, body: RedeemBody, kucoin: &KuCoin) -> Result<Value> {
+        let size: f64 = body.size.parse()?;
+
+        let mut orders = kucoin.lending().orders();
+
+        let order = orders
+            .get_mut(&body.currency)
+            .filter(|order| order.purchase_order_no() == body.purchase_order_no)
+            .ok_or_else(|| anyhow!("Lending order {} not found", body.purchase_order_no))?;
+
+        let remaining = order.purchase_size() - size;
+
+        if remaining < -f64::EPSILON {
+            bail!("Redeem size exceeds lending amount");
+        }
+
+        match remaining > f64::EPSILON {
+            true => order.set_purchase_size(remaining),
+            false => {
+                orders.remove(&body.currency);
+            }
+        }
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Main, &body.currency, size, 0.0)?;
+
+        Ok(json!({ "orderNo": Uuid::new_v4().simple().to_string() }))
+    }
+
+    fn transfer(&self, body: TransferBody, kucoin: &KuCoin) -> Result<Value> {
+        if body.r#type != "INTERNAL" {
+            bail!("Sub-account transfers are not supported in paper trading");
+        }
+
+        let amount: f64 = body.amount.parse()?;
+        let from = account_type(&body.from_account_type)?;
+        let to = account_type(&body.to_account_type)?;
+
+        kucoin
+            .accounts()
+            .adjust(from, &body.currency, -amount, 0.0)?;
+        kucoin.accounts().adjust(to, &body.currency, amount, 0.0)?;
+
+        Ok(json!({ "orderId": body.client_oid }))
+    }
+
+    fn withdraw(&self, body: WithdrawBody, kucoin: &KuCoin) -> Result<Value> {
+        let amount: f64 = body.amount.parse()?;
+
+        kucoin
+            .accounts()
+            .adjust(AccountType::Main, &body.currency, -amount, 0.0)?;
+
+        Ok(json!({ "withdrawalId": Uuid::new_v4().simple().to_string() }))
+    }
+
+    fn orders(&self) -> MutexGuard<'_, Vec<Order>> {
+        self.orders.lock().unwrap()
+    }
+}
+
+impl Order {
+    fn details(&self) -> Value {
+        json!({
+            "id": self.id,
+            "clientOid": self.client_oid,
+            "symbol": self.symbol,
+            "type": Type::Limit,
+            "side": self.side,
+            "price": self.price.to_string(),
+            "size": self.size.to_string(),
+            "dealSize": "0",
+            "dealFunds": "0",
+            "fee": "0",
+            "active": true,
+        })
+    }
+}
+
+impl Poller for (Paper, KuCoin, Bot) {
+    async fn poll(&self, _: &Client) {
+        let (paper, kucoin, bot) = (&self.0, &self.1, &self.2);
+
+        for fill in paper.r#match(kucoin) {
+            if let Err(e) = bot
+                .send_message(
+                    env!("USER_ID").to_string(),
+                    format!("{SIMULATED}<b>Order filled</b>\n\n{fill}"),
+                )
+                .parse_mode(Html)
+                .await
+            {
+                error!("{e}")
+            }
+        }
+    }
+}
+
+fn currencies(kucoin: &KuCoin, symbol: &str) -> Option<(String, String)> {
+    kucoin.spot().symbols().get(symbol).map(|symbol| {
+        (
+            symbol.base_currency().to_string(),
+            symbol.quote_currency().to_string(),
+        )
+    })
+}
+
+fn parse(value: Option<&str>) -> Result<Option<f64>> {
+    Ok(value.map(|value| value.parse()).transpose()?)
+}
+
+fn account_type(value: &str) -> Result<AccountType> {
+    Ok(serde_json::from_value(Value::String(value.to_string()))?)
+}
@@ -6,18 +6,25 @@ use reqwest::{
     Client, Method,
 };
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use sha2::Sha256;
 use std::{
     borrow::Cow,
+    fmt,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::kucoin::{constants::BASE_URL, Response};
+use crate::kucoin::{
+    constants::{BASE_URL, FUTURES_BASE_URL},
+    Response,
+};
 
+#[derive(Debug)]
 pub struct Request {
     method: Method,
     path: Cow<'static, str>,
     json: String,
+    futures: bool,
 }
 
 impl Request {
@@ -29,6 +36,7 @@ impl Request {
             method,
             path: path.into(),
             json: Default::default(),
+            futures: false,
         }
     }
 
@@ -61,8 +69,44 @@ impl Request {
         self
     }
 
+    /// Sets the client order ID on JSON object bodies.
+    pub fn client_oid(mut self, client_oid: &str) -> Self {
+        if let Ok(Value::Object(mut body)) = serde_json::from_str(&self.json) {
+            body.insert("clientOid".to_string(), client_oid.into());
+            self.json = Value::Object(body).to_string();
+        }
+
+        self
+    }
+
+    pub fn futures(mut self) -> Self {
+        self.futures = true;
+        self
+    }
+
+    pub fn is_futures(&self) -> bool {
+        self.futures
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn body<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.json)?)
+    }
+
     pub async fn send<T: DeserializeOwned>(self, client: &Client) -> Result<T> {
-        let url = BASE_URL.to_string() + &self.path;
+        let base_url = match self.futures {
+            true => FUTURES_BASE_URL,
+            false => BASE_URL,
+        };
+
+        let url = base_url.to_string() + &self.path;
         let headers = (&self).into();
 
         let mut builder = client.request(self.method, url).headers(headers);
@@ -78,6 +122,12 @@ impl Request {
     }
 }
 
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.method, self.path, self.json)
+    }
+}
+
 impl From<&Request> for HeaderMap {
     fn from(req: &Request) -> Self {
         let timestamp = SystemTime::now()
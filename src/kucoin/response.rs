@@ -40,11 +40,17 @@ impl<T> IntoIterator for Paginated<T> {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
-    #[serde(alias = "orderNo")]
+    #[serde(alias = "orderNo", alias = "withdrawalId")]
     order_id: String,
     client_oid: Option<String>,
 }
 
+impl Order {
+    pub fn order_id(&self) -> &str {
+        &self.order_id
+    }
+}
+
 impl fmt::Display for Order {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let client_oid = match &self.client_oid {
@@ -0,0 +1,94 @@
+use reqwest::Client;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::error;
+
+use crate::kucoin::{
+    task::Poller,
+    trading::spot::order::{self, Details},
+    KuCoin,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct ActiveOrders(Arc<Mutex<HashMap<String, Details>>>);
+
+impl ActiveOrders {
+    pub fn get(&self, order_id: &str) -> Option<Details> {
+        self.lock().get(order_id).cloned()
+    }
+
+    pub fn all(&self) -> Vec<Details> {
+        let mut orders: Vec<Details> = self.lock().values().cloned().collect();
+
+        orders.sort_by(|a, b| a.symbol().cmp(b.symbol()).then(a.id().cmp(b.id())));
+        orders
+    }
+
+    pub fn remove(&self, order_id: &str) -> Option<Details> {
+        self.lock().remove(order_id)
+    }
+
+    pub fn replace(&self, orders: Vec<Details>) {
+        *self.lock() = orders
+            .into_iter()
+            .map(|order| (order.id().to_string(), order))
+            .collect();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Details>> {
+        self.0.lock().unwrap()
+    }
+}
+
+impl fmt::Display for ActiveOrders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let orders = self.all();
+
+        if orders.is_empty() {
+            return write!(f, "No open orders");
+        }
+
+        for (index, order) in orders.iter().enumerate() {
+            write!(f, "<b>{}.</b>\n{order}\n\n", index + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Poller for (ActiveOrders, KuCoin) {
+    async fn poll(&self, _: &Client) {
+        #[derive(Debug, Deserialize)]
+        struct Response {
+            symbols: Vec<String>,
+        }
+
+        let (active, kucoin) = (&self.0, &self.1);
+
+        let symbols = match kucoin.send::<Response>(order::active_symbols()).await {
+            Ok(r) => r.symbols,
+            Err(e) => {
+                error!("{e}");
+                return;
+            }
+        };
+
+        let mut orders = vec![];
+
+        for symbol in symbols {
+            match kucoin.send::<Vec<Details>>(order::active(&symbol)).await {
+                Ok(details) => orders.extend(details),
+                Err(e) => {
+                    error!("{e}");
+                    return;
+                }
+            }
+        }
+
+        active.replace(orders);
+    }
+}
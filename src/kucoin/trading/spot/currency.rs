@@ -49,4 +49,71 @@ impl Currency {
     pub fn precision(&self) -> u8 {
         self.precision
     }
+
+    pub fn chains(&self) -> &[Chain] {
+        self.chains.as_deref().unwrap_or_default()
+    }
+
+    /// Looks the chain up by its id or name.
+    pub fn chain(&self, chain: &str) -> Option<&Chain> {
+        self.chains().iter().find(|candidate| {
+            candidate.chain_id.eq_ignore_ascii_case(chain)
+                || candidate.chain_name.eq_ignore_ascii_case(chain)
+        })
+    }
+}
+
+impl Chain {
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn confirms(&self) -> u16 {
+        self.confirms
+    }
+
+    pub fn pre_confirms(&self) -> u16 {
+        self.pre_confirms
+    }
+
+    pub fn is_deposit_enabled(&self) -> bool {
+        self.is_deposit_enabled
+    }
+
+    pub fn is_withdraw_enabled(&self) -> bool {
+        self.is_withdraw_enabled
+    }
+
+    pub fn need_tag(&self) -> bool {
+        self.need_tag
+    }
+
+    pub fn withdrawal_min_size(&self) -> f64 {
+        parse(self.withdrawal_min_size.as_deref())
+    }
+
+    pub fn withdraw_precision(&self) -> u8 {
+        self.withdraw_precision
+    }
+
+    /// Fee for withdrawing `amount`, bounded by the chain's min and max fees.
+    pub fn withdrawal_fee(&self, amount: f64) -> f64 {
+        let fee = (amount * parse(self.withdraw_fee_rate.as_deref()))
+            .max(parse(self.withdrawal_min_fee.as_deref()));
+
+        match self
+            .withdraw_max_fee
+            .as_deref()
+            .and_then(|fee| fee.parse().ok())
+        {
+            Some(max_fee) if max_fee > 0.0 => fee.min(max_fee),
+            _ => fee,
+        }
+    }
+}
+
+fn parse(value: Option<&str>) -> f64 {
+    value
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
 }
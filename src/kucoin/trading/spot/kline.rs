@@ -0,0 +1,60 @@
+use anyhow::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::kucoin::{constants::SPOT_KLINES, Request};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Kline(String, String, String, String, String, String, String);
+
+impl Kline {
+    pub async fn history(
+        client: &Client,
+        symbol: &str,
+        r#type: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Self>> {
+        let mut klines: Vec<Self> = vec![];
+        let mut end = end;
+
+        while end > start {
+            let path =
+                format!("{SPOT_KLINES}?type={type}&symbol={symbol}&startAt={start}&endAt={end}");
+
+            let batch: Vec<Self> = Request::get(path).send(client).await?;
+
+            match batch.iter().map(|kline| kline.time()).min() {
+                Some(oldest) if oldest < end => end = oldest,
+                _ => break,
+            }
+
+            klines.extend(batch);
+        }
+
+        klines.sort_by_key(|kline| kline.time());
+        klines.dedup_by_key(|kline| kline.time());
+
+        Ok(klines)
+    }
+
+    pub fn time(&self) -> u64 {
+        self.0.parse().unwrap()
+    }
+
+    pub fn open(&self) -> f64 {
+        self.1.parse().unwrap()
+    }
+
+    pub fn close(&self) -> f64 {
+        self.2.parse().unwrap()
+    }
+
+    pub fn high(&self) -> f64 {
+        self.3.parse().unwrap()
+    }
+
+    pub fn low(&self) -> f64 {
+        self.4.parse().unwrap()
+    }
+}
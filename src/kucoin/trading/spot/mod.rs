@@ -9,26 +9,55 @@ use tracing::error;
 
 use crate::kucoin::{
     constants::{SPOT_CURRENCIES, SPOT_SYMBOLS, SPOT_TICKERS},
+    margin::QUOTE_CURRENCY,
     task::Poller,
     Request, WithRecent,
 };
 
+pub mod active;
+use active::ActiveOrders;
+
+pub mod oco;
+use oco::OcoTracker;
+
 pub mod order;
+use order::Type;
+
+pub mod stop;
+use stop::StopOrders;
 
 mod currency;
-use currency::Currency;
+pub use currency::Currency;
+
+mod kline;
+pub use kline::Kline;
 
 mod symbol;
-use symbol::Symbol;
+pub use symbol::Symbol;
 
 mod ticker;
-use ticker::Ticker;
+pub use ticker::Ticker;
+
+pub mod tracker;
+use tracker::Tracker;
+
+pub const KCS: &str = "KCS";
+pub const KCS_FEE_DISCOUNT: f64 = 0.2;
+
+/// Whether fees are paid in KCS, which KuCoin discounts while the KCS balance covers them.
+pub fn is_kcs_fee_deduction() -> bool {
+    matches!(option_env!("KCS_FEE_DEDUCTION"), Some("true" | "1"))
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct SpotTrading {
     currencies: Arc<Mutex<Currencies>>,
     symbols: Arc<Mutex<Symbols>>,
     tickers: Arc<Mutex<Tickers>>,
+    tracker: Tracker,
+    active: ActiveOrders,
+    stops: StopOrders,
+    oco: OcoTracker,
 }
 
 impl SpotTrading {
@@ -52,6 +81,48 @@ impl SpotTrading {
     pub fn tickers(&self) -> MutexGuard<'_, Tickers> {
         self.tickers_ref().lock().unwrap()
     }
+
+    pub fn tracker(&self) -> &Tracker {
+        &self.tracker
+    }
+
+    pub fn active(&self) -> &ActiveOrders {
+        &self.active
+    }
+
+    pub fn stops(&self) -> &StopOrders {
+        &self.stops
+    }
+
+    pub fn oco(&self) -> &OcoTracker {
+        &self.oco
+    }
+
+    /// Last price of the currency in the quote currency.
+    pub fn quote_price(&self, currency: &str) -> Option<f64> {
+        match currency {
+            QUOTE_CURRENCY => Some(1.0),
+            _ => self
+                .tickers()
+                .get(&format!("{currency}-{QUOTE_CURRENCY}"), false)
+                .and_then(|ticker| ticker.last()),
+        }
+    }
+
+    /// Fee rate of the order type. Limit orders are assumed to take liquidity
+    /// when that is more expensive.
+    pub fn fee_rate(&self, symbol: &str, r#type: Type) -> Option<f64> {
+        self.tickers()
+            .get(symbol, false)
+            .map(|ticker| match r#type {
+                Type::Market => ticker.taker_fee(),
+                Type::Limit => ticker.taker_fee().max(ticker.maker_fee()),
+            })
+    }
+
+    pub fn orders(&self) -> String {
+        format!("{}\n\n{}", self.active.to_string().trim_end(), self.stops)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,259 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use strum::Display;
+use teloxide::{
+    payloads::SendMessageSetters as _, prelude::Requester as _, types::ParseMode::Html, Bot,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::kucoin::{
+    constants::{SPOT_OCO_CLIENT_ORDER, SPOT_OCO_DETAILS, SPOT_OCO_ORDER},
+    task::Poller,
+    trading::spot::order::Side,
+    KuCoin, Request,
+};
+
+const RETENTION: u64 = 60 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddOco {
+    symbol: String,
+    side: Side,
+    price: String,
+    size: String,
+    stop_price: String,
+    limit_price: String,
+    trade_type: String,
+    client_oid: String,
+}
+
+impl AddOco {
+    pub fn new(
+        symbol: String,
+        side: Side,
+        size: f64,
+        price: f64,
+        stop_price: f64,
+        limit_price: f64,
+    ) -> Self {
+        Self {
+            symbol,
+            side,
+            price: price.to_string(),
+            size: size.to_string(),
+            stop_price: stop_price.to_string(),
+            limit_price: limit_price.to_string(),
+            trade_type: "TRADE".to_string(),
+            client_oid: Uuid::new_v4().simple().to_string(),
+        }
+    }
+
+    pub fn by_client_oid(client_oid: &str) -> Request {
+        Request::get(format!("{SPOT_OCO_CLIENT_ORDER}/{client_oid}"))
+    }
+}
+
+impl From<AddOco> for Request {
+    fn from(value: AddOco) -> Self {
+        Request::post(SPOT_OCO_ORDER).json(&value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum OcoStatus {
+    New,
+    Done,
+    Triggered,
+    Cancelled,
+}
+
+impl OcoStatus {
+    pub fn is_done(&self) -> bool {
+        !matches!(self, OcoStatus::New)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Leg {
+    Limit,
+    Stop,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcoLeg {
+    id: String,
+    side: Side,
+    price: String,
+    stop_price: String,
+    size: String,
+    status: OcoStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcoDetails {
+    order_id: String,
+    symbol: String,
+    status: OcoStatus,
+    #[serde(default)]
+    orders: Vec<OcoLeg>,
+}
+
+impl OcoDetails {
+    pub fn request(order_id: &str) -> Request {
+        Request::get(format!("{SPOT_OCO_DETAILS}/{order_id}"))
+    }
+
+    pub fn status(&self) -> OcoStatus {
+        self.status
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The first leg is the limit order, the second is the stop-limit order.
+    pub fn executed(&self) -> Option<(Leg, &OcoLeg)> {
+        self.orders
+            .iter()
+            .zip([Leg::Limit, Leg::Stop])
+            .find(|(order, _)| matches!(order.status, OcoStatus::Done | OcoStatus::Triggered))
+            .map(|(order, leg)| (leg, order))
+    }
+}
+
+impl fmt::Display for OcoDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Order ID:</b> {}\n<b>Symbol:</b> {}\n<b>Status:</b> {}",
+            self.order_id, self.symbol, self.status
+        )?;
+
+        if let Some((leg, order)) = self.executed() {
+            write!(
+                f,
+                "\n<b>Executed leg:</b> {leg}\n<b>Side:</b> {}\n<b>Price:</b> {}\n<b>Size:</b> {}",
+                order.side, order.price, order.size
+            )?;
+
+            if let Leg::Stop = leg {
+                write!(f, "\n<b>Stop price:</b> {}", order.stop_price)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn cancel(order_id: &str) -> Request {
+    Request::delete(format!("{SPOT_OCO_ORDER}/{order_id}"))
+}
+
+#[derive(Debug, Clone)]
+struct Tracked {
+    strategy: String,
+    status: OcoStatus,
+    updated: u64,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct OcoTracker(Arc<Mutex<HashMap<String, Tracked>>>);
+
+impl OcoTracker {
+    pub fn track(&self, order_id: String, strategy: String, time: u64) {
+        self.lock().insert(
+            order_id,
+            Tracked {
+                strategy,
+                status: OcoStatus::New,
+                updated: time,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Tracked>> {
+        self.0.lock().unwrap()
+    }
+}
+
+impl Poller for (OcoTracker, KuCoin, Bot) {
+    async fn poll(&self, _: &Client) {
+        let (tracker, kucoin, bot) = (&self.0, &self.1, &self.2);
+
+        let now = kucoin.now();
+
+        let open: Vec<(String, String)> = {
+            let mut lock = tracker.lock();
+
+            lock.retain(|_, tracked| {
+                !tracked.status.is_done() || now - tracked.updated < RETENTION
+            });
+
+            lock.iter()
+                .filter(|(_, tracked)| !tracked.status.is_done())
+                .map(|(order_id, tracked)| (order_id.clone(), tracked.strategy.clone()))
+                .collect()
+        };
+
+        for (order_id, strategy) in open {
+            let details = match kucoin
+                .send::<OcoDetails>(OcoDetails::request(&order_id))
+                .await
+            {
+                Ok(details) => details,
+                Err(e) => {
+                    error!("{e}");
+                    continue;
+                }
+            };
+
+            if !details.status().is_done() {
+                continue;
+            }
+
+            if let Some(entry) = tracker.lock().get_mut(&order_id) {
+                entry.status = details.status();
+                entry.updated = now;
+            }
+
+            // The executed leg is followed like any other order, to report its fill and fee.
+            if let Some((_, leg)) = details.executed() {
+                kucoin.spot().tracker().track_leg(
+                    leg.id.clone(),
+                    order_id.clone(),
+                    details.symbol().to_string(),
+                    strategy.clone(),
+                    now,
+                );
+            }
+
+            if let Err(e) = bot
+                .send_message(
+                    env!("USER_ID").to_string(),
+                    format!(
+                        "{}<b>Strategy:</b> {}\n\n<b>OCO order {}</b>\n\n{details}",
+                        kucoin.mode_tag(),
+                        strategy,
+                        details.status(),
+                    ),
+                )
+                .parse_mode(Html)
+                .await
+            {
+                error!("{e}")
+            }
+        }
+    }
+}
@@ -1,7 +1,15 @@
+use anyhow::{anyhow, bail, Result};
 use serde::{Deserialize, Serialize};
-use strum::Display;
+use std::{fmt, str::FromStr};
+use strum::{Display, EnumString};
 
-use crate::kucoin::{constants::SPOT_ORDER, Request};
+use crate::kucoin::{
+    constants::{
+        MARGIN_ORDERS, SPOT_ACTIVE_ORDERS, SPOT_ACTIVE_SYMBOLS, SPOT_CLIENT_ORDER, SPOT_ORDER,
+        SPOT_TRIGGERED_ORDER,
+    },
+    Request,
+};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -55,24 +63,185 @@ pub enum Side {
     Sell,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumString, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-enum SelfTradePrevention {
+#[strum(serialize_all = "UPPERCASE", ascii_case_insensitive)]
+pub enum SelfTradePrevention {
     Dc,
     Co,
     Cn,
     Cb,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Display, EnumString, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
-enum TimeInForce {
+#[strum(serialize_all = "UPPERCASE", ascii_case_insensitive)]
+pub enum TimeInForce {
     Gtc,
     Gtt,
     Ioc,
     Fok,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Options {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time_in_force: Option<TimeInForce>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cancel_after: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    post_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hidden: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iceberg: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    visible_size: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stp: Option<SelfTradePrevention>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remark: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tags: Option<String>,
+}
+
+impl Options {
+    pub fn is_empty(&self) -> bool {
+        *self == Options::default()
+    }
+
+    pub fn validate(&self, r#type: Type) -> Result<()> {
+        let is_flagged = |flag: Option<bool>| flag.unwrap_or_default();
+
+        if let Type::Market = r#type {
+            if self.time_in_force.is_some()
+                || self.cancel_after.is_some()
+                || is_flagged(self.post_only)
+                || is_flagged(self.hidden)
+                || is_flagged(self.iceberg)
+            {
+                bail!("only STP, remark and tags are supported on market orders");
+            }
+        }
+
+        match (self.time_in_force, self.cancel_after) {
+            (Some(TimeInForce::Gtt), Some(cancel_after)) if cancel_after > 0 => (),
+            (Some(TimeInForce::Gtt), _) => bail!("GTT requires a positive cancel after"),
+            (_, Some(_)) => bail!("cancel after is only supported with GTT"),
+            _ => (),
+        }
+
+        if is_flagged(self.post_only)
+            && matches!(
+                self.time_in_force,
+                Some(TimeInForce::Ioc | TimeInForce::Fok)
+            )
+        {
+            bail!("post only is not supported with IOC or FOK");
+        }
+
+        if is_flagged(self.hidden) && is_flagged(self.iceberg) {
+            bail!("order can't be both hidden and iceberg");
+        }
+
+        match (is_flagged(self.iceberg), self.visible_size) {
+            (true, Some(size)) if size > 0.0 => (),
+            (true, _) => bail!("iceberg requires a positive visible size"),
+            (false, Some(_)) => bail!("visible size is only supported on iceberg orders"),
+            (false, None) => (),
+        }
+
+        for (name, value) in [("remark", &self.remark), ("tags", &self.tags)] {
+            if value
+                .as_ref()
+                .is_some_and(|value| value.is_empty() || value.len() > 20 || !value.is_ascii())
+            {
+                bail!("{name} must be 1-20 ASCII characters");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Options {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut options = Options::default();
+
+        for token in s.split_whitespace() {
+            let (key, value) = match token.split_once('=') {
+                Some((key, value)) => (key.to_lowercase(), Some(value)),
+                None => (token.to_lowercase(), None),
+            };
+
+            match (key.as_str(), value) {
+                ("post_only", None) => options.post_only = Some(true),
+                ("hidden", None) => options.hidden = Some(true),
+                ("iceberg", Some(size)) => {
+                    options.iceberg = Some(true);
+                    options.visible_size = Some(size.parse()?);
+                }
+                ("cancel_after", Some(seconds)) => options.cancel_after = Some(seconds.parse()?),
+                ("stp", Some(stp)) => options.stp = Some(stp.parse()?),
+                ("remark", Some(remark)) => options.remark = Some(remark.to_string()),
+                ("tags", Some(tags)) => options.tags = Some(tags.to_string()),
+                (time_in_force, None) => {
+                    options.time_in_force = Some(
+                        time_in_force
+                            .parse()
+                            .map_err(|_| anyhow!("unknown order option {token}"))?,
+                    )
+                }
+                _ => bail!("unknown order option {token}"),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+impl fmt::Display for Options {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tokens = vec![];
+
+        if let Some(time_in_force) = self.time_in_force {
+            tokens.push(time_in_force.to_string());
+        }
+
+        if let Some(cancel_after) = self.cancel_after {
+            tokens.push(format!("CANCEL_AFTER={cancel_after}"));
+        }
+
+        if self.post_only.unwrap_or_default() {
+            tokens.push("POST_ONLY".to_string());
+        }
+
+        if self.hidden.unwrap_or_default() {
+            tokens.push("HIDDEN".to_string());
+        }
+
+        if let Some(visible_size) = self.visible_size {
+            tokens.push(format!("ICEBERG={visible_size}"));
+        }
+
+        if let Some(stp) = self.stp {
+            tokens.push(format!("STP={stp}"));
+        }
+
+        if let Some(remark) = &self.remark {
+            tokens.push(format!("REMARK={remark}"));
+        }
+
+        if let Some(tags) = &self.tags {
+            tokens.push(format!("TAGS={tags}"));
+        }
+
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
 impl Add {
     pub fn limit(symbol: String, side: Side, price: f64, size: f64) -> Self {
         Self {
@@ -102,6 +271,32 @@ impl Add {
             ..Default::default()
         }
     }
+
+    pub fn by_client_oid(client_oid: &str, symbol: &str) -> Request {
+        Request::get(format!("{SPOT_CLIENT_ORDER}/{client_oid}?symbol={symbol}"))
+    }
+
+    pub fn client_oid(self, client_oid: String) -> Self {
+        Self {
+            client_oid: Some(client_oid),
+            ..self
+        }
+    }
+
+    pub fn options(self, options: &Options) -> Self {
+        Self {
+            time_in_force: options.time_in_force,
+            cancel_after: options.cancel_after,
+            post_only: options.post_only,
+            hidden: options.hidden,
+            iceberg: options.iceberg,
+            visible_size: options.visible_size.map(|size| size.to_string()),
+            stp: options.stp,
+            remark: options.remark.clone(),
+            tags: options.tags.clone(),
+            ..self
+        }
+    }
 }
 
 impl From<Add> for Request {
@@ -109,3 +304,157 @@ impl From<Add> for Request {
         Request::post(SPOT_ORDER).json(&value)
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Details {
+    id: String,
+    #[serde(default)]
+    client_oid: Option<String>,
+    symbol: String,
+    r#type: Type,
+    side: Side,
+    price: String,
+    size: String,
+    deal_size: String,
+    deal_funds: String,
+    fee: String,
+    #[serde(default)]
+    fee_currency: String,
+    // Orders placed by triggered stops report it as `isActive`.
+    #[serde(alias = "isActive")]
+    active: bool,
+}
+
+impl Details {
+    pub fn request(order_id: &str, symbol: &str) -> Request {
+        Request::get(format!("{SPOT_ORDER}/{order_id}?symbol={symbol}"))
+    }
+
+    pub fn margin_request(order_id: &str, symbol: &str) -> Request {
+        Request::get(format!("{MARGIN_ORDERS}/{order_id}?symbol={symbol}"))
+    }
+
+    /// Order placed once a stop or OCO order triggered.
+    pub fn triggered_request(order_id: &str) -> Request {
+        Request::get(format!("{SPOT_TRIGGERED_ORDER}/{order_id}"))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn client_oid(&self) -> Option<&str> {
+        self.client_oid.as_deref()
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn r#type(&self) -> Type {
+        self.r#type
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn price(&self) -> f64 {
+        self.price.parse().unwrap_or_default()
+    }
+
+    pub fn size(&self) -> f64 {
+        self.size.parse().unwrap_or_default()
+    }
+
+    pub fn deal_size(&self) -> f64 {
+        self.deal_size.parse().unwrap_or_default()
+    }
+
+    pub fn deal_funds(&self) -> f64 {
+        self.deal_funds.parse().unwrap_or_default()
+    }
+
+    pub fn fee(&self) -> f64 {
+        self.fee.parse().unwrap_or_default()
+    }
+
+    /// Currency the fee was charged in, the quote currency unless paid in KCS.
+    pub fn fee_currency(&self) -> &str {
+        match self.fee_currency.is_empty() {
+            true => self.quote_currency(),
+            false => &self.fee_currency,
+        }
+    }
+
+    /// Quote spent on a buy or received from a sell, after fees charged in the quote currency.
+    pub fn net_funds(&self) -> f64 {
+        let fee = match self.fee_currency() == self.quote_currency() {
+            true => self.fee(),
+            false => 0.0,
+        };
+
+        match self.side {
+            Side::Buy => self.deal_funds() + fee,
+            Side::Sell => self.deal_funds() - fee,
+        }
+    }
+
+    fn quote_currency(&self) -> &str {
+        self.symbol.split('-').nth(1).unwrap_or_default()
+    }
+
+    pub fn deal_price(&self) -> Option<f64> {
+        let deal_size = self.deal_size();
+
+        (deal_size > 0.0).then(|| self.deal_funds() / deal_size)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn is_filled(&self) -> bool {
+        !self.active && self.deal_size() > 0.0
+    }
+}
+
+impl fmt::Display for Details {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<b>Order ID:</b> {}\n<b>Symbol:</b> {}\n<b>Side:</b> {}\n<b>Price:</b> {}\n<b>Filled:</b> {} / {}",
+            self.id, self.symbol, self.side, self.price, self.deal_size, self.size
+        )?;
+
+        if self.deal_size() > 0.0 {
+            write!(
+                f,
+                "\n<b>Fee:</b> {} {}\n<b>Net {}:</b> {} {}",
+                self.fee,
+                self.fee_currency(),
+                match self.side {
+                    Side::Buy => "cost",
+                    Side::Sell => "proceeds",
+                },
+                self.net_funds(),
+                self.quote_currency()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn cancel(order_id: &str, symbol: &str) -> Request {
+    Request::delete(format!("{SPOT_ORDER}/{order_id}?symbol={symbol}"))
+}
+
+pub fn active(symbol: &str) -> Request {
+    Request::get(format!("{SPOT_ACTIVE_ORDERS}?symbol={symbol}"))
+}
+
+pub fn active_symbols() -> Request {
+    Request::get(SPOT_ACTIVE_SYMBOLS)
+}
@@ -0,0 +1,191 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use strum::Display;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::kucoin::{
+    constants::{SPOT_STOP_CLIENT_ORDER, SPOT_STOP_ORDER},
+    response::Paginated,
+    task::Poller,
+    trading::spot::order::{Add, Side, Type},
+    KuCoin, Request,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Stop {
+    #[default]
+    Loss,
+    Entry,
+}
+
+impl Stop {
+    pub fn is_triggered(&self, last: f64, stop_price: f64) -> bool {
+        match self {
+            Stop::Loss => last <= stop_price,
+            Stop::Entry => last >= stop_price,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddStop {
+    #[serde(flatten)]
+    order: Add,
+    stop: Stop,
+    stop_price: String,
+}
+
+impl AddStop {
+    pub fn new(order: Add, stop: Stop, stop_price: f64) -> Self {
+        Self {
+            order: order.client_oid(Uuid::new_v4().simple().to_string()),
+            stop,
+            stop_price: stop_price.to_string(),
+        }
+    }
+
+    pub fn by_client_oid(client_oid: &str, symbol: &str) -> Request {
+        Request::get(format!(
+            "{SPOT_STOP_CLIENT_ORDER}?clientOid={client_oid}&symbol={symbol}"
+        ))
+    }
+}
+
+impl From<AddStop> for Request {
+    fn from(value: AddStop) -> Self {
+        Request::post(SPOT_STOP_ORDER).json(&value)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopOrder {
+    id: String,
+    symbol: String,
+    r#type: Type,
+    side: Side,
+    #[serde(default)]
+    price: Option<String>,
+    #[serde(default)]
+    size: Option<String>,
+    #[serde(default)]
+    funds: Option<String>,
+    stop: Stop,
+    stop_price: String,
+}
+
+impl StopOrder {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn stop(&self) -> Stop {
+        self.stop
+    }
+
+    pub fn stop_price(&self) -> f64 {
+        self.stop_price.parse().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for StopOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let amount = match (&self.size, &self.funds) {
+            (Some(size), _) if !size.is_empty() => size.clone(),
+            (_, Some(funds)) => format!("{funds} IN QUOTE"),
+            _ => "🚫".to_string(),
+        };
+
+        let price = match (self.r#type, &self.price) {
+            (Type::Limit, Some(price)) => price.clone(),
+            _ => Type::Market.to_string(),
+        };
+
+        write!(
+            f,
+            "<b>Order ID:</b> {}\n<b>Symbol:</b> {}\n<b>Side:</b> {}\n<b>Stop:</b> {} {}\n<b>Price:</b> {}\n<b>Size:</b> {}",
+            self.id, self.symbol, self.side, self.stop, self.stop_price, price, amount
+        )
+    }
+}
+
+pub fn list() -> Request {
+    Request::get(SPOT_STOP_ORDER)
+}
+
+pub fn cancel(order_id: &str) -> Request {
+    Request::delete(format!("{SPOT_STOP_ORDER}/{order_id}"))
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StopOrders(Arc<Mutex<HashMap<String, StopOrder>>>);
+
+impl StopOrders {
+    pub fn get(&self, order_id: &str) -> Option<StopOrder> {
+        self.lock().get(order_id).cloned()
+    }
+
+    pub fn all(&self) -> Vec<StopOrder> {
+        let mut orders: Vec<StopOrder> = self.lock().values().cloned().collect();
+
+        orders.sort_by(|a, b| a.symbol().cmp(b.symbol()).then(a.id().cmp(b.id())));
+        orders
+    }
+
+    pub fn remove(&self, order_id: &str) -> Option<StopOrder> {
+        self.lock().remove(order_id)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, StopOrder>> {
+        self.0.lock().unwrap()
+    }
+}
+
+impl fmt::Display for StopOrders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let orders = self.all();
+
+        if orders.is_empty() {
+            return write!(f, "No stop orders");
+        }
+
+        for (index, order) in orders.iter().enumerate() {
+            write!(f, "<b>S{}.</b>\n{order}\n\n", index + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Poller for (StopOrders, KuCoin) {
+    async fn poll(&self, _: &Client) {
+        let (stops, kucoin) = (&self.0, &self.1);
+
+        match kucoin.send::<Paginated<StopOrder>>(list()).await {
+            Ok(orders) => {
+                *stops.lock() = orders
+                    .into_iter()
+                    .map(|order| (order.id().to_string(), order))
+                    .collect()
+            }
+            Err(e) => error!("{e}"),
+        }
+    }
+}
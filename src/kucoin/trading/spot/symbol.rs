@@ -1,5 +1,11 @@
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::kucoin::{
+    is_on_increment,
+    trading::spot::order::{Side, Type},
+};
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Symbol {
@@ -46,6 +52,10 @@ impl Symbol {
         &self.quote_currency
     }
 
+    pub fn fee_currency(&self) -> &str {
+        &self.fee_currency
+    }
+
     pub fn base_min_size(&self) -> f64 {
         self.base_min_size.parse().unwrap()
     }
@@ -67,10 +77,115 @@ impl Symbol {
     }
 
     pub fn quote_increment(&self) -> f64 {
-        self.base_increment.parse().unwrap()
+        self.quote_increment.parse().unwrap()
     }
 
     pub fn price_increment(&self) -> f64 {
         self.price_increment.parse().unwrap()
     }
+
+    pub fn price_limit_rate(&self) -> f64 {
+        self.price_limit_rate.parse().unwrap_or_default()
+    }
+
+    pub fn min_funds(&self) -> Option<f64> {
+        self.min_funds.as_ref().and_then(|funds| funds.parse().ok())
+    }
+
+    /// Call auction runs until continuous trading starts.
+    fn is_in_call_auction(&self, now: u64) -> bool {
+        self.callauction_is_enabled && self.trading_start_time.is_some_and(|start| now < start)
+    }
+
+    pub fn check_trading(&self, r#type: Type, side: Side, now: u64) -> Result<()> {
+        if !self.enable_trading {
+            bail!("trading is disabled for {}", self.symbol);
+        }
+
+        if let Some(start) = self.trading_start_time.filter(|start| now < *start) {
+            if !self.callauction_is_enabled {
+                bail!("trading of {} starts at {start}", self.symbol);
+            }
+        }
+
+        if let (Type::Market, true) = (r#type, self.is_in_call_auction(now)) {
+            bail!(
+                "market orders are not accepted during the {} call auction",
+                self.symbol
+            );
+        }
+
+        if let (Side::Buy, true) = (side, self.st) {
+            bail!(
+                "{} is under special treatment, buying is blocked",
+                self.symbol
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Limit prices must follow the price increment, and orders placed right away
+    /// must also stay within the price protection range around the last price.
+    pub fn check_price(
+        &self,
+        side: Side,
+        price: f64,
+        last: Option<f64>,
+        protected: bool,
+        now: u64,
+    ) -> Result<()> {
+        let increment = self.price_increment();
+
+        if !is_on_increment(price, increment) {
+            bail!("price {price} is not a multiple of the price increment {increment}");
+        }
+
+        let rate = self.price_limit_rate();
+
+        if let Some(last) = last.filter(|_| protected && rate > 0.0) {
+            match side {
+                Side::Buy if price > last * (1.0 + rate) => bail!(
+                    "buy price {price} is above the limit of {} ({}% over last price)",
+                    last * (1.0 + rate),
+                    rate * 100.0
+                ),
+                Side::Sell if price < last * (1.0 - rate) => bail!(
+                    "sell price {price} is below the limit of {} ({}% under last price)",
+                    last * (1.0 - rate),
+                    rate * 100.0
+                ),
+                _ => (),
+            }
+        }
+
+        if self.is_in_call_auction(now) {
+            let floor = self
+                .callauction_price_floor
+                .as_ref()
+                .and_then(|floor| floor.parse::<f64>().ok());
+            let ceiling = self
+                .callauction_price_ceiling
+                .as_ref()
+                .and_then(|ceiling| ceiling.parse::<f64>().ok());
+
+            if let (Some(floor), Some(ceiling)) = (floor, ceiling) {
+                if !(floor..=ceiling).contains(&price) {
+                    bail!("price {price} is outside the call auction range {floor} - {ceiling}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_funds(&self, funds: f64) -> Result<()> {
+        match self.min_funds() {
+            Some(min_funds) if funds < min_funds => bail!(
+                "order value {funds} {} is below the minimum of {min_funds}",
+                self.quote_currency
+            ),
+            _ => Ok(()),
+        }
+    }
 }
@@ -25,6 +25,31 @@ pub struct Ticker {
 }
 
 impl Ticker {
+    pub fn new(symbol: String, price: f64, taker_fee_rate: f64, maker_fee_rate: f64) -> Self {
+        let price = Some(price.to_string());
+
+        Self {
+            symbol_name: symbol.clone(),
+            symbol,
+            buy: price.clone(),
+            best_bid_size: Default::default(),
+            sell: price.clone(),
+            best_ask_size: Default::default(),
+            change_rate: Default::default(),
+            change_price: Default::default(),
+            high: Default::default(),
+            low: Default::default(),
+            vol: Default::default(),
+            vol_value: Default::default(),
+            last: price,
+            average_price: Default::default(),
+            taker_fee_rate: taker_fee_rate.to_string(),
+            maker_fee_rate: maker_fee_rate.to_string(),
+            taker_coefficient: "1".to_string(),
+            maker_coefficient: "1".to_string(),
+        }
+    }
+
     pub fn symbol(&self) -> &str {
         &self.symbol
     }
@@ -33,6 +58,22 @@ impl Ticker {
         self.last.as_ref().and_then(|last| last.parse().ok())
     }
 
+    pub fn best_bid(&self) -> Option<f64> {
+        self.buy.as_ref().and_then(|buy| buy.parse().ok())
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.sell.as_ref().and_then(|sell| sell.parse().ok())
+    }
+
+    pub fn taker_fee(&self) -> f64 {
+        self.taker_fee_rate.parse::<f64>().unwrap() * self.taker_coefficient.parse::<f64>().unwrap()
+    }
+
+    pub fn maker_fee(&self) -> f64 {
+        self.maker_fee_rate.parse::<f64>().unwrap() * self.maker_coefficient.parse::<f64>().unwrap()
+    }
+
     fn change_rate(&self) -> Option<f64> {
         self.change_rate
             .as_ref()
@@ -0,0 +1,243 @@
+use reqwest::Client;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use strum::Display;
+use teloxide::{
+    payloads::SendMessageSetters as _, prelude::Requester as _, types::ParseMode::Html, Bot,
+};
+use tracing::error;
+
+use crate::kucoin::{
+    task::Poller,
+    trading::spot::order::{Details, Type},
+    KuCoin,
+};
+
+const RETENTION: u64 = 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Status {
+    Open,
+    #[strum(serialize = "PARTIALLY FILLED")]
+    PartiallyFilled,
+    Filled,
+    Canceled,
+}
+
+impl Status {
+    pub fn is_done(&self) -> bool {
+        matches!(self, Status::Filled | Status::Canceled)
+    }
+}
+
+/// Where the details of a tracked order are queried.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+    Spot,
+    Margin,
+    /// Orders that only exist once a stop or OCO order triggered.
+    Triggered,
+}
+
+#[derive(Debug, Clone)]
+struct Tracked {
+    symbol: String,
+    strategy: String,
+    /// Order the strategy placed, which differs from the tracked one for OCO legs.
+    placed: String,
+    source: Source,
+    status: Status,
+    deal_size: f64,
+    updated: u64,
+}
+
+/// Currency and amount of a fee.
+type Fee = (String, f64);
+
+#[derive(Debug, Default, Clone)]
+pub struct Tracker {
+    orders: Arc<Mutex<HashMap<String, Tracked>>>,
+    fees: Arc<Mutex<Vec<(String, Fee)>>>,
+}
+
+impl Tracker {
+    pub fn track(
+        &self,
+        order_id: String,
+        symbol: String,
+        strategy: String,
+        time: u64,
+        source: Source,
+    ) {
+        self.insert(order_id.clone(), order_id, symbol, strategy, time, source);
+    }
+
+    /// Tracks the executed leg of an OCO order, reporting its fee under the OCO order.
+    pub fn track_leg(
+        &self,
+        leg_id: String,
+        order_id: String,
+        symbol: String,
+        strategy: String,
+        time: u64,
+    ) {
+        self.insert(leg_id, order_id, symbol, strategy, time, Source::Triggered);
+    }
+
+    /// Actual fees of the orders done since the last call, by the order the strategy placed.
+    pub fn fees(&self) -> Vec<(String, Fee)> {
+        self.fees.lock().unwrap().drain(..).collect()
+    }
+
+    fn insert(
+        &self,
+        order_id: String,
+        placed: String,
+        symbol: String,
+        strategy: String,
+        time: u64,
+        source: Source,
+    ) {
+        self.lock().insert(
+            order_id,
+            Tracked {
+                symbol,
+                strategy,
+                placed,
+                source,
+                status: Status::Open,
+                deal_size: Default::default(),
+                updated: time,
+            },
+        );
+    }
+
+    pub fn status(&self, order_id: &str) -> Option<Status> {
+        self.lock().get(order_id).map(|tracked| tracked.status)
+    }
+
+    pub fn strategy(&self, order_id: &str) -> Option<String> {
+        self.lock()
+            .get(order_id)
+            .map(|tracked| tracked.strategy.clone())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Tracked>> {
+        self.orders.lock().unwrap()
+    }
+}
+
+impl Tracker {
+    /// Updates tracked orders from their details and returns the changes to report.
+    pub async fn refresh(&self, kucoin: &KuCoin) -> Vec<String> {
+        let now = kucoin.now();
+        let mut messages = Vec::new();
+
+        let open: Vec<(String, Tracked)> = {
+            let mut lock = self.lock();
+
+            lock.retain(|_, tracked| {
+                !tracked.status.is_done() || now - tracked.updated < RETENTION
+            });
+
+            lock.iter()
+                .filter(|(_, tracked)| !tracked.status.is_done())
+                .map(|(order_id, tracked)| (order_id.clone(), tracked.clone()))
+                .collect()
+        };
+
+        for (order_id, tracked) in open {
+            let request = match tracked.source {
+                Source::Spot => Details::request(&order_id, &tracked.symbol),
+                Source::Margin => Details::margin_request(&order_id, &tracked.symbol),
+                Source::Triggered if kucoin.spot().stops().get(&order_id).is_some() => {
+                    if let Some(entry) = self.lock().get_mut(&order_id) {
+                        entry.updated = now;
+                    }
+
+                    continue;
+                }
+                Source::Triggered => Details::triggered_request(&order_id),
+            };
+
+            let details = match kucoin.send::<Details>(request).await {
+                Ok(details) => details,
+                // Stops may not be listed yet, or were canceled before they triggered.
+                Err(_) if matches!(tracked.source, Source::Triggered) => {
+                    if now - tracked.updated >= RETENTION {
+                        self.lock().remove(&order_id);
+                    }
+
+                    continue;
+                }
+                Err(e) => {
+                    error!("{e}");
+                    continue;
+                }
+            };
+
+            let status = match (details.is_active(), details.is_filled()) {
+                (true, _) if details.deal_size() > 0.0 => Status::PartiallyFilled,
+                (true, _) => Status::Open,
+                (false, true) if details.deal_size() >= details.size() => Status::Filled,
+                (false, _) => Status::Canceled,
+            };
+
+            let is_changed = status != tracked.status || details.deal_size() > tracked.deal_size;
+
+            if !is_changed {
+                continue;
+            }
+
+            if let Some(entry) = self.lock().get_mut(&order_id) {
+                entry.status = status;
+                entry.deal_size = details.deal_size();
+                entry.updated = now;
+            }
+
+            if status.is_done() && details.deal_size() > 0.0 {
+                self.fees.lock().unwrap().push((
+                    tracked.placed.clone(),
+                    (details.fee_currency().to_string(), details.fee()),
+                ));
+            }
+
+            let is_reported = match kucoin.is_simulated() {
+                true => status != Status::Filled,
+                false => true,
+            };
+
+            let is_market = matches!(details.r#type(), Type::Market);
+
+            if !is_reported || (is_market && !status.is_done()) {
+                continue;
+            }
+
+            messages.push(format!(
+                "{}<b>Strategy:</b> {}\n\n<b>Order {status}</b>\n\n{details}",
+                kucoin.mode_tag(),
+                tracked.strategy,
+            ));
+        }
+
+        messages
+    }
+}
+
+impl Poller for (Tracker, KuCoin, Bot) {
+    async fn poll(&self, _: &Client) {
+        for text in self.0.refresh(&self.1).await {
+            if let Err(e) = self
+                .2
+                .send_message(env!("USER_ID").to_string(), text)
+                .parse_mode(Html)
+                .await
+            {
+                error!("{e}")
+            }
+        }
+    }
+}
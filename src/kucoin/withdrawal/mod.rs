@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::kucoin::{
+    constants::{WITHDRAWALS, WITHDRAWAL_HISTORY},
+    Request,
+};
+
+mod whitelist;
+pub use whitelist::{Address, Whitelist};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdraw {
+    currency: String,
+    to_address: String,
+    amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memo: Option<String>,
+    is_inner: bool,
+    chain: String,
+    fee_deduct_type: String,
+    withdraw_type: String,
+}
+
+impl Withdraw {
+    /// Fees are deducted from the withdrawn amount.
+    pub fn new(amount: f64, address: &Address) -> Self {
+        Self {
+            currency: address.currency().to_string(),
+            to_address: address.address().to_string(),
+            amount: amount.to_string(),
+            memo: address.memo().map(|memo| memo.to_string()),
+            is_inner: false,
+            chain: address.chain().to_string(),
+            fee_deduct_type: "INTERNAL".to_string(),
+            withdraw_type: "ADDRESS".to_string(),
+        }
+    }
+
+    pub fn history(currency: &str, since: u64) -> Request {
+        Request::get(format!(
+            "{WITHDRAWAL_HISTORY}?currency={currency}&startAt={since}"
+        ))
+    }
+}
+
+impl From<Withdraw> for Request {
+    fn from(value: Withdraw) -> Self {
+        Request::post(WITHDRAWALS).json(&value)
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, sync::Arc};
+use tracing::error;
+
+const PATH: &str = match option_env!("WHITELIST_PATH") {
+    Some(path) => path,
+    None => "whitelist.json",
+};
+
+#[derive(Debug, Clone)]
+pub struct Whitelist(Arc<Vec<Address>>);
+
+impl Default for Whitelist {
+    fn default() -> Self {
+        let addresses = match fs::read_to_string(PATH) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                error!("{PATH}: {e}");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self(Arc::new(addresses))
+    }
+}
+
+impl Whitelist {
+    pub fn get(&self, name: &str) -> Option<&Address> {
+        self.0.iter().find(|address| address.name == name)
+    }
+
+    pub fn names(&self, currency: &str, chain: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|address| address.currency == currency && address.chain == chain)
+            .map(|address| address.name.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Address {
+    name: String,
+    currency: String,
+    chain: String,
+    address: String,
+    #[serde(default)]
+    memo: Option<String>,
+}
+
+impl Address {
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    pub fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+}
@@ -1,3 +1,4 @@
+pub mod backtest;
 pub mod kucoin;
 pub mod strategies;
 pub mod telegram;
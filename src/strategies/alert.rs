@@ -0,0 +1,88 @@
+use serde_json::{json, Value};
+
+use crate::{kucoin::KuCoin, strategies::strategy::Strategy};
+
+/// Actions that report to the user or other systems instead of trading.
+#[derive(Debug)]
+pub enum Alert {
+    Notify(String),
+    Webhook(String),
+}
+
+/// Strategy state captured when its alerts are dispatched.
+#[derive(Debug)]
+pub struct Context {
+    strategy: String,
+    product: Option<String>,
+    value: Option<f64>,
+}
+
+impl Context {
+    pub fn new(strategy: &Strategy, kucoin: &KuCoin) -> Self {
+        Self {
+            strategy: strategy.name().to_string(),
+            product: strategy.product().map(|product| product.to_string()),
+            value: strategy.value(kucoin),
+        }
+    }
+}
+
+impl Alert {
+    /// Rendered message for notifications and the JSON body for webhooks.
+    pub fn payload(&self, context: &Context, kucoin: &KuCoin) -> String {
+        match self {
+            Self::Notify(template) => render(template, context, kucoin),
+            Self::Webhook(event) => {
+                let balances: serde_json::Map<String, Value> = kucoin
+                    .accounts()
+                    .balances()
+                    .into_iter()
+                    .filter(|(_, balance)| *balance > 0.0)
+                    .map(|(currency, balance)| (currency, json!(balance)))
+                    .collect();
+
+                json!({
+                    "event": event,
+                    "strategy": context.strategy,
+                    "product": context.product,
+                    "value": context.value,
+                    "balances": balances,
+                    "simulated": kucoin.is_simulated(),
+                    "timestamp": kucoin.now(),
+                })
+                .to_string()
+            }
+        }
+    }
+}
+
+fn render(template: &str, context: &Context, kucoin: &KuCoin) -> String {
+    let mut message = template
+        .replace("{strategy}", &context.strategy)
+        .replace("{product}", context.product.as_deref().unwrap_or("🚫"))
+        .replace(
+            "{value}",
+            &context
+                .value
+                .map_or_else(|| "🚫".to_string(), |value| value.to_string()),
+        );
+
+    while let Some(start) = message.find("{balance:") {
+        let Some(end) = message[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+
+        let currency = message[start + "{balance:".len()..end].to_uppercase();
+
+        let balance = kucoin
+            .accounts()
+            .balances()
+            .get(&currency)
+            .copied()
+            .unwrap_or_default();
+
+        message.replace_range(start..=end, &balance.to_string());
+    }
+
+    message
+}
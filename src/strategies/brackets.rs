@@ -0,0 +1,489 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    mem,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use teloxide::{
+    payloads::SendMessageSetters as _, prelude::Requester as _, types::ParseMode::Html, Bot,
+};
+use tracing::error;
+
+use crate::{
+    kucoin::{
+        response::Order,
+        task::Poller,
+        trading::spot::order::{self, Add, Details, Side},
+        with_increment, KuCoin,
+    },
+    strategies::{risk::Risk, strategy::Action},
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Level {
+    Price(f64),
+    Percent(f64),
+}
+
+impl Level {
+    fn price(&self, entry: f64, up: bool) -> f64 {
+        match (self, up) {
+            (Level::Price(price), _) => *price,
+            (Level::Percent(percent), true) => entry * (1.0 + percent / 100.0),
+            (Level::Percent(percent), false) => entry * (1.0 - percent / 100.0),
+        }
+    }
+
+    pub fn validate(&self, up: bool) -> Result<()> {
+        match self {
+            Level::Price(price) if *price > 0.0 => Ok(()),
+            Level::Percent(percent) if *percent > 0.0 && (up || *percent < 100.0) => Ok(()),
+            _ => Err(anyhow!("invalid bracket level {self}")),
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().strip_suffix('%') {
+            Some(percent) => Ok(Level::Percent(percent.trim().parse()?)),
+            None => Ok(Level::Price(s.trim().parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Price(price) => write!(f, "{price}"),
+            Level::Percent(percent) => write!(f, "{percent}%"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Bracket {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    take_profit: Option<Level>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stop_loss: Option<Level>,
+}
+
+impl Bracket {
+    pub fn take_profit_mut(&mut self) -> &mut Option<Level> {
+        &mut self.take_profit
+    }
+
+    pub fn stop_loss_mut(&mut self) -> &mut Option<Level> {
+        &mut self.stop_loss
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(level) = &self.take_profit {
+            level.validate(true)?;
+        }
+
+        if let Some(level) = &self.stop_loss {
+            level.validate(false)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Bracket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels: Vec<String> = [("TP", &self.take_profit), ("SL", &self.stop_loss)]
+            .into_iter()
+            .filter_map(|(name, level)| level.map(|level| format!("{name} {level}")))
+            .collect();
+
+        write!(f, "{}", levels.join(" "))
+    }
+}
+
+const PATH: &str = match option_env!("BRACKETS_PATH") {
+    Some(path) => path,
+    None => "brackets.jsonl",
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Position {
+    strategy: String,
+    symbol: String,
+    bracket: Bracket,
+    stage: Stage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    Entry {
+        order_id: String,
+    },
+    Exit {
+        size: f64,
+        take_profit: Option<(f64, String)>,
+        stop_loss: Option<f64>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Brackets {
+    positions: Arc<Mutex<Vec<Position>>>,
+    persistent: bool,
+}
+
+impl Default for Brackets {
+    fn default() -> Self {
+        let positions = fs::read_to_string(PATH)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            positions: Arc::new(Mutex::new(positions)),
+            persistent: true,
+        }
+    }
+}
+
+impl Brackets {
+    /// Brackets that are never written to disk, for backtests.
+    pub fn in_memory() -> Self {
+        Self {
+            positions: Default::default(),
+            persistent: false,
+        }
+    }
+
+    pub fn open(&self, strategy: String, symbol: String, order_id: String, bracket: Bracket) {
+        let position = Position {
+            strategy,
+            symbol,
+            bracket,
+            stage: Stage::Entry { order_id },
+        };
+
+        let mut positions = self.lock();
+
+        if self.persistent {
+            let result = serde_json::to_string(&position)
+                .map_err(Into::into)
+                .and_then(|line| {
+                    OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(PATH)
+                        .and_then(|mut file| writeln!(file, "{line}"))
+                });
+
+            if let Err(e) = result {
+                error!("{PATH}: {e}");
+            }
+        }
+
+        positions.push(position);
+    }
+
+    /// Rewrites the file with the positions still open, as their stages change.
+    fn save(&self, positions: &[Position]) {
+        if !self.persistent {
+            return;
+        }
+
+        let content: String = positions
+            .iter()
+            .filter_map(|position| serde_json::to_string(position).ok())
+            .map(|line| line + "\n")
+            .collect();
+
+        if let Err(e) = fs::write(PATH, content) {
+            error!("{PATH}: {e}");
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Position>> {
+        self.positions.lock().unwrap()
+    }
+}
+
+impl Position {
+    async fn advance(self, kucoin: &KuCoin, risk: &Risk) -> (Option<Position>, Option<String>) {
+        let Some((base_increment, price_increment)) = kucoin
+            .spot()
+            .symbols()
+            .get(&self.symbol)
+            .map(|symbol| (symbol.base_increment(), symbol.price_increment()))
+        else {
+            return (Some(self), None);
+        };
+
+        match &self.stage {
+            Stage::Entry { order_id } => {
+                let details = match kucoin
+                    .send::<Details>(Details::request(order_id, &self.symbol))
+                    .await
+                {
+                    Ok(details) if details.is_active() => return (Some(self), None),
+                    Ok(details) => details,
+                    Err(e) => {
+                        error!("{e}");
+                        return (Some(self), None);
+                    }
+                };
+
+                let Some(entry) = details.deal_price() else {
+                    return (None, Some("Entry order canceled".to_string()));
+                };
+
+                let size = with_increment(details.deal_size(), base_increment);
+
+                let stop_loss = self
+                    .bracket
+                    .stop_loss
+                    .map(|level| with_increment(level.price(entry, false), price_increment));
+
+                let mut text = format!("Entry filled @ {entry}");
+
+                let take_profit = match self.bracket.take_profit {
+                    Some(level) => {
+                        let price = with_increment(level.price(entry, true), price_increment);
+
+                        let placed = match self.check(kucoin, risk, Some(price), size) {
+                            Ok(exposure) => {
+                                self.exit(kucoin, risk, exposure, Some(price), size).await
+                            }
+                            Err(e) => Err(e),
+                        };
+
+                        match placed {
+                            Ok(order) => {
+                                text.push_str(&format!("\nTake profit placed @ {price}"));
+                                Some((price, order.order_id().to_string()))
+                            }
+                            Err(e) => {
+                                text.push_str(&format!("\nTake profit ❌ {e}"));
+                                None
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                if let Some(stop_loss) = stop_loss {
+                    text.push_str(&format!("\nStop loss armed @ {stop_loss}"));
+                }
+
+                let position = (take_profit.is_some() || stop_loss.is_some()).then_some(Position {
+                    stage: Stage::Exit {
+                        size,
+                        take_profit,
+                        stop_loss,
+                    },
+                    ..self
+                });
+
+                (position, Some(text))
+            }
+            Stage::Exit {
+                size,
+                take_profit,
+                stop_loss,
+            } => {
+                let (mut size, take_profit, stop_loss) = (*size, take_profit.clone(), *stop_loss);
+
+                let last = kucoin
+                    .spot()
+                    .tickers()
+                    .get(&self.symbol, false)
+                    .and_then(|ticker| ticker.last());
+
+                let triggered = stop_loss
+                    .zip(last)
+                    .is_some_and(|(stop_loss, last)| last <= stop_loss);
+
+                if let Some((price, order_id)) = &take_profit {
+                    // The take profit is canceled before selling, so the exit is sized from
+                    // what it actually filled. A failed cancel is only logged, since the
+                    // order may already be done.
+                    if triggered {
+                        if let Err(e) = kucoin
+                            .send::<Order>(order::cancel(order_id, &self.symbol))
+                            .await
+                        {
+                            error!("{e}");
+                        }
+                    }
+
+                    let details = match kucoin
+                        .send::<Details>(Details::request(order_id, &self.symbol))
+                        .await
+                    {
+                        Ok(details) if details.is_active() => return (Some(self), None),
+                        Ok(details) => details,
+                        Err(e) => {
+                            error!("{e}");
+                            return (Some(self), None);
+                        }
+                    };
+
+                    size = with_increment(size - details.deal_size(), base_increment);
+
+                    if size <= 0.0 {
+                        return (None, Some(format!("Take profit filled @ {price}")));
+                    }
+
+                    if !triggered {
+                        let text = format!(
+                            "⚠️ Take profit order canceled with {} filled",
+                            details.deal_size()
+                        );
+
+                        return match stop_loss {
+                            Some(stop_loss) => (
+                                Some(Position {
+                                    stage: Stage::Exit {
+                                        size,
+                                        take_profit: None,
+                                        stop_loss: Some(stop_loss),
+                                    },
+                                    ..self
+                                }),
+                                Some(format!("{text}\nStop loss kept @ {stop_loss} for {size}")),
+                            ),
+                            None => (None, Some(format!("{text}\n{size} left unprotected"))),
+                        };
+                    }
+                }
+
+                let (Some(last), true) = (last, triggered) else {
+                    return (Some(self), None);
+                };
+
+                // A blocked exit is held, with the take profit already canceled, and retried.
+                let exposure = match self.check(kucoin, risk, None, size) {
+                    Ok(exposure) => exposure,
+                    Err(e) => {
+                        let text = format!("Stop loss triggered @ {last}\n🚫 {e}");
+                        let report = risk.report(&self.strategy, &e.to_string(), kucoin.now());
+
+                        let position = Position {
+                            stage: Stage::Exit {
+                                size,
+                                take_profit: None,
+                                stop_loss,
+                            },
+                            ..self
+                        };
+
+                        return (Some(position), report.then_some(text));
+                    }
+                };
+
+                let text = match self.exit(kucoin, risk, exposure, None, size).await {
+                    Ok(order) => format!("Stop loss triggered @ {last}\n{order}"),
+                    Err(e) => format!("Stop loss triggered @ {last}\n❌ {e}"),
+                };
+
+                (None, Some(text))
+            }
+        }
+    }
+
+    /// Checks an exit order against the trading rules and risk limits.
+    fn check(
+        &self,
+        kucoin: &KuCoin,
+        risk: &Risk,
+        price: Option<f64>,
+        size: f64,
+    ) -> Result<Option<(String, f64)>> {
+        let action = Action::exit(self.symbol.clone(), price, size);
+
+        action
+            .check_trade(kucoin)
+            .map_err(|e| anyhow!("rejected by trading rules\n{e}"))?;
+
+        risk.check(&action, kucoin)
+            .map_err(|e| anyhow!("blocked by risk limits\n{e}"))
+    }
+
+    /// Sends a checked exit order and records it against the risk limits.
+    async fn exit(
+        &self,
+        kucoin: &KuCoin,
+        risk: &Risk,
+        exposure: Option<(String, f64)>,
+        price: Option<f64>,
+        size: f64,
+    ) -> Result<Order> {
+        let request = match price {
+            Some(price) => Add::limit(self.symbol.clone(), Side::Sell, price, size),
+            None => Add::market(self.symbol.clone(), Side::Sell, size),
+        };
+
+        let order = kucoin.send::<Order>(request.into()).await?;
+        risk.record(exposure, kucoin.now());
+
+        Ok(order)
+    }
+}
+
+impl Poller for (Brackets, Risk, Bot, KuCoin) {
+    async fn poll(&self, _: &Client) {
+        let (brackets, risk, bot, kucoin) = (&self.0, &self.1, &self.2, &self.3);
+
+        let positions = mem::take(&mut *brackets.lock());
+        let mut remaining = vec![];
+        let mut changed = false;
+
+        for position in positions {
+            let header = format!(
+                "{}<b>Strategy:</b> {}\n\n<b>Bracket:</b> {} {}\n\n",
+                kucoin.mode_tag(),
+                position.strategy,
+                position.symbol,
+                position.bracket
+            );
+
+            let before = serde_json::to_string(&position).ok();
+            let (position, maybe_text) = position.advance(kucoin, risk).await;
+
+            changed |= position
+                .as_ref()
+                .and_then(|p| serde_json::to_string(p).ok())
+                != before;
+            remaining.extend(position);
+
+            if let Some(text) = maybe_text {
+                if let Err(e) = bot
+                    .send_message(env!("USER_ID").to_string(), header + &text)
+                    .parse_mode(Html)
+                    .await
+                {
+                    error!("{e}")
+                }
+            }
+        }
+
+        let mut positions = brackets.lock();
+        positions.extend(remaining);
+
+        if changed {
+            brackets.save(&positions);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard},
+};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+use uuid::Uuid;
+
+use crate::strategies::strategy::Action;
+
+pub const CONFIRM: &str = "confirm:";
+pub const REJECT: &str = "reject:";
+
+/// Requests not answered within this time are discarded.
+pub const TIMEOUT: u64 = 10 * 60 * 1000;
+
+#[derive(Debug)]
+pub struct Confirmation {
+    strategy: String,
+    index: usize,
+    action: Action,
+    amount: Option<f64>,
+    payload: String,
+    text: String,
+    deadline: u64,
+    fee: Option<(String, f64)>,
+}
+
+impl Confirmation {
+    pub fn new(
+        strategy: String,
+        index: usize,
+        action: Action,
+        amount: Option<f64>,
+        payload: String,
+        text: String,
+        deadline: u64,
+    ) -> Self {
+        Self {
+            strategy,
+            index,
+            action,
+            amount,
+            payload,
+            text,
+            deadline,
+            fee: None,
+        }
+    }
+
+    /// Sets the fee estimated when approval was requested, before any balance changes.
+    pub fn with_fee(self, fee: Option<(String, f64)>) -> Self {
+        Self { fee, ..self }
+    }
+
+    pub fn strategy(&self) -> &str {
+        &self.strategy
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn amount(&self) -> Option<f64> {
+        self.amount
+    }
+
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    pub fn fee(&self) -> Option<(String, f64)> {
+        self.fee.clone()
+    }
+}
+
+/// Requests held back until the user approves them in Telegram.
+#[derive(Debug, Default, Clone)]
+pub struct Confirmations {
+    pending: Arc<Mutex<HashMap<String, Confirmation>>>,
+    /// Strategies whose request was rejected or timed out, not prompted
+    /// again until their condition re-arms.
+    suppressed: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Confirmations {
+    pub fn add(&self, confirmation: Confirmation) -> InlineKeyboardMarkup {
+        let id = Uuid::new_v4().simple().to_string();
+
+        let markup = InlineKeyboardMarkup::new([[
+            InlineKeyboardButton::callback("✅ Approve", format!("{CONFIRM}{id}")),
+            InlineKeyboardButton::callback("❌ Reject", format!("{REJECT}{id}")),
+        ]]);
+
+        self.lock().insert(id, confirmation);
+
+        markup
+    }
+
+    pub fn take(&self, id: &str) -> Option<Confirmation> {
+        self.lock().remove(id)
+    }
+
+    pub fn is_pending(&self, strategy: &str) -> bool {
+        self.lock()
+            .values()
+            .any(|confirmation| confirmation.strategy == strategy)
+    }
+
+    /// Removes and returns requests whose deadline has passed.
+    pub fn expired(&self, now: u64) -> Vec<Confirmation> {
+        let mut confirmations = self.lock();
+
+        let ids: Vec<String> = confirmations
+            .iter()
+            .filter(|(_, confirmation)| confirmation.deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        ids.iter()
+            .filter_map(|id| confirmations.remove(id))
+            .collect()
+    }
+
+    pub fn suppress(&self, strategy: &str) {
+        self.suppressed.lock().unwrap().insert(strategy.to_string());
+    }
+
+    pub fn is_suppressed(&self, strategy: &str) -> bool {
+        self.suppressed.lock().unwrap().contains(strategy)
+    }
+
+    /// Lifts the suppression of strategies whose condition is no longer met.
+    pub fn rearm(&self, is_met: impl Fn(&str) -> bool) {
+        self.suppressed
+            .lock()
+            .unwrap()
+            .retain(|strategy| is_met(strategy));
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Confirmation>> {
+        self.pending.lock().unwrap()
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::strategies::strategy::Strategy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum Format {
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Document {
+    One(Strategy),
+    Many(Vec<Strategy>),
+}
+
+impl Format {
+    pub fn from_file_name(file_name: &str) -> Self {
+        match file_name.to_lowercase() {
+            name if name.ends_with(".json") => Format::Json,
+            _ => Format::Yaml,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Yaml => "yaml",
+        }
+    }
+}
+
+pub fn export(strategies: Vec<Strategy>, format: Format) -> Result<String> {
+    let document = match strategies.len() {
+        1 => Document::One(strategies.into_iter().next().unwrap()),
+        _ => Document::Many(strategies),
+    };
+
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&document)?,
+        Format::Yaml => serde_yaml::to_string(&document)?,
+    })
+}
+
+pub fn import(content: &str, format: Format) -> Result<Vec<Strategy>> {
+    let document = match format {
+        Format::Json => serde_json::from_str(content)?,
+        Format::Yaml => serde_yaml::from_str(content)?,
+    };
+
+    Ok(match document {
+        Document::One(strategy) => vec![strategy],
+        Document::Many(strategies) => strategies,
+    })
+}
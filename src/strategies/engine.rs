@@ -0,0 +1,555 @@
+use anyhow::anyhow;
+use std::{future::Future, sync::Mutex};
+use teloxide::{
+    payloads::SendMessageSetters as _,
+    prelude::Requester as _,
+    types::{InlineKeyboardMarkup, ParseMode::Html},
+    utils::html,
+    Bot,
+};
+use tracing::error;
+
+use crate::{
+    kucoin::{response::Order, trading::spot::order, KuCoin},
+    strategies::{
+        alert::{Alert, Context},
+        confirmations::{Confirmation, TIMEOUT},
+        journal::Entry,
+        strategy::{Execution, Product, Strategy},
+        submissions::Reconciliation,
+        webhook, Strategies,
+    },
+};
+
+/// Outcome of an engine step that is reported to the user.
+#[derive(Debug)]
+pub struct Event {
+    action: String,
+    result: Result<String, String>,
+    text: String,
+    markup: Option<InlineKeyboardMarkup>,
+}
+
+impl Event {
+    fn new(action: String, result: Result<String, String>, text: String) -> Self {
+        Self {
+            action,
+            result,
+            text,
+            markup: None,
+        }
+    }
+
+    fn with_markup(self, markup: InlineKeyboardMarkup) -> Self {
+        Self {
+            markup: Some(markup),
+            ..self
+        }
+    }
+
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    pub fn result(&self) -> &Result<String, String> {
+        &self.result
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Where the engine sends its output: Telegram when trading, the report when backtesting.
+pub trait Reporter: Sync {
+    fn report(&self, event: Event) -> impl Future<Output = ()> + Send;
+
+    /// Delivers a webhook, reporting the event built from its outcome.
+    fn deliver(
+        &self,
+        payload: String,
+        finish: impl FnOnce(anyhow::Result<()>) -> Event + Send + 'static,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+impl Reporter for Bot {
+    async fn report(&self, event: Event) {
+        let mut request = self
+            .send_message(env!("USER_ID").to_string(), event.text)
+            .parse_mode(Html);
+
+        if let Some(markup) = event.markup {
+            request = request.reply_markup(markup);
+        }
+
+        if let Err(e) = request.await {
+            error!("{e}")
+        }
+    }
+
+    /// Runs apart from the poller, since delivery retries and may take a while.
+    async fn deliver(
+        &self,
+        payload: String,
+        finish: impl FnOnce(anyhow::Result<()>) -> Event + Send + 'static,
+    ) {
+        let bot = self.clone();
+
+        tokio::spawn(async move {
+            let result = webhook::post(&payload).await;
+
+            bot.report(finish(result)).await;
+        });
+    }
+}
+
+/// Collects events instead of sending them, and never delivers webhooks.
+#[derive(Debug, Default)]
+pub struct Recorder(Mutex<Vec<Event>>);
+
+impl Recorder {
+    pub fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl Reporter for Recorder {
+    async fn report(&self, event: Event) {
+        self.0.lock().unwrap().push(event);
+    }
+
+    async fn deliver(
+        &self,
+        _: String,
+        finish: impl FnOnce(anyhow::Result<()>) -> Event + Send + 'static,
+    ) {
+        self.report(finish(Ok(()))).await;
+    }
+}
+
+impl Strategies {
+    /// Evaluates every strategy that can act once.
+    pub async fn tick(&self, kucoin: &KuCoin, reporter: &impl Reporter) {
+        for (order_id, fee) in kucoin.spot().tracker().fees() {
+            self.journal().settle(&order_id, fee);
+        }
+
+        self.confirmations().rearm(|name| {
+            self.get(name)
+                .is_some_and(|strategy| strategy.can_execute(kucoin))
+        });
+
+        for confirmation in self.confirmations().expired(kucoin.now()) {
+            self.confirmations().suppress(confirmation.strategy());
+
+            self.journal().record(Entry::new(
+                kucoin,
+                confirmation.strategy().to_string(),
+                confirmation.action().label(),
+                confirmation.amount(),
+                confirmation.payload().to_string(),
+                Err("approval timed out".to_string()),
+                Default::default(),
+            ));
+
+            reporter
+                .report(Event::new(
+                    confirmation.action().to_string(),
+                    Err("approval timed out".to_string()),
+                    confirmation.text().to_string() + "⌛ approval timed out",
+                ))
+                .await;
+        }
+
+        for (strategy, can_execute) in self.executable(kucoin) {
+            self.step(kucoin, reporter, strategy, can_execute).await;
+        }
+    }
+
+    async fn step(
+        &self,
+        kucoin: &KuCoin,
+        reporter: &impl Reporter,
+        mut strategy: Strategy,
+        can_execute: bool,
+    ) {
+        let name = strategy.name().to_string();
+        let mut remaining = strategy.actions().len();
+
+        if let Some(Product::DepositReceived(currency)) = strategy.product().filter(|_| can_execute)
+        {
+            kucoin.deposits().consume(&name, currency);
+        }
+
+        let tracked = self.update(&name, |stored| {
+            stored.actions_mut().track(kucoin, can_execute)
+        });
+
+        if tracked == Some(true) {
+            strategy = self.get(&name).unwrap_or(strategy);
+        }
+
+        if let Some(pending) = strategy.pending().cloned() {
+            let maybe_text = match kucoin.spot().tracker().status(pending.order_id()) {
+                Some(status) if status.is_done() => None,
+                _ if kucoin.now() < pending.deadline() => return,
+                _ => Some(
+                    match kucoin
+                        .send::<Order>(order::cancel(pending.order_id(), pending.symbol()))
+                        .await
+                    {
+                        Ok(order) => (Ok(order.order_id().to_string()), "✅".to_string()),
+                        Err(e) => (Err(e.to_string()), format!("❌\n{e}")),
+                    },
+                ),
+            };
+
+            strategy.set_pending(None);
+            self.update(&name, |stored| stored.set_pending(None));
+
+            if let Some((result, text)) = maybe_text {
+                reporter
+                    .report(Event::new(
+                        format!("CANCEL {} {}", pending.symbol(), pending.order_id()),
+                        result,
+                        format!(
+                            "{}<b>Strategy:</b> {}\n\n<b>Order {} not filled in time, canceling:</b> {}",
+                            kucoin.mode_tag(),
+                            name,
+                            pending.order_id(),
+                            text
+                        ),
+                    ))
+                    .await;
+            }
+        }
+
+        if self.confirmations().is_pending(&name) {
+            return;
+        }
+
+        if let Some(submission) = self.submissions().pending(&name) {
+            let action = submission.action();
+
+            let result = match submission.reconcile(kucoin).await {
+                Reconciliation::Pending => return,
+                Reconciliation::Retry => {
+                    self.submissions().release(&name);
+
+                    None
+                }
+                Reconciliation::Placed(order_id) => {
+                    self.submissions().complete(&name, submission.index());
+                    self.placed(kucoin, &name, action, &order_id, submission.exposure())
+                        .await;
+
+                    Some(Ok(order_id))
+                }
+                Reconciliation::Abandon => {
+                    self.submissions().complete(&name, submission.index());
+
+                    Some(Err("outcome unknown, not retried".to_string()))
+                }
+            };
+
+            if let Some(result) = result {
+                self.executed(
+                    kucoin,
+                    &name,
+                    submission.index(),
+                    action,
+                    result.as_deref().ok(),
+                );
+
+                let text = format!(
+                    "{}<b>Strategy:</b> {}\n\n<b>Action:</b> {}\n\n<b>Execution status:</b> {}",
+                    kucoin.mode_tag(),
+                    name,
+                    action,
+                    match &result {
+                        Ok(order_id) => format!("✅ found on KuCoin\n<b>Order ID:</b> {order_id}"),
+                        Err(e) => format!("⚠️ {e}"),
+                    }
+                );
+
+                self.journal().record(Entry::new(
+                    kucoin,
+                    name.clone(),
+                    action.label(),
+                    None,
+                    submission.payload().to_string(),
+                    result.clone(),
+                    Default::default(),
+                ));
+
+                reporter
+                    .report(Event::new(action.to_string(), result, text))
+                    .await;
+
+                return;
+            }
+        }
+
+        let context = Context::new(&strategy, kucoin);
+        let require_approval = strategy.require_approval();
+
+        while let Some((index, action, execution)) =
+            strategy
+                .actions_mut()
+                .executable(kucoin, &name, !can_execute)
+        {
+            let header = format!(
+                "{}<b>Strategy:</b> {}\n\n<b>Action:</b> {}\n\n",
+                kucoin.mode_tag(),
+                name,
+                action
+            );
+            let mut text = format!("{header}<b>Execution status:</b> ");
+            let label = action.to_string();
+
+            let request = match execution {
+                Execution::Request(request) => request,
+                Execution::Alert(alert) => {
+                    let payload = alert.payload(&context, kucoin);
+
+                    match alert {
+                        Alert::Notify(_) => {
+                            let result = Ok("notified".to_string());
+
+                            self.journal().record(Entry::new(
+                                kucoin,
+                                name.clone(),
+                                action.label(),
+                                None,
+                                payload.clone(),
+                                result.clone(),
+                                Default::default(),
+                            ));
+
+                            let text = format!("{}{}", kucoin.mode_tag(), html::escape(&payload));
+
+                            reporter
+                                .report(Event::new(label.clone(), result, text))
+                                .await;
+                        }
+                        Alert::Webhook(_) => {
+                            let journal = self.journal().clone();
+                            let (name, label, entry_label) =
+                                (name.clone(), label.clone(), action.label());
+                            let kucoin = kucoin.clone();
+
+                            reporter
+                                .deliver(payload.clone(), move |result| {
+                                    let result = match result {
+                                        Ok(_) => {
+                                            text.push('✅');
+
+                                            Ok("delivered".to_string())
+                                        }
+                                        Err(e) => {
+                                            text.push_str(&format!(
+                                                "❌\n{}",
+                                                html::escape(&e.to_string())
+                                            ));
+
+                                            Err(e.to_string())
+                                        }
+                                    };
+
+                                    journal.record(Entry::new(
+                                        &kucoin,
+                                        name,
+                                        entry_label,
+                                        None,
+                                        payload,
+                                        result.clone(),
+                                        Default::default(),
+                                    ));
+
+                                    Event::new(label, result, text)
+                                })
+                                .await;
+                        }
+                    }
+
+                    self.executed(kucoin, &name, index, action, None);
+                    action.executed();
+
+                    match remaining {
+                        1 => break,
+                        _ => {
+                            remaining -= 1;
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let amount = action.amount(kucoin);
+            let estimate = action.fee(kucoin);
+            let fee = estimate
+                .as_ref()
+                .map(|(currency, fee)| format!("<b>Estimated fee:</b> {fee:.8} {currency}\n"))
+                .unwrap_or_default();
+
+            if !fee.is_empty() {
+                text = format!("{header}{fee}\n<b>Execution status:</b> ");
+            }
+
+            let request = self.prepare(&name, index, action, request);
+            let payload = request.to_string();
+
+            let checked = action
+                .check_trade(kucoin)
+                .map_err(|e| anyhow!("rejected by trading rules\n{e}"))
+                .and_then(|_| {
+                    self.risk()
+                        .check(action, kucoin)
+                        .map_err(|e| anyhow!("blocked by risk limits\n{e}"))
+                });
+
+            let exposure = match checked {
+                Ok(exposure) => exposure,
+                Err(e) => {
+                    if self.risk().report(&name, &e.to_string(), kucoin.now()) {
+                        text.push_str(&format!("🚫 {e}"));
+
+                        self.journal().record(Entry::new(
+                            kucoin,
+                            name.clone(),
+                            action.label(),
+                            amount,
+                            payload,
+                            Err(e.to_string()),
+                            Default::default(),
+                        ));
+
+                        reporter
+                            .report(Event::new(label.clone(), Err(e.to_string()), text))
+                            .await;
+                    }
+
+                    match remaining {
+                        1 => break,
+                        _ => {
+                            remaining -= 1;
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if action.needs_confirmation() || require_approval {
+                if self.confirmations().is_suppressed(&name) {
+                    break;
+                }
+
+                let text = format!(
+                    "{header}<b>Amount:</b> {}\n{}{fee}\n<b>Execution status:</b> ",
+                    amount.map_or_else(|| "🚫".to_string(), |amount| amount.to_string()),
+                    action
+                        .price(kucoin)
+                        .map(|price| format!("<b>Price:</b> {price}\n"))
+                        .unwrap_or_default()
+                );
+
+                let markup = self.confirmations().add(
+                    Confirmation::new(
+                        name.clone(),
+                        index,
+                        action.clone(),
+                        amount,
+                        payload,
+                        text.clone(),
+                        kucoin.now() + TIMEOUT,
+                    )
+                    .with_fee(estimate.clone()),
+                );
+
+                reporter
+                    .report(
+                        Event::new(
+                            label.clone(),
+                            Err("awaiting approval".to_string()),
+                            text + "⏳ awaiting approval",
+                        )
+                        .with_markup(markup),
+                    )
+                    .await;
+
+                break;
+            }
+
+            let result = match self
+                .submit(kucoin, &name, index, action, request, exposure)
+                .await
+            {
+                Ok(order) => {
+                    text.push_str(&format!("✅\n{order}"));
+
+                    Ok(order.order_id().to_string())
+                }
+                Err(e) => {
+                    text.push_str(&format!("❌\n{e}"));
+
+                    Err(e.to_string())
+                }
+            };
+
+            let is_tracked = action.is_tracked();
+
+            let balances = {
+                let balances = kucoin.accounts().balances();
+
+                action
+                    .symbol()
+                    .split('-')
+                    .map(|currency| {
+                        (
+                            currency.to_string(),
+                            balances.get(currency).copied().unwrap_or_default(),
+                        )
+                    })
+                    .collect()
+            };
+
+            self.journal().record(
+                Entry::new(
+                    kucoin,
+                    name.clone(),
+                    action.label(),
+                    amount,
+                    payload,
+                    result.clone(),
+                    balances,
+                )
+                .with_fee(estimate),
+            );
+
+            if let Ok(order_id) = &result {
+                self.executed(kucoin, &name, index, action, Some(order_id));
+                action.executed();
+            }
+
+            reporter
+                .report(Event::new(label.clone(), result.clone(), text))
+                .await;
+
+            let maybe_wait = strategy
+                .actions()
+                .get(index + 1)
+                .and_then(|next| next.wait())
+                .filter(|_| is_tracked);
+
+            // The stored strategy now waits for the placed order.
+            if result.is_ok() && maybe_wait.is_some() {
+                break;
+            }
+
+            match remaining {
+                1 => break,
+                _ => remaining -= 1,
+            }
+        }
+    }
+}
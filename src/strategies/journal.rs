@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use teloxide::utils::html;
+use tracing::error;
+
+use crate::kucoin::{paper::SIMULATED, KuCoin};
+
+const PATH: &str = match option_env!("JOURNAL_PATH") {
+    Some(path) => path,
+    None => "journal.jsonl",
+};
+
+// Caps on the escaped text of each field, keeping a page of `PAGE_SIZE` entries within Telegram's
+// 4096 character message limit.
+pub const PAGE_SIZE: usize = 4;
+const ACTION_LIMIT: usize = 200;
+const REQUEST_LIMIT: usize = 350;
+const RESULT_LIMIT: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct Journal {
+    entries: Arc<Mutex<Vec<Entry>>>,
+    persistent: bool,
+}
+
+/// A journal line, either an entry or the actual fee of an earlier entry's order.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Line {
+    Entry(Entry),
+    Settlement { settled: String, fee: (String, f64) },
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        let mut entries = Vec::new();
+
+        for line in fs::read_to_string(PATH)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+        {
+            match line {
+                Line::Entry(entry) => entries.push(entry),
+                Line::Settlement { settled, fee } => settle(&mut entries, &settled, fee),
+            }
+        }
+
+        Self {
+            entries: Arc::new(Mutex::new(entries)),
+            persistent: true,
+        }
+    }
+}
+
+impl Journal {
+    /// Journal that is never written to disk, for backtests.
+    pub fn in_memory() -> Self {
+        Self {
+            entries: Default::default(),
+            persistent: false,
+        }
+    }
+
+    pub fn record(&self, entry: Entry) {
+        let line = Line::Entry(entry);
+        self.append(&line);
+
+        if let Line::Entry(entry) = line {
+            self.lock().push(entry);
+        }
+    }
+
+    /// Returns the given page of a strategy's entries, newest page first, along with the number of
+    /// pages.
+    pub fn history(&self, strategy: &str, page: usize, page_size: usize) -> (Vec<Entry>, usize) {
+        let lock = self.lock();
+
+        let entries: Vec<&Entry> = lock
+            .iter()
+            .rev()
+            .filter(|entry| entry.strategy == strategy)
+            .collect();
+
+        let pages = entries.len().div_ceil(page_size);
+
+        let mut page: Vec<Entry> = entries
+            .into_iter()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(Entry::clone)
+            .collect();
+
+        page.reverse();
+        (page, pages)
+    }
+
+    /// Records the actual fee of a placed order next to its estimate.
+    pub fn settle(&self, order_id: &str, fee: (String, f64)) {
+        self.append(&Line::Settlement {
+            settled: order_id.to_string(),
+            fee: fee.clone(),
+        });
+
+        settle(&mut self.lock(), order_id, fee);
+    }
+
+    fn append(&self, line: &Line) {
+        if !self.persistent {
+            return;
+        }
+
+        let result = serde_json::to_string(line)
+            .map_err(Into::into)
+            .and_then(|line| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(PATH)
+                    .and_then(|mut file| writeln!(file, "{line}"))
+            });
+
+        if let Err(e) = result {
+            error!("{PATH}: {e}");
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Entry>> {
+        self.entries.lock().unwrap()
+    }
+}
+
+fn settle(entries: &mut [Entry], order_id: &str, fee: (String, f64)) {
+    if let Some(entry) = entries
+        .iter_mut()
+        .rev()
+        .find(|entry| entry.actual_fee.is_none() && entry.result.as_deref() == Ok(order_id))
+    {
+        entry.actual_fee = Some(fee);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    time: u64,
+    strategy: String,
+    action: String,
+    amount: Option<f64>,
+    request: String,
+    result: Result<String, String>,
+    balances: Vec<(String, f64)>,
+    simulated: bool,
+    #[serde(default)]
+    fee: Option<(String, f64)>,
+    #[serde(default)]
+    actual_fee: Option<(String, f64)>,
+}
+
+impl Entry {
+    pub fn new(
+        kucoin: &KuCoin,
+        strategy: String,
+        action: String,
+        amount: Option<f64>,
+        request: String,
+        result: Result<String, String>,
+        balances: Vec<(String, f64)>,
+    ) -> Self {
+        Self {
+            time: kucoin.now(),
+            strategy,
+            action,
+            amount,
+            request,
+            result,
+            balances,
+            simulated: kucoin.is_simulated(),
+            fee: None,
+            actual_fee: None,
+        }
+    }
+
+    /// Sets the estimated fee, the actual one being recorded once the order is done.
+    pub fn with_fee(self, fee: Option<(String, f64)>) -> Self {
+        Self { fee, ..self }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = match &self.result {
+            Ok(order_id) => format!("✅ {}", excerpt(order_id, RESULT_LIMIT)),
+            Err(e) => format!("❌ {}", excerpt(e, RESULT_LIMIT)),
+        };
+
+        let balances = self
+            .balances
+            .iter()
+            .map(|(currency, balance)| format!("{balance} {currency}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(
+            f,
+            "{}<b>Time:</b> {}\n\
+            <b>Action:</b> {}\n\
+            <b>Amount:</b> {}\n\
+            <b>Request:</b> <code>{}</code>\n\
+            <b>Result:</b> {}\n\
+            <b>Balances:</b> {}{}",
+            if self.simulated { SIMULATED } else { "" },
+            format_time(self.time),
+            excerpt(&self.action, ACTION_LIMIT),
+            self.amount
+                .map_or_else(|| "🚫".to_string(), |amount| amount.to_string()),
+            excerpt(&self.request, REQUEST_LIMIT),
+            result,
+            balances,
+            fees(&self.fee, &self.actual_fee)
+        )
+    }
+}
+
+fn fees(estimated: &Option<(String, f64)>, actual: &Option<(String, f64)>) -> String {
+    let fee = |fee: &Option<(String, f64)>| {
+        fee.as_ref().map_or_else(
+            || "🚫".to_string(),
+            |(currency, fee)| format!("{fee:.8} {currency}"),
+        )
+    };
+
+    match (estimated, actual) {
+        (None, None) => String::new(),
+        _ => format!(
+            "\n<b>Fee:</b> {} estimated, {} actual",
+            fee(estimated),
+            fee(actual)
+        ),
+    }
+}
+
+/// HTML-escapes the text, cutting it off once the escaped text would exceed `limit` characters.
+fn excerpt(text: &str, limit: usize) -> String {
+    let mut excerpt = String::new();
+    let mut length = 0;
+
+    for c in text.chars() {
+        let escaped = html::escape(c.encode_utf8(&mut [0; 4]));
+        length += escaped.chars().count();
+
+        if length > limit {
+            excerpt.push('…');
+            break;
+        }
+
+        excerpt.push_str(&escaped);
+    }
+
+    excerpt
+}
+
+fn format_time(millis: u64) -> String {
+    let secs = millis / 1000;
+    let days = (secs / 86_400) as i64;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
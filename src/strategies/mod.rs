@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use reqwest::Client;
 use std::{
     collections::HashMap,
@@ -5,28 +6,72 @@ use std::{
     sync::{Arc, Mutex, MutexGuard},
     time::Duration,
 };
-use teloxide::{
-    payloads::SendMessageSetters as _, prelude::Requester as _, types::ParseMode::Html, Bot,
-};
-use tracing::error;
+use teloxide::Bot;
 
 use crate::kucoin::{
     response::Order,
     task::{Poller, Spawnable as _},
-    KuCoin,
+    trading::spot::tracker::Source,
+    KuCoin, Request,
 };
 
+pub mod alert;
+
+pub mod brackets;
+use brackets::Brackets;
+
+pub mod confirmations;
+use confirmations::Confirmations;
+
+pub mod document;
+
+pub mod engine;
+
+pub mod journal;
+use journal::Journal;
+
+pub mod risk;
+use risk::Risk;
+
 pub mod strategy;
-use strategy::Strategy;
+
+pub mod submissions;
+use submissions::{is_unknown_outcome, Submission, Submissions};
+
+pub mod webhook;
+use strategy::{Action, ActionKind, Pending, Strategy};
 
 #[derive(Debug, Default, Clone)]
-pub struct Strategies(Arc<Mutex<HashMap<String, Strategy>>>);
+pub struct Strategies {
+    strategies: Arc<Mutex<HashMap<String, Strategy>>>,
+    journal: Journal,
+    brackets: Brackets,
+    confirmations: Confirmations,
+    risk: Risk,
+    submissions: Submissions,
+}
 
 impl Strategies {
+    /// Strategies that keep their journal in memory, for backtests.
+    pub fn in_memory() -> Self {
+        Self {
+            strategies: Default::default(),
+            journal: Journal::in_memory(),
+            brackets: Brackets::in_memory(),
+            confirmations: Default::default(),
+            risk: Risk::in_memory(),
+            submissions: Submissions::in_memory(),
+        }
+    }
+
     pub fn get(&self, name: &str) -> Option<Strategy> {
         self.lock().get(name).cloned()
     }
 
+    pub fn all(&self) -> Vec<Strategy> {
+        self.lock().values().cloned().collect()
+    }
+
     pub fn names(&self) -> Vec<String> {
         self.lock().keys().cloned().collect()
     }
@@ -35,27 +80,244 @@ impl Strategies {
         self.lock().insert(strategy.name().to_string(), strategy);
     }
 
+    /// Changes the stored strategy in place, keeping any edits made since it was read.
+    pub fn update<T>(&self, name: &str, f: impl FnOnce(&mut Strategy) -> T) -> Option<T> {
+        self.lock().get_mut(name).map(f)
+    }
+
     pub fn remove(&self, name: &str) -> Option<Strategy> {
         self.lock().remove(name)
     }
 
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    pub fn brackets(&self) -> &Brackets {
+        &self.brackets
+    }
+
+    pub fn confirmations(&self) -> &Confirmations {
+        &self.confirmations
+    }
+
+    pub fn risk(&self) -> &Risk {
+        &self.risk
+    }
+
+    pub fn submissions(&self) -> &Submissions {
+        &self.submissions
+    }
+
     pub fn run(self, bot: Bot, kucoin: KuCoin) {
         let client = kucoin.client().clone();
 
+        (
+            self.brackets.clone(),
+            self.risk.clone(),
+            bot.clone(),
+            kucoin.clone(),
+        )
+            .poller(client.clone(), Duration::from_secs(5))
+            .spawn();
+
         (self, bot, kucoin)
             .poller(client, Duration::from_secs(1))
             .spawn();
     }
 
-    fn executable(&self, kucoin: &KuCoin) -> Option<Strategy> {
+    /// Tags the request with the client order ID of the action's next submission.
+    pub fn prepare(&self, name: &str, index: usize, action: &Action, request: Request) -> Request {
+        match Submissions::accepts_client_oid(action) {
+            true => request.client_oid(&self.submissions.client_oid(name, index)),
+            false => request,
+        }
+    }
+
+    /// Sends the request, funding it first if needed, and tracks the placed order.
+    /// Requests with an unknown outcome are held until reconciled, while funding of
+    /// rejected requests is moved back.
+    pub async fn submit(
+        &self,
+        kucoin: &KuCoin,
+        name: &str,
+        index: usize,
+        action: &Action,
+        request: Request,
+        exposure: Option<(String, f64)>,
+    ) -> anyhow::Result<Order> {
+        let refund =
+            match action.funding(kucoin) {
+                Some((funding, transfer)) => {
+                    let funding_exposure = self
+                        .risk
+                        .check(&funding, kucoin)
+                        .map_err(|e| anyhow!("funding blocked by risk limits\n{e}"))?;
+
+                    let refund = Request::from(transfer.reversed())
+                        .client_oid(&self.submissions.transfer_client_oid(name, index, "refund"));
+
+                    kucoin
+                        .send::<Order>(Request::from(transfer).client_oid(
+                            &self.submissions.transfer_client_oid(name, index, "funding"),
+                        ))
+                        .await?;
+                    self.risk.record(funding_exposure, kucoin.now());
+
+                    Some(refund)
+                }
+                None => None,
+            };
+
+        let submission = Submission::new(
+            name.to_string(),
+            index,
+            action.clone(),
+            self.submissions.client_oid(name, index),
+            exposure.clone(),
+            &request,
+            kucoin.now(),
+        );
+
+        match kucoin.send::<Order>(request).await {
+            Ok(order) => {
+                self.submissions.complete(name, index);
+                self.placed(kucoin, name, action, order.order_id(), exposure)
+                    .await;
+
+                Ok(order)
+            }
+            Err(e) if is_unknown_outcome(&e) => {
+                self.submissions.hold(submission);
+
+                Err(anyhow!(
+                    "{e}\noutcome unknown, reconciling before any retry"
+                ))
+            }
+            Err(e) => {
+                let e = match refund {
+                    Some(refund) => {
+                        match kucoin.send::<Order>(refund).await {
+                            Ok(_) => anyhow!("{e}\nfunding moved back"),
+                            Err(refund) => {
+                                anyhow!("{e}\nfunding left in the trade account, not moved back\n{refund}")
+                            }
+                        }
+                    }
+                    None => e,
+                };
+
+                self.submissions.complete(name, index);
+
+                Err(e)
+            }
+        }
+    }
+
+    /// Marks the action executed on the stored strategy once it's done, holding back
+    /// the next action while it waits for the placed order, if any.
+    pub fn executed(
+        &self,
+        kucoin: &KuCoin,
+        name: &str,
+        index: usize,
+        action: &Action,
+        order_id: Option<&str>,
+    ) {
+        self.update(name, |strategy| {
+            let wait = strategy
+                .actions()
+                .get(index + 1)
+                .and_then(|next| next.wait())
+                .filter(|_| action.is_tracked())
+                .zip(order_id);
+
+            let current = strategy
+                .actions_mut()
+                .get_mut(index)
+                .filter(|current| current.id() == action.id());
+
+            if let Some(current) = current {
+                current.executed();
+
+                if let Some((wait, order_id)) = wait {
+                    strategy.set_pending(Some(Pending::new(
+                        order_id.to_string(),
+                        action.symbol().to_string(),
+                        kucoin.now() + wait * 1000,
+                    )));
+                }
+            }
+        });
+    }
+
+    async fn placed(
+        &self,
+        kucoin: &KuCoin,
+        name: &str,
+        action: &Action,
+        order_id: &str,
+        exposure: Option<(String, f64)>,
+    ) {
+        self.risk.record(exposure, kucoin.now());
+
+        if !kucoin.is_simulated() {
+            kucoin.accounts().poll(kucoin.client()).await;
+        }
+
+        if let ActionKind::CancelOrder { .. } = action.kind() {
+            kucoin.spot().active().remove(order_id);
+        }
+
+        let source = match action.kind() {
+            ActionKind::SpotOrder { .. } | ActionKind::TrailingStop { .. } => Some(Source::Spot),
+            ActionKind::MarginOrder { .. } => Some(Source::Margin),
+            ActionKind::StopOrder { .. } => Some(Source::Triggered),
+            _ => None,
+        };
+
+        if let Some(source) = source {
+            kucoin.spot().tracker().track(
+                order_id.to_string(),
+                action.symbol().to_string(),
+                name.to_string(),
+                kucoin.now(),
+                source,
+            );
+        }
+
+        if let ActionKind::Oco { .. } = action.kind() {
+            kucoin
+                .spot()
+                .oco()
+                .track(order_id.to_string(), name.to_string(), kucoin.now());
+        }
+
+        if let Some(bracket) = action.bracket() {
+            self.brackets.open(
+                name.to_string(),
+                action.symbol().to_string(),
+                order_id.to_string(),
+                bracket.clone(),
+            );
+        }
+    }
+
+    fn executable(&self, kucoin: &KuCoin) -> Vec<(Strategy, bool)> {
         self.lock()
             .values()
-            .find(|strategy| !strategy.actions().is_empty() && strategy.can_execute(kucoin))
-            .cloned()
+            .filter(|strategy| !strategy.actions().is_empty())
+            .filter_map(|strategy| {
+                let can_execute = strategy.can_execute(kucoin);
+
+                (can_execute || strategy.actions().is_armed() || strategy.pending().is_some())
+                    .then(|| (strategy.clone(), can_execute))
+            })
+            .collect()
     }
 
     fn lock(&self) -> MutexGuard<'_, HashMap<String, Strategy>> {
-        self.0.lock().unwrap()
+        self.strategies.lock().unwrap()
     }
 }
 
@@ -73,49 +335,7 @@ impl fmt::Display for Strategies {
 }
 
 impl Poller for (Strategies, Bot, KuCoin) {
-    async fn poll(&self, client: &Client) {
-        let strategies = &self.0;
-        let bot = &self.1;
-        let kucoin = &self.2;
-
-        if let Some(strategy) = strategies.executable(kucoin) {
-            let name = strategy.name().to_string();
-            let mut strategy = strategy;
-            let mut remaining = strategy.actions().len();
-
-            while let Some((action, request)) = strategy.actions_mut().executable(kucoin) {
-                let mut text = format!(
-                    "<b>Strategy:</b> {}\n\n<b>Action:</b> {}\n\n<b>Execution status:</b> ",
-                    name, action
-                );
-
-                match request.send::<Order>(client).await {
-                    Ok(order) => {
-                        kucoin.accounts().poll(client).await;
-
-                        if action.percentage() != 100 {
-                            *action.skip_mut() = true;
-                            strategies.add(strategy.clone());
-                        }
-
-                        text.push_str(&format!("✅\n{order}"))
-                    }
-                    Err(e) => text.push_str(&format!("❌\n{e}")),
-                }
-
-                if let Err(e) = bot
-                    .send_message(env!("USER_ID").to_string(), text)
-                    .parse_mode(Html)
-                    .await
-                {
-                    error!("{e}")
-                }
-
-                match remaining {
-                    1 => break,
-                    _ => remaining -= 1,
-                }
-            }
-        }
+    async fn poll(&self, _: &Client) {
+        self.0.tick(&self.2, &self.1).await;
     }
 }
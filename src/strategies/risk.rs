@@ -0,0 +1,528 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    sync::{Arc, Mutex, MutexGuard},
+    time::UNIX_EPOCH,
+};
+use tracing::error;
+
+use crate::{
+    kucoin::{margin::QUOTE_CURRENCY, KuCoin},
+    strategies::strategy::{Action, ActionKind},
+};
+
+const PATH: &str = match option_env!("RISK_PATH") {
+    Some(path) => path,
+    None => "risk.json",
+};
+
+const RECORDS_PATH: &str = match option_env!("RISK_RECORDS_PATH") {
+    Some(path) => path,
+    None => "risk_records.jsonl",
+};
+
+const KILL_SWITCH_PATH: &str = match option_env!("RISK_KILL_SWITCH_PATH") {
+    Some(path) => path,
+    None => "risk_kill_switch.json",
+};
+
+const HOUR: u64 = 60 * 60 * 1000;
+const DAY: u64 = 24 * HOUR;
+
+/// Blocked executions of a strategy are reported again after this long.
+const REPORT_INTERVAL: u64 = 10 * 60 * 1000;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Limits {
+    max_order_value: Option<f64>,
+    max_daily_value: Option<f64>,
+    max_daily_asset_value: HashMap<String, f64>,
+    max_executions_per_hour: Option<usize>,
+    allowed_symbols: Vec<String>,
+    kill_switch: bool,
+}
+
+impl Limits {
+    /// An unreadable config engages the kill switch instead of trading without limits.
+    fn load() -> Self {
+        match fs::read_to_string(PATH) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                error!("{PATH}: {e}");
+                Limits {
+                    kill_switch: true,
+                    ..Default::default()
+                }
+            }),
+            Err(_) => Limits::default(),
+        }
+    }
+
+    /// Orders are matched by symbol. Requests that move a currency match the
+    /// currency itself or any allowed symbol it's traded in.
+    fn allows(&self, action: &Action) -> bool {
+        let symbol = action.symbol();
+
+        self.allowed_symbols.is_empty()
+            || match action.kind() {
+                ActionKind::Borrow
+                | ActionKind::Repay
+                | ActionKind::Lend { .. }
+                | ActionKind::Redeem
+                | ActionKind::Transfer { .. }
+                | ActionKind::SubTransfer { .. }
+                | ActionKind::Withdraw { .. } => self.allowed_symbols.iter().any(|allowed| {
+                    allowed == symbol || allowed.split('-').any(|currency| currency == symbol)
+                }),
+                _ => self.allowed_symbols.iter().any(|allowed| allowed == symbol),
+            }
+    }
+
+    fn has_value_limits(&self) -> bool {
+        self.max_order_value.is_some()
+            || self.max_daily_value.is_some()
+            || !self.max_daily_asset_value.is_empty()
+    }
+}
+
+/// Global limits consulted before every request sent by the strategy engine.
+#[derive(Debug, Clone)]
+pub struct Risk {
+    limits: Arc<Limits>,
+    state: Arc<Mutex<State>>,
+    persistent: bool,
+}
+
+impl Default for Risk {
+    fn default() -> Self {
+        let now = UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
+
+        let records: Vec<Record> = fs::read_to_string(RECORDS_PATH)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<Record>(line).ok())
+                    .filter(|record| record.time + DAY > now)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Older records are dropped from the file as well, so it doesn't grow.
+        let content: String = records
+            .iter()
+            .filter_map(|record| serde_json::to_string(record).ok())
+            .map(|line| line + "\n")
+            .collect();
+
+        if let Err(e) = fs::write(RECORDS_PATH, content) {
+            error!("{RECORDS_PATH}: {e}");
+        }
+
+        let limits = Limits::load();
+        let killed = limits.kill_switch || load_kill_switch(KILL_SWITCH_PATH);
+
+        Self::new(limits, records, killed, true)
+    }
+}
+
+/// Kill switch engaged from Telegram, kept until it's cleared there.
+fn load_kill_switch(path: &str) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_kill_switch(path: &str, killed: bool) {
+    if let Err(e) = fs::write(path, killed.to_string()) {
+        error!("{path}: {e}");
+    }
+}
+
+/// The reason without its numbers, which change with prices and balances.
+fn reason_kind(reason: &str) -> String {
+    reason
+        .chars()
+        .filter(|c| !c.is_ascii_digit() && *c != '.')
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct State {
+    killed: bool,
+    records: Vec<Record>,
+    reported: HashMap<String, (String, u64)>,
+}
+
+impl State {
+    fn check(
+        &mut self,
+        limits: &Limits,
+        action: &Action,
+        exposure: Option<(String, f64)>,
+        now: u64,
+    ) -> Result<Option<(String, f64)>> {
+        self.prune(now);
+
+        if self.killed {
+            bail!("kill switch is engaged");
+        }
+
+        if !limits.allows(action) {
+            bail!("{} is not an allowed symbol", action.symbol());
+        }
+
+        if let Some(max) = limits.max_executions_per_hour {
+            if self.executions(now) >= max {
+                bail!("limit of {max} executions per hour reached");
+            }
+        }
+
+        if !limits.has_value_limits() {
+            return Ok(exposure);
+        }
+
+        let Some((asset, value)) = exposure else {
+            bail!("unable to value the order in {QUOTE_CURRENCY}");
+        };
+
+        if let Some(max) = limits.max_order_value {
+            if value > max {
+                bail!("order value {value:.2} {QUOTE_CURRENCY} exceeds the limit of {max} {QUOTE_CURRENCY}");
+            }
+        }
+
+        if let Some(max) = limits.max_daily_value {
+            if self.value(None) + value > max {
+                bail!("daily limit of {max} {QUOTE_CURRENCY} would be exceeded");
+            }
+        }
+
+        if let Some(max) = limits.max_daily_asset_value.get(&asset) {
+            if self.value(Some(&asset)) + value > *max {
+                bail!("daily limit of {max} {QUOTE_CURRENCY} for {asset} would be exceeded");
+            }
+        }
+
+        Ok(Some((asset, value)))
+    }
+
+    fn prune(&mut self, now: u64) {
+        self.records.retain(|record| record.time + DAY > now);
+    }
+
+    fn executions(&self, now: u64) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.time + HOUR > now)
+            .count()
+    }
+
+    fn value(&self, asset: Option<&str>) -> f64 {
+        self.records
+            .iter()
+            .filter_map(|record| record.exposure.as_ref())
+            .filter(|(other, _)| asset.is_none_or(|asset| asset == other))
+            .map(|(_, value)| value)
+            .sum()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    time: u64,
+    exposure: Option<(String, f64)>,
+}
+
+impl Risk {
+    fn new(limits: Limits, records: Vec<Record>, killed: bool, persistent: bool) -> Self {
+        let state = State {
+            killed,
+            records,
+            ..Default::default()
+        };
+
+        Self {
+            limits: Arc::new(limits),
+            state: Arc::new(Mutex::new(state)),
+            persistent,
+        }
+    }
+
+    /// Limits whose records are never written to disk, for backtests.
+    pub fn in_memory() -> Self {
+        let limits = Limits::load();
+        let killed = limits.kill_switch;
+
+        Self::new(limits, Default::default(), killed, false)
+    }
+
+    pub fn is_killed(&self) -> bool {
+        self.lock().killed
+    }
+
+    pub fn set_killed(&self, killed: bool) {
+        let mut state = self.lock();
+
+        state.killed = killed;
+        state.reported.clear();
+
+        if self.persistent {
+            save_kill_switch(KILL_SWITCH_PATH, killed);
+        }
+    }
+
+    /// Returns the traded asset and value to record once the request succeeds.
+    /// Cancellations are never blocked.
+    pub fn check(&self, action: &Action, kucoin: &KuCoin) -> Result<Option<(String, f64)>> {
+        if let ActionKind::CancelOrder { .. } = action.kind() {
+            return Ok(None);
+        }
+
+        let exposure = action.exposure(kucoin);
+
+        self.lock()
+            .check(&self.limits, action, exposure, kucoin.now())
+    }
+
+    pub fn record(&self, exposure: Option<(String, f64)>, now: u64) {
+        let record = Record {
+            time: now,
+            exposure,
+        };
+
+        if self.persistent {
+            let result = serde_json::to_string(&record)
+                .map_err(Into::into)
+                .and_then(|line| {
+                    OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(RECORDS_PATH)
+                        .and_then(|mut file| writeln!(file, "{line}"))
+                });
+
+            if let Err(e) = result {
+                error!("{RECORDS_PATH}: {e}");
+            }
+        }
+
+        self.lock().records.push(record);
+    }
+
+    /// Whether a blocked execution should be reported, so that a strategy
+    /// whose condition stays met does not report the same block every second.
+    pub fn report(&self, strategy: &str, reason: &str, now: u64) -> bool {
+        let mut state = self.lock();
+        let kind = reason_kind(reason);
+
+        match state.reported.get(strategy) {
+            Some((last, time)) if *last == kind && *time + REPORT_INTERVAL > now => false,
+            _ => {
+                state.reported.insert(strategy.to_string(), (kind, now));
+
+                true
+            }
+        }
+    }
+
+    pub fn status(&self, now: u64) -> String {
+        Status(self, now).to_string()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+struct Status<'a>(&'a Risk, u64);
+
+impl fmt::Display for Status<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (risk, now) = (self.0, self.1);
+        let limits = &risk.limits;
+        let mut state = risk.lock();
+        state.prune(now);
+
+        writeln!(
+            f,
+            "<b>Kill switch:</b> {}\n",
+            match state.killed {
+                true => "🛑 engaged",
+                false => "✅ off",
+            }
+        )?;
+
+        write!(f, "<b>Executions (1h):</b> {}", state.executions(now))?;
+
+        match limits.max_executions_per_hour {
+            Some(max) => writeln!(f, " / {max}")?,
+            None => writeln!(f)?,
+        }
+
+        write!(f, "<b>Traded (24h):</b> {:.2}", state.value(None))?;
+
+        match limits.max_daily_value {
+            Some(max) => writeln!(f, " / {max} {QUOTE_CURRENCY}")?,
+            None => writeln!(f, " {QUOTE_CURRENCY}")?,
+        }
+
+        for (asset, max) in &limits.max_daily_asset_value {
+            writeln!(
+                f,
+                "<b>{asset} (24h):</b> {:.2} / {max} {QUOTE_CURRENCY}",
+                state.value(Some(asset))
+            )?;
+        }
+
+        if let Some(max) = limits.max_order_value {
+            writeln!(f, "<b>Max order value:</b> {max} {QUOTE_CURRENCY}")?;
+        }
+
+        if !limits.allowed_symbols.is_empty() {
+            writeln!(
+                f,
+                "<b>Allowed symbols:</b> {}",
+                limits.allowed_symbols.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 10 * DAY;
+
+    fn action(mut action: Action, symbol: &str) -> Action {
+        *action.symbol_mut() = symbol.to_string();
+        action
+    }
+
+    fn exposure(asset: &str, value: f64) -> Option<(String, f64)> {
+        Some((asset.to_string(), value))
+    }
+
+    fn record(state: &mut State, time: u64, asset: &str, value: f64) {
+        state.records.push(Record {
+            time,
+            exposure: exposure(asset, value),
+        });
+    }
+
+    #[test]
+    fn blocks_everything_when_killed() {
+        let mut state = State {
+            killed: true,
+            ..Default::default()
+        };
+
+        let buy = action(Action::buy(), "BTC-USDT");
+
+        assert!(state
+            .check(&Limits::default(), &buy, exposure("BTC", 1.0), NOW)
+            .is_err());
+    }
+
+    #[test]
+    fn matches_allowed_symbols_by_kind() {
+        let limits = Limits {
+            allowed_symbols: vec!["BTC-USDT".to_string()],
+            ..Default::default()
+        };
+
+        assert!(limits.allows(&action(Action::buy(), "BTC-USDT")));
+        assert!(!limits.allows(&action(Action::buy(), "ETH-USDT")));
+        assert!(limits.allows(&action(Action::lend(), "USDT")));
+        assert!(limits.allows(&action(Action::transfer(), "BTC")));
+        assert!(!limits.allows(&action(Action::redeem(), "ETH")));
+    }
+
+    #[test]
+    fn limits_executions_per_hour() {
+        let limits = Limits {
+            max_executions_per_hour: Some(2),
+            ..Default::default()
+        };
+        let mut state = State::default();
+        let buy = action(Action::buy(), "BTC-USDT");
+
+        record(&mut state, NOW - 2 * HOUR, "BTC", 1.0);
+        record(&mut state, NOW - 1000, "BTC", 1.0);
+        assert!(state.check(&limits, &buy, None, NOW).is_ok());
+
+        record(&mut state, NOW - 500, "BTC", 1.0);
+        assert!(state.check(&limits, &buy, None, NOW).is_err());
+    }
+
+    #[test]
+    fn limits_order_and_daily_values() {
+        let limits = Limits {
+            max_order_value: Some(100.0),
+            max_daily_value: Some(250.0),
+            max_daily_asset_value: HashMap::from([("BTC".to_string(), 150.0)]),
+            ..Default::default()
+        };
+        let mut state = State::default();
+        let buy = action(Action::buy(), "BTC-USDT");
+
+        assert!(state
+            .check(&limits, &buy, exposure("BTC", 101.0), NOW)
+            .is_err());
+        assert!(state.check(&limits, &buy, None, NOW).is_err());
+
+        record(&mut state, NOW - DAY, "ETH", 1000.0);
+        record(&mut state, NOW - HOUR, "BTC", 100.0);
+        assert!(state
+            .check(&limits, &buy, exposure("BTC", 60.0), NOW)
+            .is_err());
+        assert!(state
+            .check(&limits, &buy, exposure("ETH", 60.0), NOW)
+            .is_ok());
+
+        record(&mut state, NOW - HOUR, "ETH", 100.0);
+        assert!(state
+            .check(&limits, &buy, exposure("ETH", 60.0), NOW)
+            .is_err());
+    }
+
+    #[test]
+    fn keeps_the_kill_switch_after_reloading() {
+        let path = std::env::temp_dir().join(format!("kill_switch_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        save_kill_switch(path, true);
+        let risk = Risk::new(
+            Limits::default(),
+            Default::default(),
+            load_kill_switch(path),
+            false,
+        );
+        assert!(risk.is_killed());
+
+        save_kill_switch(path, false);
+        assert!(!load_kill_switch(path));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reports_changing_values_once_per_interval() {
+        let risk = Risk::new(Limits::default(), Default::default(), false, false);
+        let reason =
+            |value: f64| format!("order value {value:.2} USDT exceeds the limit of 100 USDT");
+
+        assert!(risk.report("dca", &reason(101.5), NOW));
+        assert!(!risk.report("dca", &reason(102.25), NOW + 1000));
+        assert!(risk.report("dca", "kill switch is engaged", NOW + 2000));
+        assert!(risk.report("dca", &reason(103.0), NOW + 3000));
+        assert!(risk.report("dca", &reason(103.0), NOW + 3000 + REPORT_INTERVAL));
+    }
+}
@@ -1,26 +1,95 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use teloxide::utils::html;
+use uuid::Uuid;
 
-use crate::kucoin::{
-    account::AccountType,
-    trading::spot::order::{Side, Type},
-    KuCoin,
+use crate::{
+    kucoin::{
+        account::{AccountType, Transfer},
+        trading::spot::{is_kcs_fee_deduction, KCS, KCS_FEE_DISCOUNT},
+        trading::spot::{
+            order::{Details, Options, Side, Type},
+            stop::Stop,
+        },
+        with_increment, KuCoin,
+    },
+    strategies::{brackets::Bracket, webhook},
 };
 
-#[derive(Debug, Clone)]
+use super::{Price, Size};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Action {
     kind: ActionKind,
     symbol: String,
     percentage: u8,
+    #[serde(default)]
+    size: Size,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    account: Option<AccountType>,
+    #[serde(default, skip_serializing_if = "Options::is_empty")]
+    options: Options,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait: Option<u64>,
+    #[serde(default)]
+    reserve_fees: bool,
+    // Runtime state, left out of documents so imported actions start fresh.
+    #[serde(skip)]
     skip: bool,
+    // Tells the action apart from others in the strategy, kept across edits and set anew on import.
+    #[serde(skip, default = "new_id")]
+    id: String,
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().simple().to_string()
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ActionKind {
     SpotOrder {
         r#type: Type,
         side: Side,
-        price: Option<f64>,
+        price: Option<Price>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bracket: Option<Bracket>,
+    },
+    TrailingStop {
+        r#type: Type,
+        side: Side,
+        callback_rate: f64,
+        #[serde(skip)]
+        extreme: Option<f64>,
+    },
+    StopOrder {
+        r#type: Type,
+        side: Side,
+        stop: Stop,
+        stop_price: Price,
+        price: Option<Price>,
     },
+    Oco {
+        side: Side,
+        price: Price,
+        stop_price: Price,
+        limit_price: Price,
+    },
+    MarginOrder {
+        r#type: Type,
+        side: Side,
+        price: Option<Price>,
+        auto_borrow: bool,
+        auto_repay: bool,
+    },
+    Borrow,
+    Repay,
+    FuturesOrder {
+        side: Side,
+        leverage: u8,
+    },
+    ClosePosition,
     Lend {
         interest_rate: f64,
     },
@@ -31,6 +100,41 @@ pub enum ActionKind {
         from_account_tag: Option<String>,
         to_account_tag: Option<String>,
     },
+    SubTransfer {
+        sub_account: String,
+        direction: Direction,
+        from: AccountType,
+        to: AccountType,
+    },
+    Withdraw {
+        chain: String,
+        address: String,
+    },
+    CancelOrder {
+        scope: CancelScope,
+    },
+    Notify {
+        template: String,
+    },
+    Webhook {
+        event: String,
+    },
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    #[default]
+    ToSub,
+    ToParent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelScope {
+    Strategy,
+    Symbol,
+    ClientOid(String),
 }
 
 impl Action {
@@ -39,7 +143,13 @@ impl Action {
             kind,
             symbol: Default::default(),
             percentage: Default::default(),
+            size: Default::default(),
+            account: Default::default(),
+            options: Default::default(),
+            wait: Default::default(),
+            reserve_fees: Default::default(),
             skip: true,
+            id: new_id(),
         }
     }
 
@@ -48,6 +158,7 @@ impl Action {
             side,
             r#type: Default::default(),
             price: Default::default(),
+            bracket: Default::default(),
         })
     }
 
@@ -59,6 +170,79 @@ impl Action {
         Action::spot_order(Side::Sell)
     }
 
+    /// Sale of a fixed size that closes a bracket, at market when there's no price.
+    pub fn exit(symbol: String, price: Option<f64>, size: f64) -> Self {
+        Action {
+            symbol,
+            percentage: 100,
+            size: Size::Base(size),
+            skip: false,
+            ..Action::new(ActionKind::SpotOrder {
+                r#type: Default::default(),
+                side: Side::Sell,
+                price: price.map(Price::Fixed),
+                bracket: Default::default(),
+            })
+        }
+    }
+
+    pub fn trailing_stop(side: Side) -> Self {
+        Action::new(ActionKind::TrailingStop {
+            r#type: Default::default(),
+            side,
+            callback_rate: Default::default(),
+            extreme: Default::default(),
+        })
+    }
+
+    pub fn stop_order(side: Side) -> Self {
+        Action::new(ActionKind::StopOrder {
+            r#type: Default::default(),
+            side,
+            stop: Default::default(),
+            stop_price: Price::Fixed(Default::default()),
+            price: Default::default(),
+        })
+    }
+
+    pub fn oco(side: Side) -> Self {
+        Action::new(ActionKind::Oco {
+            side,
+            price: Price::Fixed(Default::default()),
+            stop_price: Price::Fixed(Default::default()),
+            limit_price: Price::Fixed(Default::default()),
+        })
+    }
+
+    pub fn margin_order(side: Side) -> Self {
+        Action::new(ActionKind::MarginOrder {
+            r#type: Default::default(),
+            side,
+            price: Default::default(),
+            auto_borrow: Default::default(),
+            auto_repay: Default::default(),
+        })
+    }
+
+    pub fn borrow() -> Self {
+        Action::new(ActionKind::Borrow)
+    }
+
+    pub fn repay() -> Self {
+        Action::new(ActionKind::Repay)
+    }
+
+    pub fn futures_order(side: Side) -> Self {
+        Action::new(ActionKind::FuturesOrder { side, leverage: 1 })
+    }
+
+    pub fn close_position() -> Self {
+        Action {
+            percentage: 100,
+            ..Action::new(ActionKind::ClosePosition)
+        }
+    }
+
     pub fn lend() -> Self {
         Action::new(ActionKind::Lend {
             interest_rate: Default::default(),
@@ -78,6 +262,47 @@ impl Action {
         })
     }
 
+    pub fn sub_transfer() -> Self {
+        Action::new(ActionKind::SubTransfer {
+            sub_account: Default::default(),
+            direction: Default::default(),
+            from: Default::default(),
+            to: Default::default(),
+        })
+    }
+
+    pub fn withdraw() -> Self {
+        Action::new(ActionKind::Withdraw {
+            chain: Default::default(),
+            address: Default::default(),
+        })
+    }
+
+    pub fn cancel_order(scope: CancelScope) -> Self {
+        Action {
+            percentage: 100,
+            ..Action::new(ActionKind::CancelOrder { scope })
+        }
+    }
+
+    pub fn notify() -> Self {
+        Action {
+            percentage: 100,
+            ..Action::new(ActionKind::Notify {
+                template: Default::default(),
+            })
+        }
+    }
+
+    pub fn webhook() -> Self {
+        Action {
+            percentage: 100,
+            ..Action::new(ActionKind::Webhook {
+                event: Default::default(),
+            })
+        }
+    }
+
     pub fn kind(&self) -> &ActionKind {
         &self.kind
     }
@@ -102,6 +327,74 @@ impl Action {
         &mut self.percentage
     }
 
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn size_mut(&mut self) -> &mut Size {
+        &mut self.size
+    }
+
+    pub fn account_mut(&mut self) -> &mut Option<AccountType> {
+        &mut self.account
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn options_mut(&mut self) -> &mut Options {
+        &mut self.options
+    }
+
+    pub fn wait(&self) -> Option<u64> {
+        self.wait
+    }
+
+    pub fn wait_mut(&mut self) -> &mut Option<u64> {
+        &mut self.wait
+    }
+
+    pub fn is_spot(&self) -> bool {
+        matches!(
+            self.kind,
+            ActionKind::SpotOrder { .. }
+                | ActionKind::TrailingStop { .. }
+                | ActionKind::StopOrder { .. }
+                | ActionKind::Oco { .. }
+        )
+    }
+
+    pub fn is_margin(&self) -> bool {
+        matches!(self.kind, ActionKind::MarginOrder { .. })
+    }
+
+    pub fn is_futures(&self) -> bool {
+        matches!(
+            self.kind,
+            ActionKind::FuturesOrder { .. } | ActionKind::ClosePosition
+        )
+    }
+
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self.kind, ActionKind::Withdraw { .. })
+    }
+
+    pub fn is_tracked(&self) -> bool {
+        matches!(
+            self.kind,
+            ActionKind::SpotOrder { .. } | ActionKind::TrailingStop { .. }
+        )
+    }
+
+    pub fn reserve_fees_mut(&mut self) -> &mut bool {
+        &mut self.reserve_fees
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
     pub fn skip(&self) -> bool {
         self.skip
     }
@@ -110,39 +403,590 @@ impl Action {
         &mut self.skip
     }
 
+    pub fn bracket(&self) -> Option<&Bracket> {
+        match &self.kind {
+            ActionKind::SpotOrder {
+                side: Side::Buy,
+                bracket: Some(bracket),
+                ..
+            } => Some(bracket),
+            _ => None,
+        }
+    }
+
+    pub fn is_armed(&self) -> bool {
+        matches!(
+            self.kind,
+            ActionKind::TrailingStop {
+                extreme: Some(_),
+                ..
+            }
+        )
+    }
+
+    pub fn track(&mut self, kucoin: &KuCoin, arm: bool) -> bool {
+        let ActionKind::TrailingStop { side, extreme, .. } = &mut self.kind else {
+            return false;
+        };
+
+        let Some(last) = kucoin
+            .spot()
+            .tickers()
+            .get(&self.symbol, false)
+            .and_then(|ticker| ticker.last())
+        else {
+            return false;
+        };
+
+        match extreme {
+            Some(value) => {
+                let is_new_extreme = match side {
+                    Side::Sell => last > *value,
+                    Side::Buy => last < *value,
+                };
+
+                if is_new_extreme {
+                    *value = last;
+                }
+
+                is_new_extreme
+            }
+            None if arm => {
+                *extreme = Some(last);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn trigger_price(&self, kucoin: &KuCoin) -> Option<f64> {
+        let ActionKind::TrailingStop {
+            side,
+            callback_rate,
+            extreme: Some(extreme),
+            ..
+        } = &self.kind
+        else {
+            return None;
+        };
+
+        let last = kucoin
+            .spot()
+            .tickers()
+            .get(self.symbol(), false)
+            .and_then(|ticker| ticker.last())?;
+
+        let is_triggered = match side {
+            Side::Sell => last <= extreme * (1.0 - callback_rate / 100.0),
+            Side::Buy => last >= extreme * (1.0 + callback_rate / 100.0),
+        };
+
+        is_triggered
+            .then(|| {
+                kucoin
+                    .spot()
+                    .symbols()
+                    .get(self.symbol())
+                    .map(|symbol| with_increment(last, symbol.price_increment()))
+            })
+            .flatten()
+    }
+
+    pub fn cancelable(&self, kucoin: &KuCoin, strategy: &str) -> Option<Details> {
+        let ActionKind::CancelOrder { scope } = &self.kind else {
+            return None;
+        };
+
+        if self.skip {
+            return None;
+        }
+
+        kucoin
+            .spot()
+            .active()
+            .all()
+            .into_iter()
+            .filter(|order| order.symbol() == self.symbol)
+            .find(|order| match scope {
+                CancelScope::Strategy => {
+                    kucoin.spot().tracker().strategy(order.id()).as_deref() == Some(strategy)
+                }
+                CancelScope::Symbol => true,
+                CancelScope::ClientOid(client_oid) => order.client_oid() == Some(client_oid),
+            })
+    }
+
+    pub fn validate(&self, kucoin: &KuCoin) -> Result<()> {
+        if !(1..=100).contains(&self.percentage) {
+            return Err(anyhow!("percentage must be between 1-100"));
+        }
+
+        let is_spot = self.is_spot();
+        let is_margin = self.is_margin();
+
+        match self.size {
+            Size::Base(amount) | Size::Quote(amount) if amount <= 0.0 => {
+                return Err(anyhow!("size must be positive"));
+            }
+            Size::Quote(_) if !is_spot && !is_margin => {
+                return Err(anyhow!(
+                    "quote value sizing is only supported on spot orders"
+                ));
+            }
+            Size::Reserve(reserve) if reserve < 0.0 => {
+                return Err(anyhow!("reserve must not be negative"));
+            }
+            _ => (),
+        }
+
+        if self.account.is_some() && !is_spot {
+            return Err(anyhow!("source account is only supported on spot orders"));
+        }
+
+        if !self.options.is_empty() && !is_spot {
+            return Err(anyhow!("order options are only supported on spot orders"));
+        }
+
+        match &self.kind {
+            ActionKind::SpotOrder {
+                r#type,
+                side,
+                price,
+                bracket,
+            } => {
+                if let Some(bracket) = bracket {
+                    if let Side::Sell = side {
+                        return Err(anyhow!("brackets are only supported on buy orders"));
+                    }
+
+                    bracket.validate()?;
+                }
+
+                self.options.validate(*r#type)?;
+
+                let increment = kucoin
+                    .spot()
+                    .symbols()
+                    .get(self.symbol())
+                    .map(|symbol| symbol.price_increment())
+                    .ok_or_else(|| anyhow!("pair {} not found", self.symbol))?;
+
+                match (r#type, price) {
+                    (Type::Limit, Some(price)) => price.validate(increment),
+                    (Type::Limit, None) => Err(anyhow!("limit price is missing")),
+                    (Type::Market, _) => Ok(()),
+                }
+            }
+            ActionKind::TrailingStop {
+                r#type,
+                callback_rate,
+                ..
+            } => {
+                if kucoin.spot().symbols().get(self.symbol()).is_none() {
+                    return Err(anyhow!("pair {} not found", self.symbol));
+                }
+
+                self.options.validate(*r#type)?;
+
+                match *callback_rate > 0.0 && *callback_rate < 100.0 {
+                    true => Ok(()),
+                    false => Err(anyhow!("callback rate must be between 0-100")),
+                }
+            }
+            ActionKind::StopOrder {
+                r#type,
+                stop_price,
+                price,
+                ..
+            } => {
+                let increment = kucoin
+                    .spot()
+                    .symbols()
+                    .get(self.symbol())
+                    .map(|symbol| symbol.price_increment())
+                    .ok_or_else(|| anyhow!("pair {} not found", self.symbol))?;
+
+                stop_price.validate(increment)?;
+                self.options.validate(*r#type)?;
+
+                match (r#type, price) {
+                    (Type::Limit, Some(price)) => price.validate(increment),
+                    (Type::Limit, None) => Err(anyhow!("limit price is missing")),
+                    (Type::Market, _) => Ok(()),
+                }
+            }
+            ActionKind::Oco {
+                side,
+                price,
+                stop_price,
+                limit_price,
+            } => {
+                if !self.options.is_empty() {
+                    return Err(anyhow!("order options are not supported on OCO orders"));
+                }
+
+                let increment = kucoin
+                    .spot()
+                    .symbols()
+                    .get(self.symbol())
+                    .map(|symbol| symbol.price_increment())
+                    .ok_or_else(|| anyhow!("pair {} not found", self.symbol))?;
+
+                price.validate(increment)?;
+                stop_price.validate(increment)?;
+                limit_price.validate(increment)?;
+
+                match (side, price, stop_price) {
+                    (Side::Sell, Price::Fixed(price), Price::Fixed(stop_price))
+                        if price <= stop_price =>
+                    {
+                        Err(anyhow!("sell OCO price must be above the stop price"))
+                    }
+                    (Side::Buy, Price::Fixed(price), Price::Fixed(stop_price))
+                        if price >= stop_price =>
+                    {
+                        Err(anyhow!("buy OCO price must be below the stop price"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ActionKind::MarginOrder { r#type, price, .. } => {
+                let increment = kucoin
+                    .spot()
+                    .symbols()
+                    .get(self.symbol())
+                    .map(|symbol| symbol.price_increment())
+                    .ok_or_else(|| anyhow!("pair {} not found", self.symbol))?;
+
+                match (r#type, price) {
+                    (Type::Limit, Some(price)) => price.validate(increment),
+                    (Type::Limit, None) => Err(anyhow!("limit price is missing")),
+                    (Type::Market, _) => Ok(()),
+                }
+            }
+            ActionKind::Borrow | ActionKind::Repay => {
+                match kucoin.spot().currencies().get(self.symbol()) {
+                    Some(_) => Ok(()),
+                    None => Err(anyhow!("currency {} not found", self.symbol)),
+                }
+            }
+            ActionKind::FuturesOrder { leverage, .. } => {
+                let max_leverage = kucoin
+                    .futures()
+                    .contracts()
+                    .get(self.symbol())
+                    .map(|contract| contract.max_leverage())
+                    .ok_or_else(|| anyhow!("contract {} not found", self.symbol))?;
+
+                match (1..=max_leverage).contains(leverage) {
+                    true => Ok(()),
+                    false => Err(anyhow!("leverage must be between 1-{max_leverage}")),
+                }
+            }
+            ActionKind::ClosePosition => {
+                if self.percentage != 100 {
+                    return Err(anyhow!("close position always closes the whole position"));
+                }
+
+                match kucoin.futures().contracts().contains_key(self.symbol()) {
+                    true => Ok(()),
+                    false => Err(anyhow!("contract {} not found", self.symbol)),
+                }
+            }
+            ActionKind::Lend { interest_rate } => {
+                let mut currencies = kucoin.lending().currencies();
+
+                let currency = currencies
+                    .get(self.symbol(), false)
+                    .ok_or_else(|| anyhow!("lending currency {} not found", self.symbol))?;
+
+                match (currency.min_interest_rate()..=currency.max_interest_rate())
+                    .contains(interest_rate)
+                {
+                    true => Ok(()),
+                    false => Err(anyhow!(
+                        "min lending APY must be between {}-{}",
+                        currency.min_interest_rate(),
+                        currency.max_interest_rate()
+                    )),
+                }
+            }
+            ActionKind::Redeem => match kucoin.lending().currencies().get(self.symbol(), false) {
+                Some(_) => Ok(()),
+                None => Err(anyhow!("lending currency {} not found", self.symbol)),
+            },
+            ActionKind::Transfer { from, to, .. } => {
+                if from == to {
+                    return Err(anyhow!("transfer accounts must differ"));
+                }
+
+                match kucoin.spot().currencies().get(self.symbol()) {
+                    Some(_) => Ok(()),
+                    None => Err(anyhow!("currency {} not found", self.symbol)),
+                }
+            }
+            ActionKind::SubTransfer { sub_account, .. } => {
+                if kucoin.sub_accounts().user_id(sub_account).is_none() {
+                    return Err(anyhow!("sub-account {sub_account} not found"));
+                }
+
+                match kucoin.spot().currencies().get(self.symbol()) {
+                    Some(_) => Ok(()),
+                    None => Err(anyhow!("currency {} not found", self.symbol)),
+                }
+            }
+            ActionKind::Withdraw { chain, address } => {
+                let whitelisted = kucoin
+                    .whitelist()
+                    .get(address)
+                    .ok_or_else(|| anyhow!("address {address} is not whitelisted"))?;
+
+                if whitelisted.currency() != self.symbol() || whitelisted.chain() != chain {
+                    return Err(anyhow!(
+                        "address {address} is not whitelisted for {} on {chain}",
+                        self.symbol
+                    ));
+                }
+
+                let currencies = kucoin.spot().currencies();
+
+                let chain = currencies
+                    .get(self.symbol())
+                    .ok_or_else(|| anyhow!("currency {} not found", self.symbol))?
+                    .chain(chain)
+                    .ok_or_else(|| anyhow!("chain {chain} not found"))?;
+
+                if !chain.is_withdraw_enabled() {
+                    return Err(anyhow!("withdrawals are disabled on {}", chain.chain_id()));
+                }
+
+                if chain.need_tag() && whitelisted.memo().is_none() {
+                    return Err(anyhow!("address {address} is missing a memo"));
+                }
+
+                match self.size {
+                    Size::Base(amount) if amount < chain.withdrawal_min_size() => Err(anyhow!(
+                        "size must be at least {}",
+                        chain.withdrawal_min_size()
+                    )),
+                    Size::Base(amount)
+                        if with_increment(
+                            amount,
+                            10_f64.powi(-(chain.withdraw_precision() as i32)),
+                        ) != amount =>
+                    {
+                        Err(anyhow!(
+                            "size must have at most {} decimals",
+                            chain.withdraw_precision()
+                        ))
+                    }
+                    Size::Base(amount) if amount <= chain.withdrawal_fee(amount) => {
+                        Err(anyhow!("size must exceed the withdrawal fee"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ActionKind::CancelOrder { scope } => {
+                if kucoin.spot().symbols().get(self.symbol()).is_none() {
+                    return Err(anyhow!("pair {} not found", self.symbol));
+                }
+
+                match scope {
+                    CancelScope::ClientOid(client_oid) if client_oid.is_empty() => {
+                        Err(anyhow!("client order ID is missing"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ActionKind::Notify { template } => match template.trim().is_empty() {
+                true => Err(anyhow!("message template is missing")),
+                false => Ok(()),
+            },
+            ActionKind::Webhook { event } => {
+                if event.trim().is_empty() {
+                    return Err(anyhow!("webhook event is missing"));
+                }
+
+                webhook::endpoint().map(|_| ())
+            }
+        }
+    }
+
+    /// Checks the request about to be sent against the current trading rules.
+    pub fn check_trade(&self, kucoin: &KuCoin) -> Result<()> {
+        let (r#type, side, price, protected) = match &self.kind {
+            ActionKind::Lend { interest_rate } => {
+                return match kucoin.lending().currencies().get(self.symbol(), false) {
+                    Some(currency) => currency.check_lend(*interest_rate),
+                    None => Err(anyhow!("lending currency {} not found", self.symbol)),
+                }
+            }
+            ActionKind::Redeem => {
+                return match kucoin.lending().currencies().get(self.symbol(), false) {
+                    Some(currency) => currency.check_redeem(),
+                    None => Err(anyhow!("lending currency {} not found", self.symbol)),
+                }
+            }
+            ActionKind::SpotOrder { side, price, .. } => {
+                let r#type = match price {
+                    Some(_) => Type::Limit,
+                    None => Type::Market,
+                };
+
+                (r#type, *side, Some(self.price(kucoin)), true)
+            }
+            ActionKind::MarginOrder { r#type, side, .. }
+            | ActionKind::TrailingStop { r#type, side, .. } => {
+                (*r#type, *side, Some(self.price(kucoin)), true)
+            }
+            ActionKind::StopOrder {
+                r#type,
+                side,
+                price,
+                ..
+            } => (
+                *r#type,
+                *side,
+                Some(price.and_then(|price| price.resolve(kucoin, self.symbol()))),
+                false,
+            ),
+            ActionKind::Oco {
+                side, limit_price, ..
+            } => (
+                Type::Limit,
+                *side,
+                Some(limit_price.resolve(kucoin, self.symbol())),
+                false,
+            ),
+            _ => return Ok(()),
+        };
+
+        let now = kucoin.now();
+        let amount = self.amount(kucoin);
+        let last = kucoin
+            .spot()
+            .tickers()
+            .get(self.symbol(), false)
+            .and_then(|ticker| ticker.last());
+        let oco_price = match &self.kind {
+            ActionKind::Oco { price, .. } => price.resolve(kucoin, self.symbol()),
+            _ => None,
+        };
+
+        let symbols = kucoin.spot().symbols();
+        let symbol = symbols
+            .get(self.symbol())
+            .ok_or_else(|| anyhow!("symbol {} not found", self.symbol))?;
+
+        symbol.check_trading(r#type, side, now)?;
+
+        let price = match (r#type, price.flatten()) {
+            (Type::Limit, Some(price)) => {
+                symbol.check_price(side, price, last, protected, now)?;
+
+                if let Some(price) = oco_price {
+                    symbol.check_price(side, price, last, false, now)?;
+                }
+
+                Some(price)
+            }
+            (Type::Limit, None) => return Err(anyhow!("limit price is not available")),
+            (Type::Market, _) => last,
+        };
+
+        let funds = match (r#type, side) {
+            (Type::Market, Side::Buy) => amount,
+            _ => amount.zip(price).map(|(size, price)| size * price),
+        };
+
+        match funds {
+            Some(funds) => symbol.check_funds(funds),
+            None => Ok(()),
+        }
+    }
+
+    pub fn executed(&mut self) -> bool {
+        if let ActionKind::TrailingStop { extreme, .. } = &mut self.kind {
+            *extreme = None;
+        }
+
+        if self.percentage != 100
+            || self.size.is_fixed()
+            || matches!(
+                self.kind,
+                ActionKind::StopOrder { .. }
+                    | ActionKind::Borrow
+                    | ActionKind::Withdraw { .. }
+                    | ActionKind::Notify { .. }
+                    | ActionKind::Webhook { .. }
+            )
+        {
+            self.skip = true;
+        }
+
+        self.skip || matches!(self.kind, ActionKind::TrailingStop { .. })
+    }
+
     pub fn amount(&self, kucoin: &KuCoin) -> Option<f64> {
         let percentage = self.percentage() as f64 / 100.0;
 
         match &self.kind {
-            ActionKind::SpotOrder { r#type, side, .. } => kucoin
-                .spot()
-                .symbols()
-                .get(self.symbol())
-                .map(|symbol| {
-                    if let (Type::Market, Side::Buy) = (r#type, side) {
-                        (
-                            symbol.quote_currency(),
-                            symbol.quote_increment(),
-                            symbol.quote_min_size(),
-                            symbol.quote_max_size(),
-                        )
-                    } else {
-                        (
-                            symbol.base_currency(),
-                            symbol.base_increment(),
-                            symbol.base_min_size(),
-                            symbol.base_max_size(),
-                        )
+            ActionKind::SpotOrder { .. }
+            | ActionKind::TrailingStop { .. }
+            | ActionKind::StopOrder { .. }
+            | ActionKind::Oco { .. }
+            | ActionKind::MarginOrder { .. } => {
+                self.spot_funds(kucoin).map(|(_, _, amount)| amount)
+            }
+            ActionKind::Borrow | ActionKind::Repay => {
+                let increment = kucoin
+                    .spot()
+                    .currencies()
+                    .get(self.symbol())
+                    .map(|currency| 10_f64.powi(currency.precision().into()).recip())?;
+
+                let account = kucoin.margin().account();
+                let asset = account.asset(self.symbol())?;
+
+                let (available, total) = match self.kind {
+                    ActionKind::Borrow => (asset.max_borrow_size(), asset.max_borrow_size()),
+                    _ => (asset.liability().min(asset.available()), asset.liability()),
+                };
+
+                Some(self.size.amount(available, total, percentage))
+                    .map(|amount| with_increment(amount, increment))
+                    .filter(|amount| *amount >= increment)
+            }
+            ActionKind::FuturesOrder { leverage, .. } => {
+                let contracts = kucoin.futures().contracts();
+                let contract = contracts.get(self.symbol())?;
+
+                let lots = match self.size {
+                    Size::Base(lots) => lots,
+                    size => {
+                        let overview = kucoin.futures().overview();
+                        let overview = overview
+                            .as_ref()
+                            .filter(|overview| overview.currency() == contract.settle_currency())?;
+
+                        let margin = size.amount(
+                            overview.available_balance(),
+                            overview.account_equity(),
+                            percentage,
+                        );
+
+                        margin * *leverage as f64 / (contract.mark_price()? * contract.multiplier())
                     }
-                })
-                .and_then(|(currency, increment, min_size, max_size)| {
-                    kucoin
-                        .accounts()
-                        .available(&AccountType::Trade, currency)
-                        .map(|amount| amount * percentage)
-                        .map(|amount| with_increment(amount, increment))
-                        .filter(|amount| (min_size..=max_size).contains(amount))
-                }),
+                };
+
+                Some(contract.lots(lots) as f64)
+                    .filter(|lots| (contract.lot_size()..=contract.max_order_qty()).contains(lots))
+            }
+            ActionKind::ClosePosition => kucoin
+                .futures()
+                .positions()
+                .get(self.symbol())
+                .map(|position| position.current_qty().abs()),
             ActionKind::Lend { .. } => kucoin
                 .lending()
                 .currencies()
@@ -151,7 +995,14 @@ impl Action {
                     kucoin
                         .accounts()
                         .available(&AccountType::Main, self.symbol())
-                        .map(|amount| amount * percentage)
+                        .map(|available| {
+                            let total = kucoin
+                                .accounts()
+                                .total(&AccountType::Main, self.symbol())
+                                .unwrap_or(available);
+
+                            self.size.amount(available, total, percentage)
+                        })
                         .map(|amount| with_increment(amount, currency.increment()))
                         .filter(|amount| {
                             (currency.min_purchase_size()..=currency.max_purchase_size())
@@ -168,7 +1019,13 @@ impl Action {
                         .lending()
                         .orders()
                         .get(self.symbol())
-                        .map(|order| order.purchase_size() * percentage)
+                        .map(|order| {
+                            self.size.amount(
+                                order.purchase_size(),
+                                order.purchase_size(),
+                                percentage,
+                            )
+                        })
                         .map(|amount| with_increment(amount, increment))
                         .filter(|amount| *amount >= increment)
                 }),
@@ -181,10 +1038,446 @@ impl Action {
                     kucoin
                         .accounts()
                         .available(from, self.symbol())
-                        .map(|amount| amount * percentage)
+                        .map(|available| {
+                            let total = kucoin
+                                .accounts()
+                                .total(from, self.symbol())
+                                .unwrap_or(available);
+
+                            self.size.amount(available, total, percentage)
+                        })
                         .map(|amount| (amount * precision).trunc() / precision)
                         .filter(|amount| *amount >= precision.recip())
                 }),
+            ActionKind::SubTransfer {
+                sub_account,
+                direction,
+                from,
+                ..
+            } => {
+                let precision = kucoin
+                    .spot()
+                    .currencies()
+                    .get(self.symbol())
+                    .map(|currency| 10_f64.powi(currency.precision().into()))?;
+
+                let (available, total) = match direction {
+                    Direction::ToSub => (
+                        kucoin.accounts().available(from, self.symbol()),
+                        kucoin.accounts().total(from, self.symbol()),
+                    ),
+                    Direction::ToParent => (
+                        kucoin
+                            .sub_accounts()
+                            .available(sub_account, from, self.symbol()),
+                        kucoin
+                            .sub_accounts()
+                            .total(sub_account, from, self.symbol()),
+                    ),
+                };
+
+                available
+                    .map(|available| {
+                        self.size
+                            .amount(available, total.unwrap_or(available), percentage)
+                    })
+                    .map(|amount| (amount * precision).trunc() / precision)
+                    .filter(|amount| *amount >= precision.recip())
+            }
+            ActionKind::Withdraw { chain, .. } => {
+                let currencies = kucoin.spot().currencies();
+                let chain = currencies.get(self.symbol())?.chain(chain)?;
+
+                let available = kucoin
+                    .accounts()
+                    .available(&AccountType::Main, self.symbol())?;
+                let total = kucoin
+                    .accounts()
+                    .total(&AccountType::Main, self.symbol())
+                    .unwrap_or(available);
+
+                Some(self.size.amount(available, total, percentage))
+                    .map(|amount| {
+                        with_increment(amount, 10_f64.powi(-(chain.withdraw_precision() as i32)))
+                    })
+                    .filter(|amount| {
+                        *amount >= chain.withdrawal_min_size()
+                            && *amount > chain.withdrawal_fee(*amount)
+                    })
+            }
+            ActionKind::CancelOrder { .. }
+            | ActionKind::Notify { .. }
+            | ActionKind::Webhook { .. } => None,
+        }
+    }
+
+    /// Transfer that moves the funds of a spot order into the trade account,
+    /// along with the transfer action it's checked against the risk limits as.
+    pub fn funding(&self, kucoin: &KuCoin) -> Option<(Action, Transfer)> {
+        let account = self
+            .account
+            .filter(|account| *account != AccountType::Trade)?;
+
+        let (currency, funds, _) = self.spot_funds(kucoin).filter(|_| self.is_spot())?;
+
+        let precision = kucoin
+            .spot()
+            .currencies()
+            .get(&currency)
+            .map(|currency| 10_f64.powi(currency.precision().into()))?;
+
+        let amount = (funds * precision).ceil() / precision;
+
+        let action = Action {
+            symbol: currency.clone(),
+            percentage: 100,
+            size: Size::Base(amount),
+            skip: false,
+            ..Action::new(ActionKind::Transfer {
+                from: account,
+                to: AccountType::Trade,
+                from_account_tag: None,
+                to_account_tag: None,
+            })
+        };
+
+        Some((
+            action,
+            Transfer::internal(currency, amount, account, AccountType::Trade, None, None),
+        ))
+    }
+
+    /// Price the order would be placed at, or the last price for market orders.
+    pub fn price(&self, kucoin: &KuCoin) -> Option<f64> {
+        match &self.kind {
+            ActionKind::SpotOrder { price, .. } | ActionKind::MarginOrder { price, .. } => price
+                .and_then(|price| price.resolve(kucoin, self.symbol()))
+                .or_else(|| {
+                    kucoin
+                        .spot()
+                        .tickers()
+                        .get(self.symbol(), false)
+                        .and_then(|ticker| ticker.last())
+                }),
+            ActionKind::TrailingStop { .. } => self.trigger_price(kucoin),
+            ActionKind::StopOrder {
+                stop_price, price, ..
+            } => price.unwrap_or(*stop_price).resolve(kucoin, self.symbol()),
+            ActionKind::Oco { price, .. } => price.resolve(kucoin, self.symbol()),
+            ActionKind::FuturesOrder { .. } | ActionKind::ClosePosition => kucoin
+                .futures()
+                .contracts()
+                .get(self.symbol())
+                .and_then(|contract| contract.mark_price()),
+            _ => None,
+        }
+    }
+
+    /// Traded asset and its value in the quote currency.
+    pub fn exposure(&self, kucoin: &KuCoin) -> Option<(String, f64)> {
+        match &self.kind {
+            ActionKind::SpotOrder { .. }
+            | ActionKind::TrailingStop { .. }
+            | ActionKind::StopOrder { .. }
+            | ActionKind::Oco { .. }
+            | ActionKind::MarginOrder { .. } => {
+                let (currency, funds, _) = self.spot_funds(kucoin)?;
+                let price = kucoin.spot().quote_price(&currency)?;
+                let asset = kucoin
+                    .spot()
+                    .symbols()
+                    .get(self.symbol())
+                    .map(|symbol| symbol.base_currency().to_string())?;
+
+                Some((asset, funds * price))
+            }
+            ActionKind::FuturesOrder { .. } | ActionKind::ClosePosition => {
+                let lots = self.amount(kucoin)?;
+                let contracts = kucoin.futures().contracts();
+                let contract = contracts.get(self.symbol())?;
+
+                Some((
+                    self.symbol().to_string(),
+                    lots * contract.multiplier() * contract.mark_price()?,
+                ))
+            }
+            ActionKind::CancelOrder { .. }
+            | ActionKind::Notify { .. }
+            | ActionKind::Webhook { .. } => None,
+            _ => {
+                let amount = self.amount(kucoin)?;
+                let price = kucoin.spot().quote_price(self.symbol())?;
+
+                Some((self.symbol().to_string(), amount * price))
+            }
+        }
+    }
+
+    /// Estimated fee of a spot or margin order and the currency it's charged in.
+    pub fn fee(&self, kucoin: &KuCoin) -> Option<(String, f64)> {
+        let r#type = match &self.kind {
+            ActionKind::SpotOrder { r#type, .. }
+            | ActionKind::TrailingStop { r#type, .. }
+            | ActionKind::StopOrder { r#type, .. }
+            | ActionKind::MarginOrder { r#type, .. } => *r#type,
+            ActionKind::Oco { .. } => Type::Limit,
+            _ => return None,
+        };
+
+        let (currency, funds, _) = self.spot_funds(kucoin)?;
+
+        let (quote, fee_currency) = {
+            let symbols = kucoin.spot().symbols();
+            let symbol = symbols.get(self.symbol())?;
+
+            (
+                symbol.quote_currency().to_string(),
+                symbol.fee_currency().to_string(),
+            )
+        };
+
+        let value = match currency == quote {
+            true => funds,
+            false => funds * self.price(kucoin)?,
+        };
+
+        let spot = kucoin.spot();
+        let fee = value * spot.fee_rate(self.symbol(), r#type)?;
+
+        let fee = match fee_currency == quote {
+            true => fee,
+            false => fee * spot.quote_price(&quote)? / spot.quote_price(&fee_currency)?,
+        };
+
+        Some(match self.kcs_fee(kucoin, &fee_currency, fee) {
+            Some(fee) => (KCS.to_string(), fee),
+            None => (fee_currency, fee),
+        })
+    }
+
+    /// Discounted fee in KCS when fee deduction is enabled and the balance covers it.
+    fn kcs_fee(&self, kucoin: &KuCoin, currency: &str, fee: f64) -> Option<f64> {
+        if !is_kcs_fee_deduction() {
+            return None;
+        }
+
+        let account = match self.kind {
+            ActionKind::MarginOrder { .. } => AccountType::Margin,
+            _ => AccountType::Trade,
+        };
+
+        let spot = kucoin.spot();
+        let fee =
+            fee * (1.0 - KCS_FEE_DISCOUNT) * spot.quote_price(currency)? / spot.quote_price(KCS)?;
+
+        kucoin
+            .accounts()
+            .available(&account, KCS)
+            .filter(|available| *available >= fee)
+            .map(|_| fee)
+    }
+
+    /// Leaves room for a fee charged in the spent currency, so that spending
+    /// the whole balance doesn't fail.
+    fn reserve_fee(&self, kucoin: &KuCoin, r#type: Type, currency: &str, spend: f64) -> f64 {
+        let Some(rate) = kucoin.spot().fee_rate(self.symbol(), r#type) else {
+            return spend;
+        };
+
+        let in_kcs = self.kcs_fee(kucoin, currency, spend * rate).is_some();
+
+        reserve_fee(spend, rate, in_kcs)
+    }
+
+    fn spot_funds(&self, kucoin: &KuCoin) -> Option<(String, f64, f64)> {
+        match &self.kind {
+            ActionKind::SpotOrder {
+                r#type,
+                side,
+                price,
+                ..
+            }
+            | ActionKind::MarginOrder {
+                r#type,
+                side,
+                price,
+                ..
+            } => self.spot_amount(
+                kucoin,
+                *r#type,
+                *side,
+                price.and_then(|price| price.resolve(kucoin, self.symbol())),
+            ),
+            ActionKind::TrailingStop { r#type, side, .. } => {
+                self.spot_amount(kucoin, *r#type, *side, self.trigger_price(kucoin))
+            }
+            ActionKind::StopOrder {
+                r#type,
+                side,
+                stop_price,
+                price,
+                ..
+            } => self.spot_amount(
+                kucoin,
+                *r#type,
+                *side,
+                price.unwrap_or(*stop_price).resolve(kucoin, self.symbol()),
+            ),
+            ActionKind::Oco {
+                side,
+                price,
+                limit_price,
+                ..
+            } => self.spot_amount(
+                kucoin,
+                Type::Limit,
+                *side,
+                self.oco_price(*price, *limit_price, *side, kucoin),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Buy OCO orders are sized for the higher of both limit prices.
+    fn oco_price(
+        &self,
+        price: Price,
+        limit_price: Price,
+        side: Side,
+        kucoin: &KuCoin,
+    ) -> Option<f64> {
+        let price = price.resolve(kucoin, self.symbol())?;
+        let limit_price = limit_price.resolve(kucoin, self.symbol())?;
+
+        Some(match side {
+            Side::Buy => price.max(limit_price),
+            Side::Sell => price,
+        })
+    }
+
+    fn spot_amount(
+        &self,
+        kucoin: &KuCoin,
+        r#type: Type,
+        side: Side,
+        price: Option<f64>,
+    ) -> Option<(String, f64, f64)> {
+        let percentage = self.percentage() as f64 / 100.0;
+
+        let account = match self.kind {
+            ActionKind::MarginOrder { .. } => AccountType::Margin,
+            _ => self.account.unwrap_or(AccountType::Trade),
+        };
+
+        let price = price.or_else(|| {
+            kucoin
+                .spot()
+                .tickers()
+                .get(self.symbol(), false)
+                .and_then(|ticker| ticker.last())
+        });
+
+        let (currency, fee_currency) = {
+            let symbols = kucoin.spot().symbols();
+            let symbol = symbols.get(self.symbol())?;
+
+            let currency = match side {
+                Side::Buy => symbol.quote_currency(),
+                Side::Sell => symbol.base_currency(),
+            };
+
+            (currency.to_string(), symbol.fee_currency().to_string())
+        };
+
+        let available = kucoin.accounts().available(&account, &currency);
+        let total = kucoin.accounts().total(&account, &currency);
+
+        let spend = match (self.size, side) {
+            (Size::Base(amount), Side::Buy) => amount * price?,
+            (Size::Quote(amount), Side::Sell) => amount / price?,
+            (size, _) if size.is_fixed() => size.amount(0.0, 0.0, percentage),
+            (size, _) => size.amount(available?, total.or(available)?, percentage),
+        };
+
+        let spend = match self.reserve_fees && !self.size.is_fixed() && currency == fee_currency {
+            true => self.reserve_fee(kucoin, r#type, &currency, spend),
+            false => spend,
+        };
+
+        let symbols = kucoin.spot().symbols();
+        let symbol = symbols.get(self.symbol())?;
+
+        let (amount, increment, min_size, max_size) = match (r#type, side) {
+            (Type::Market, Side::Buy) => (
+                spend,
+                symbol.quote_increment(),
+                symbol.quote_min_size(),
+                symbol.quote_max_size(),
+            ),
+            (_, Side::Buy) => (
+                spend / price?,
+                symbol.base_increment(),
+                symbol.base_min_size(),
+                symbol.base_max_size(),
+            ),
+            (_, Side::Sell) => (
+                spend,
+                symbol.base_increment(),
+                symbol.base_min_size(),
+                symbol.base_max_size(),
+            ),
+        };
+
+        let amount = with_increment(amount, increment);
+
+        (min_size..=max_size).contains(&amount).then(|| {
+            let funds = match (r#type, side) {
+                (Type::Limit, Side::Buy) => amount * price.unwrap_or_default(),
+                _ => amount,
+            };
+
+            (currency, funds, amount)
+        })
+    }
+
+    fn size_text(&self) -> String {
+        let size = match self.size {
+            Size::Available => format!("{}%", self.percentage),
+            Size::Total => format!("{}% OF TOTAL", self.percentage),
+            Size::Base(amount) => amount.to_string(),
+            Size::Quote(amount) => format!("{amount} IN QUOTE"),
+            Size::Reserve(reserve) => format!("ALL BUT {reserve}"),
+        };
+
+        let size = match self.reserve_fees {
+            true => format!("{size} LESS FEES"),
+            false => size,
+        };
+
+        let size = match self.account {
+            Some(account) => format!("{size} FROM {}", account.to_string().to_uppercase()),
+            None => size,
+        };
+
+        let size = match self.options.is_empty() {
+            true => size,
+            false => format!("{size} {}", self.options),
+        };
+
+        match self.wait {
+            Some(wait) => format!("{size} AFTER FILL ({wait}s)"),
+            None => size,
+        }
+    }
+}
+
+impl Action {
+    /// Plain-text description, the displayed one having its user text HTML-escaped.
+    pub fn label(&self) -> String {
+        match &self.kind {
+            ActionKind::Notify { template } => format!("NOTIFY \"{template}\""),
+            ActionKind::Webhook { event } => format!("WEBHOOK {event}"),
+            _ => self.to_string(),
         }
     }
 }
@@ -192,28 +1485,97 @@ impl Action {
 impl fmt::Display for Action {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let symbol = self.symbol();
-        let percentage = self.percentage();
+        let size = self.size_text();
 
         match &self.kind {
             ActionKind::SpotOrder {
                 r#type,
                 side,
                 price,
+                bracket,
             } => {
                 write!(
                     f,
-                    "{} {} {symbol} {percentage}% {}",
+                    "{} {} {symbol} {size} {}{}",
                     r#type,
                     side,
-                    price.map_or(Default::default(), |price| format!("@ {price}"))
+                    price.map_or(Default::default(), |price| format!("@ {price}")),
+                    bracket
+                        .as_ref()
+                        .map_or(Default::default(), |bracket| format!(" {bracket}"))
                 )
             }
-            ActionKind::Lend { interest_rate } => write!(
+            ActionKind::TrailingStop {
+                r#type,
+                side,
+                callback_rate,
+                extreme,
+            } => write!(
                 f,
-                "LEND {symbol} {percentage}% MIN LENDING APY {}%",
-                interest_rate
+                "TRAILING STOP {} {} {symbol} {size} CALLBACK {}% ({})",
+                r#type,
+                side,
+                callback_rate,
+                match (side, extreme) {
+                    (Side::Sell, Some(extreme)) => format!("HIGH {extreme}"),
+                    (Side::Buy, Some(extreme)) => format!("LOW {extreme}"),
+                    (_, None) => "NOT ARMED".to_string(),
+                }
             ),
-            ActionKind::Redeem => write!(f, "REDEEM {symbol} {percentage}%"),
+            ActionKind::StopOrder {
+                r#type,
+                side,
+                stop,
+                stop_price,
+                price,
+            } => write!(
+                f,
+                "STOP {} {} {symbol} {size} {} {}{}",
+                r#type,
+                side,
+                stop,
+                stop_price,
+                price.map_or(Default::default(), |price| format!(" @ {price}"))
+            ),
+            ActionKind::Oco {
+                side,
+                price,
+                stop_price,
+                limit_price,
+            } => write!(
+                f,
+                "OCO {side} {symbol} {size} @ {price} STOP {stop_price} @ {limit_price}"
+            ),
+            ActionKind::MarginOrder {
+                r#type,
+                side,
+                price,
+                auto_borrow,
+                auto_repay,
+            } => write!(
+                f,
+                "MARGIN {} {} {symbol} {size} {}{}{}",
+                r#type,
+                side,
+                price.map_or(Default::default(), |price| format!("@ {price}")),
+                if *auto_borrow { " AUTO-BORROW" } else { "" },
+                if *auto_repay { " AUTO-REPAY" } else { "" },
+            ),
+            ActionKind::Borrow => write!(f, "BORROW {symbol} {size}"),
+            ActionKind::Repay => write!(f, "REPAY {symbol} {size}"),
+            ActionKind::FuturesOrder { side, leverage } => write!(
+                f,
+                "FUTURES {} {symbol} {size} {leverage}x",
+                match side {
+                    Side::Buy => "LONG",
+                    Side::Sell => "SHORT",
+                }
+            ),
+            ActionKind::ClosePosition => write!(f, "CLOSE POSITION {symbol}"),
+            ActionKind::Lend { interest_rate } => {
+                write!(f, "LEND {symbol} {size} MIN LENDING APY {}%", interest_rate)
+            }
+            ActionKind::Redeem => write!(f, "REDEEM {symbol} {size}"),
             ActionKind::Transfer {
                 from,
                 to,
@@ -221,18 +1583,76 @@ impl fmt::Display for Action {
                 to_account_tag,
             } => write!(
                 f,
-                "TRANSFER {symbol} {percentage}% ({} {} -> {} {})",
+                "TRANSFER {symbol} {size} ({} {} -> {} {})",
                 from.to_string().to_uppercase(),
                 from_account_tag.as_ref().map_or("", |v| v),
                 to.to_string().to_uppercase(),
                 to_account_tag.as_ref().map_or("", |v| v),
             ),
+            ActionKind::SubTransfer {
+                sub_account,
+                direction,
+                from,
+                to,
+            } => {
+                let (from, to) = (
+                    from.to_string().to_uppercase(),
+                    to.to_string().to_uppercase(),
+                );
+
+                match direction {
+                    Direction::ToSub => {
+                        write!(f, "TRANSFER {symbol} {size} ({from} -> {sub_account} {to})")
+                    }
+                    Direction::ToParent => {
+                        write!(f, "TRANSFER {symbol} {size} ({sub_account} {from} -> {to})")
+                    }
+                }
+            }
+            ActionKind::Withdraw { chain, address } => {
+                write!(f, "WITHDRAW {symbol} {size} VIA {chain} TO {address}")
+            }
+            ActionKind::CancelOrder { scope } => match scope {
+                CancelScope::Strategy => write!(f, "CANCEL STRATEGY ORDERS {symbol}"),
+                CancelScope::Symbol => write!(f, "CANCEL ALL ORDERS {symbol}"),
+                CancelScope::ClientOid(client_oid) => {
+                    write!(f, "CANCEL ORDER {symbol} {client_oid}")
+                }
+            },
+            ActionKind::Notify { template } => write!(f, "NOTIFY \"{}\"", html::escape(template)),
+            ActionKind::Webhook { event } => write!(f, "WEBHOOK {}", html::escape(event)),
         }
     }
 }
 
-fn with_increment(value: f64, increment: f64) -> f64 {
-    let decimals = increment.log10().abs() as i32;
-    let precision = 10_f64.powi(decimals);
-    (value * precision).trunc() / precision
+/// Part of the spend left for the order once a fee at the rate is set aside,
+/// unless the fee is paid in KCS instead.
+fn reserve_fee(spend: f64, rate: f64, in_kcs: bool) -> f64 {
+    match in_kcs {
+        true => spend,
+        false => spend / (1.0 + rate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_fee_leaves_room_for_the_fee() {
+        let spend = reserve_fee(100.0, 0.001, false);
+
+        assert!(spend < 100.0);
+        assert!((spend * (1.0 + 0.001) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reserve_fee_keeps_the_spend_when_paid_in_kcs() {
+        assert_eq!(reserve_fee(100.0, 0.001, true), 100.0);
+    }
+
+    #[test]
+    fn reserve_fee_keeps_the_spend_without_a_fee() {
+        assert_eq!(reserve_fee(100.0, 0.0, false), 100.0);
+    }
 }
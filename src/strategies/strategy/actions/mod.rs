@@ -1,17 +1,41 @@
+use serde::{Deserialize, Serialize};
 use std::ops::Deref;
 
-use crate::kucoin::{
-    account::Transfer,
-    lending::{Lend, Redeem},
-    request::Request,
-    trading::spot::order,
-    KuCoin,
+use crate::{
+    kucoin::{
+        account::Transfer,
+        futures::AddFutures,
+        lending::{Lend, Redeem},
+        margin::{AddMargin, Borrow, Repay},
+        request::Request,
+        trading::spot::{
+            oco::AddOco,
+            order::{self, Type},
+            stop::AddStop,
+        },
+        withdrawal::Withdraw,
+        KuCoin,
+    },
+    strategies::alert::Alert,
 };
 
 mod action;
-pub use action::{Action, ActionKind};
+pub use action::{Action, ActionKind, CancelScope, Direction};
 
-#[derive(Debug, Default, Clone)]
+mod price;
+pub use price::{Offset, Price, Reference};
+
+mod size;
+pub use size::Size;
+
+/// What running an action amounts to.
+#[derive(Debug)]
+pub enum Execution {
+    Request(Request),
+    Alert(Alert),
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Actions(Vec<Action>);
 
 impl Actions {
@@ -19,6 +43,10 @@ impl Actions {
         self.0.push(action);
     }
 
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Action> {
+        self.0.get_mut(index)
+    }
+
     pub fn remove(&mut self, index: usize) {
         self.0.remove(index);
     }
@@ -31,12 +59,50 @@ impl Actions {
         }
     }
 
-    pub fn executable(&mut self, kucoin: &KuCoin) -> Option<(&mut Action, Request)> {
-        self.0.iter_mut().find_map(|action| {
-            Request::try_from((action.deref(), kucoin))
-                .map(|request| (action, request))
-                .ok()
-        })
+    pub fn is_armed(&self) -> bool {
+        self.0
+            .iter()
+            .any(|action| !action.skip() && action.is_armed())
+    }
+
+    pub fn track(&mut self, kucoin: &KuCoin, arm: bool) -> bool {
+        let mut changed = false;
+
+        for action in self.0.iter_mut().filter(|action| !action.skip()) {
+            changed |= action.track(kucoin, arm);
+        }
+
+        changed
+    }
+
+    pub fn executable(
+        &mut self,
+        kucoin: &KuCoin,
+        strategy: &str,
+        armed_only: bool,
+    ) -> Option<(usize, &mut Action, Execution)> {
+        self.0
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, action)| !armed_only || action.is_armed())
+            .find_map(|(index, action)| {
+                let maybe_execution = match action.kind() {
+                    ActionKind::CancelOrder { .. } => action
+                        .cancelable(kucoin, strategy)
+                        .map(|order| Execution::Request(order::cancel(order.id(), order.symbol()))),
+                    ActionKind::Notify { template } => {
+                        (!action.skip()).then(|| Execution::Alert(Alert::Notify(template.clone())))
+                    }
+                    ActionKind::Webhook { event } => {
+                        (!action.skip()).then(|| Execution::Alert(Alert::Webhook(event.clone())))
+                    }
+                    _ => Request::try_from((action.deref(), kucoin))
+                        .ok()
+                        .map(Execution::Request),
+                };
+
+                maybe_execution.map(|execution| (index, action, execution))
+            })
     }
 }
 
@@ -62,10 +128,102 @@ impl TryFrom<(&Action, &KuCoin)> for Request {
 
                 match action.kind() {
                     ActionKind::SpotOrder { side, price, .. } => Ok(match price {
-                        Some(price) => order::Add::limit(symbol, *side, *price, amount),
+                        Some(price) => {
+                            let price = price.resolve(kucoin, &symbol).ok_or(())?;
+
+                            order::Add::limit(symbol, *side, price, amount)
+                        }
                         None => order::Add::market(symbol, *side, amount),
                     }
+                    .options(action.options())
                     .into()),
+                    ActionKind::TrailingStop { r#type, side, .. } => {
+                        let price = action.trigger_price(kucoin).ok_or(())?;
+
+                        Ok(match r#type {
+                            Type::Limit => order::Add::limit(symbol, *side, price, amount),
+                            Type::Market => order::Add::market(symbol, *side, amount),
+                        }
+                        .options(action.options())
+                        .into())
+                    }
+                    ActionKind::StopOrder {
+                        r#type,
+                        side,
+                        stop,
+                        stop_price,
+                        price,
+                    } => {
+                        let stop_price = stop_price.resolve(kucoin, &symbol).ok_or(())?;
+
+                        let order = match (r#type, price) {
+                            (Type::Limit, Some(price)) => {
+                                let price = price.resolve(kucoin, &symbol).ok_or(())?;
+
+                                order::Add::limit(symbol, *side, price, amount)
+                            }
+                            (Type::Limit, None) => return Err(()),
+                            (Type::Market, _) => order::Add::market(symbol, *side, amount),
+                        };
+
+                        Ok(AddStop::new(order.options(action.options()), *stop, stop_price).into())
+                    }
+                    ActionKind::Oco {
+                        side,
+                        price,
+                        stop_price,
+                        limit_price,
+                    } => {
+                        let price = price.resolve(kucoin, &symbol).ok_or(())?;
+                        let stop_price = stop_price.resolve(kucoin, &symbol).ok_or(())?;
+                        let limit_price = limit_price.resolve(kucoin, &symbol).ok_or(())?;
+
+                        Ok(
+                            AddOco::new(symbol, *side, amount, price, stop_price, limit_price)
+                                .into(),
+                        )
+                    }
+                    ActionKind::MarginOrder {
+                        side,
+                        price,
+                        auto_borrow,
+                        auto_repay,
+                        ..
+                    } => {
+                        let order = match price {
+                            Some(price) => {
+                                let price = price.resolve(kucoin, &symbol).ok_or(())?;
+
+                                order::Add::limit(symbol, *side, price, amount)
+                            }
+                            None => order::Add::market(symbol, *side, amount),
+                        };
+
+                        Ok(AddMargin::new(order, *auto_borrow, *auto_repay).into())
+                    }
+                    ActionKind::Borrow => Ok(Borrow::new(symbol, amount).into()),
+                    ActionKind::Repay => Ok(Repay::new(symbol, amount).into()),
+                    ActionKind::FuturesOrder { side, leverage } => {
+                        let lots = kucoin
+                            .futures()
+                            .contracts()
+                            .get(&symbol)
+                            .map(|contract| contract.lots(amount))
+                            .filter(|lots| *lots > 0)
+                            .ok_or(())?;
+
+                        Ok(AddFutures::market(symbol, *side, *leverage, lots).into())
+                    }
+                    ActionKind::ClosePosition => {
+                        let side = kucoin
+                            .futures()
+                            .positions()
+                            .get(&symbol)
+                            .map(|position| position.closing_side())
+                            .ok_or(())?;
+
+                        Ok(AddFutures::close(symbol, side).into())
+                    }
                     ActionKind::Lend { interest_rate } => {
                         Ok(Lend::new(symbol, *interest_rate, amount).into())
                     }
@@ -92,6 +250,37 @@ impl TryFrom<(&Action, &KuCoin)> for Request {
                         to_account_tag.clone(),
                     )
                     .into()),
+                    ActionKind::SubTransfer {
+                        sub_account,
+                        direction,
+                        from,
+                        to,
+                    } => {
+                        let sub_user_id = kucoin.sub_accounts().user_id(sub_account).ok_or(())?;
+
+                        Ok(match direction {
+                            Direction::ToSub => {
+                                Transfer::parent_to_sub(symbol, amount, *from, *to, sub_user_id)
+                            }
+                            Direction::ToParent => {
+                                Transfer::sub_to_parent(symbol, amount, *from, *to, sub_user_id)
+                            }
+                        }
+                        .into())
+                    }
+                    ActionKind::Withdraw { chain, address } => {
+                        let address = kucoin
+                            .whitelist()
+                            .get(address)
+                            .filter(|address| address.currency() == symbol)
+                            .filter(|address| address.chain() == chain)
+                            .ok_or(())?;
+
+                        Ok(Withdraw::new(amount, address).into())
+                    }
+                    ActionKind::CancelOrder { .. }
+                    | ActionKind::Notify { .. }
+                    | ActionKind::Webhook { .. } => Err(()),
                 }
             }
             None => Err(()),
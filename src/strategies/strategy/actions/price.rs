@@ -0,0 +1,242 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use strum::{Display, EnumString};
+
+use crate::kucoin::{is_on_increment, with_increment, KuCoin};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Price {
+    Fixed(f64),
+    Relative {
+        reference: Reference,
+        offset: Offset,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "UPPERCASE", ascii_case_insensitive)]
+pub enum Reference {
+    Last,
+    Bid,
+    Ask,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Offset {
+    Percent(f64),
+    Ticks(i64),
+}
+
+impl Price {
+    pub fn resolve(&self, kucoin: &KuCoin, symbol: &str) -> Option<f64> {
+        let (reference, offset) = match self {
+            Price::Fixed(price) => return Some(*price),
+            Price::Relative { reference, offset } => (reference, offset),
+        };
+
+        let (base, last) = kucoin
+            .spot()
+            .tickers()
+            .get(symbol, false)
+            .and_then(|ticker| {
+                let base = match reference {
+                    Reference::Last => ticker.last(),
+                    Reference::Bid => ticker.best_bid(),
+                    Reference::Ask => ticker.best_ask(),
+                };
+
+                base.zip(ticker.last())
+            })?;
+
+        let (increment, limit_rate) = kucoin
+            .spot()
+            .symbols()
+            .get(symbol)
+            .map(|symbol| (symbol.price_increment(), symbol.price_limit_rate()))?;
+
+        offset.apply(base, last, increment, limit_rate)
+    }
+
+    pub fn validate(&self, increment: f64) -> Result<()> {
+        match self {
+            Price::Fixed(price) if *price > 0.0 && is_on_increment(*price, increment) => Ok(()),
+            Price::Fixed(_) => Err(anyhow!(
+                "limit price must be positive with increment of {increment}"
+            )),
+            Price::Relative {
+                offset: Offset::Percent(percent),
+                ..
+            } if *percent <= -100.0 => Err(anyhow!("price offset must be above -100%")),
+            Price::Relative { .. } => Ok(()),
+        }
+    }
+}
+
+impl Offset {
+    /// Offsets the reference price, kept within the symbol's price limit around the last price.
+    fn apply(&self, base: f64, last: f64, increment: f64, limit_rate: f64) -> Option<f64> {
+        let price = match self {
+            Offset::Percent(percent) => base * (1.0 + percent / 100.0),
+            Offset::Ticks(ticks) => base + *ticks as f64 * increment,
+        };
+
+        let price = match limit_rate > 0.0 {
+            true => price.clamp(last * (1.0 - limit_rate), last * (1.0 + limit_rate)),
+            false => price,
+        };
+
+        Some(with_increment(
+            (price / increment).round() * increment,
+            increment,
+        ))
+        .filter(|price| *price > 0.0)
+    }
+}
+
+impl FromStr for Price {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s: String = s.split_whitespace().collect();
+
+        if let Ok(price) = s.parse() {
+            return Ok(Price::Fixed(price));
+        }
+
+        let (reference, offset) = match s.find(['+', '-']) {
+            Some(index) => s.split_at(index),
+            None => (s.as_str(), ""),
+        };
+
+        let offset = match offset.to_lowercase() {
+            offset if offset.is_empty() => Offset::Percent(0.0),
+            offset if offset.ends_with('%') => Offset::Percent(
+                offset
+                    .trim_end_matches('%')
+                    .trim_start_matches('+')
+                    .parse()?,
+            ),
+            offset if offset.ends_with('t') || offset.ends_with("ticks") => Offset::Ticks(
+                offset
+                    .trim_end_matches("ticks")
+                    .trim_end_matches('t')
+                    .trim_start_matches('+')
+                    .parse()?,
+            ),
+            _ => return Err(anyhow!("offset must end with % or t")),
+        };
+
+        Ok(Price::Relative {
+            reference: reference.parse()?,
+            offset,
+        })
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Price::Fixed(price) => write!(f, "{price}"),
+            Price::Relative { reference, offset } => match offset {
+                Offset::Percent(percent) if *percent == 0.0 => write!(f, "{reference}"),
+                Offset::Percent(percent) => write!(f, "{reference} {percent:+}%"),
+                Offset::Ticks(ticks) => write!(f, "{reference} {ticks:+} TICKS"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relative(s: &str) -> (Reference, Offset) {
+        match s.parse().unwrap() {
+            Price::Relative { reference, offset } => (reference, offset),
+            price => panic!("{price} is not relative"),
+        }
+    }
+
+    #[test]
+    fn parses_fixed_prices() {
+        assert!(matches!("0.25".parse(), Ok(Price::Fixed(0.25))));
+        assert!(matches!(" 1 000 ".parse(), Ok(Price::Fixed(1000.0))));
+    }
+
+    #[test]
+    fn parses_relative_prices() {
+        assert!(matches!(
+            relative("last"),
+            (Reference::Last, Offset::Percent(0.0))
+        ));
+        assert!(matches!(
+            relative("BID -0.5%"),
+            (Reference::Bid, Offset::Percent(-0.5))
+        ));
+        assert!(matches!(
+            relative("ask +3t"),
+            (Reference::Ask, Offset::Ticks(3))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_prices() {
+        assert!("mid".parse::<Price>().is_err());
+        assert!("last +1".parse::<Price>().is_err());
+        assert!("last +x%".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn parses_displayed_prices() {
+        for s in ["last", "bid -0.5%", "ask +3t", "last -2 ticks"] {
+            let price: Price = s.parse().unwrap();
+
+            assert_eq!(
+                price.to_string().parse::<Price>().unwrap().to_string(),
+                price.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn applies_offsets_on_the_increment() {
+        assert_eq!(
+            Offset::Percent(1.0).apply(100.0, 100.0, 0.01, 0.0),
+            Some(101.0)
+        );
+        assert_eq!(Offset::Ticks(-3).apply(100.0, 100.0, 0.1, 0.0), Some(99.7));
+        assert_eq!(
+            Offset::Percent(0.0).apply(1.23456, 1.23456, 0.001, 0.0),
+            Some(1.235)
+        );
+    }
+
+    #[test]
+    fn clamps_to_the_price_limit() {
+        assert_eq!(
+            Offset::Percent(50.0).apply(100.0, 100.0, 0.1, 0.1),
+            Some(110.0)
+        );
+        assert_eq!(
+            Offset::Percent(-50.0).apply(100.0, 100.0, 0.1, 0.1),
+            Some(90.0)
+        );
+    }
+
+    #[test]
+    fn rejects_non_positive_prices() {
+        assert_eq!(Offset::Ticks(-10).apply(1.0, 1.0, 0.1, 0.0), None);
+    }
+
+    #[test]
+    fn validates_fixed_prices_on_the_increment() {
+        assert!(Price::Fixed(1.1).validate(0.01).is_ok());
+        assert!(Price::Fixed(0.3).validate(0.1).is_ok());
+        assert!(Price::Fixed(1.105).validate(0.01).is_err());
+        assert!(Price::Fixed(0.0).validate(0.01).is_err());
+    }
+}
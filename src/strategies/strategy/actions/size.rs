@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Size {
+    #[default]
+    Available,
+    Total,
+    Base(f64),
+    Quote(f64),
+    Reserve(f64),
+}
+
+impl Size {
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, Size::Base(_) | Size::Quote(_))
+    }
+
+    pub fn is_percentage(&self) -> bool {
+        matches!(self, Size::Available | Size::Total)
+    }
+
+    pub(super) fn amount(&self, available: f64, total: f64, percentage: f64) -> f64 {
+        match self {
+            Size::Available => available * percentage,
+            Size::Total => (total * percentage).min(available),
+            Size::Base(amount) | Size::Quote(amount) => *amount,
+            Size::Reserve(reserve) => (available - reserve).max(0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_a_percentage_of_available() {
+        assert_eq!(Size::Available.amount(80.0, 100.0, 0.5), 40.0);
+    }
+
+    #[test]
+    fn takes_a_percentage_of_total_up_to_available() {
+        assert_eq!(Size::Total.amount(80.0, 100.0, 0.5), 50.0);
+        assert_eq!(Size::Total.amount(80.0, 100.0, 1.0), 80.0);
+    }
+
+    #[test]
+    fn ignores_balances_for_fixed_sizes() {
+        assert_eq!(Size::Base(3.0).amount(0.0, 0.0, 0.5), 3.0);
+        assert_eq!(Size::Quote(25.0).amount(0.0, 0.0, 0.5), 25.0);
+    }
+
+    #[test]
+    fn keeps_the_reserve() {
+        assert_eq!(Size::Reserve(30.0).amount(80.0, 100.0, 1.0), 50.0);
+        assert_eq!(Size::Reserve(100.0).amount(80.0, 100.0, 1.0), 0.0);
+    }
+}
@@ -1,9 +1,157 @@
-use strum::Display;
+use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use strum::{Display, EnumString, VariantNames};
 
-#[derive(Debug, Clone, Display)]
+use super::{snapshot::Snapshot, Product};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Condition {
-    #[strum(to_string = "&gt {0}")]
     GreaterThan(f64),
-    #[strum(to_string = "&lt {0}")]
     LessThan(f64),
+    /// Compares the strategy product combined with another product.
+    Cross {
+        other: Operand,
+        relation: Relation,
+        threshold: Box<Condition>,
+    },
+}
+
+impl Condition {
+    pub fn with_value(self, value: f64) -> Self {
+        match self {
+            Self::GreaterThan(_) => Self::GreaterThan(value),
+            Self::LessThan(_) => Self::LessThan(value),
+            Self::Cross {
+                other,
+                relation,
+                threshold,
+            } => Self::Cross {
+                other,
+                relation,
+                threshold: Box::new(threshold.with_value(value)),
+            },
+        }
+    }
+
+    pub fn is_met(&self, value: f64, snapshot: &mut Snapshot) -> bool {
+        match self {
+            Self::GreaterThan(bound) => value > *bound,
+            Self::LessThan(bound) => value < *bound,
+            Self::Cross {
+                other,
+                relation,
+                threshold,
+            } => other
+                .value(snapshot)
+                .and_then(|other| relation.apply(value, other))
+                .is_some_and(|combined| threshold.is_met(combined, snapshot)),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GreaterThan(value) => write!(f, "&gt {value}"),
+            Self::LessThan(value) => write!(f, "&lt {value}"),
+            Self::Cross {
+                other,
+                relation,
+                threshold,
+            } => write!(
+                f,
+                "{} VS {other} {threshold}",
+                relation.to_string().to_uppercase()
+            ),
+        }
+    }
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, Display, EnumString, VariantNames, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Relation {
+    /// Product value minus the other value.
+    #[default]
+    Difference,
+    /// Product value divided by the other value.
+    Ratio,
+    /// Difference in percent of the other value.
+    #[strum(to_string = "Spread %")]
+    Spread,
+}
+
+impl Relation {
+    fn apply(&self, value: f64, other: f64) -> Option<f64> {
+        match self {
+            Self::Difference => Some(value - other),
+            Self::Ratio => (other != 0.0).then(|| value / other),
+            Self::Spread => (other != 0.0).then(|| (value - other) / other * 100.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operand {
+    Product(Product),
+    /// Implied value, e.g. ETH-USDT / BTC-USDT for the ETH-BTC price.
+    Ratio(Product, Product),
+}
+
+impl Operand {
+    pub fn products(&self) -> Vec<&Product> {
+        match self {
+            Self::Product(product) => vec![product],
+            Self::Ratio(numerator, denominator) => vec![numerator, denominator],
+        }
+    }
+
+    fn value(&self, snapshot: &mut Snapshot) -> Option<f64> {
+        match self {
+            Self::Product(product) => snapshot.value(product),
+            Self::Ratio(numerator, denominator) => {
+                let numerator = snapshot.value(numerator)?;
+                let denominator = snapshot.value(denominator)?;
+
+                (denominator != 0.0).then(|| numerator / denominator)
+            }
+        }
+    }
+}
+
+/// Spot pairs contain a dash, anything else is a lending currency.
+impl FromStr for Operand {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let product = |s: &str| {
+            let s = s.trim().to_uppercase();
+
+            match s.is_empty() {
+                true => Err(anyhow!("product is missing")),
+                false if s.contains('-') => Ok(Product::SpotTradingPair(s)),
+                false => Ok(Product::LendingCurrency(s)),
+            }
+        };
+
+        match s.split_once('/') {
+            Some((numerator, denominator)) => {
+                Ok(Self::Ratio(product(numerator)?, product(denominator)?))
+            }
+            None => Ok(Self::Product(product(s)?)),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Product(product) => write!(f, "{product}"),
+            Self::Ratio(numerator, denominator) => write!(f, "{numerator} / {denominator}"),
+        }
+    }
 }
@@ -1,22 +1,62 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::kucoin::KuCoin;
 
 mod product;
-pub use product::Product;
+pub use product::{FuturesMetric, Product};
 
 mod condition;
-pub use condition::Condition;
+pub use condition::{Condition, Operand, Relation};
+
+mod snapshot;
+use snapshot::Snapshot;
 
 mod actions;
-pub use actions::{Action, ActionKind, Actions};
+pub use actions::{
+    Action, ActionKind, Actions, CancelScope, Direction, Execution, Offset, Price, Reference, Size,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Strategy {
     name: String,
     product: Option<Product>,
     condition: Option<Condition>,
     actions: Actions,
+    #[serde(default)]
+    require_approval: bool,
+    #[serde(skip)]
+    pending: Option<Pending>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Pending {
+    order_id: String,
+    symbol: String,
+    deadline: u64,
+}
+
+impl Pending {
+    pub fn new(order_id: String, symbol: String, deadline: u64) -> Self {
+        Self {
+            order_id,
+            symbol,
+            deadline,
+        }
+    }
+
+    pub fn order_id(&self) -> &str {
+        &self.order_id
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
 }
 
 impl Strategy {
@@ -26,6 +66,8 @@ impl Strategy {
             product: Default::default(),
             condition: Default::default(),
             actions: Default::default(),
+            require_approval: Default::default(),
+            pending: Default::default(),
         }
     }
 
@@ -37,10 +79,18 @@ impl Strategy {
         self.name = name
     }
 
+    pub fn product(&self) -> Option<&Product> {
+        self.product.as_ref()
+    }
+
     pub fn set_product(&mut self, product: Product) {
         self.product = Some(product)
     }
 
+    pub fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
     pub fn set_condition(&mut self, condition: Condition) {
         self.condition = Some(condition)
     }
@@ -53,35 +103,98 @@ impl Strategy {
         &mut self.actions
     }
 
-    pub fn can_execute(&self, kucoin: &KuCoin) -> bool {
-        match self.product.as_ref().zip(self.condition.as_ref()) {
-            Some((product, condition)) => {
-                let maybe_value = match product {
-                    Product::SpotTradingPair(symbol) => kucoin
-                        .spot()
-                        .tickers()
-                        .get(symbol, false)
-                        .and_then(|ticker| ticker.last()),
-                    Product::LendingCurrency(currency) => kucoin
-                        .lending()
-                        .currencies()
-                        .get(currency, false)
-                        .map(|currency| currency.market_interest_rate()),
-                    Product::BalanceCurrency { r#type, currency } => {
-                        kucoin.accounts().available(r#type, currency)
-                    }
-                };
-
-                match maybe_value {
-                    Some(latest_value) => match condition {
-                        Condition::GreaterThan(value) => latest_value > *value,
-                        Condition::LessThan(value) => latest_value < *value,
-                    },
-                    None => false,
-                }
+    pub fn require_approval(&self) -> bool {
+        self.require_approval
+    }
+
+    pub fn set_require_approval(&mut self, require_approval: bool) {
+        self.require_approval = require_approval
+    }
+
+    pub fn pending(&self) -> Option<&Pending> {
+        self.pending.as_ref()
+    }
+
+    pub fn set_pending(&mut self, pending: Option<Pending>) {
+        self.pending = pending
+    }
+
+    pub fn validate(&self, kucoin: &KuCoin) -> Result<()> {
+        let is_known = self
+            .product
+            .as_ref()
+            .is_none_or(|product| product.is_known(kucoin));
+
+        if !is_known {
+            return Err(anyhow!(
+                "Strategy {}: product {} not found",
+                self.name,
+                self.product.as_ref().unwrap()
+            ));
+        }
+
+        if let Some(Condition::Cross {
+            other, threshold, ..
+        }) = &self.condition
+        {
+            if let Condition::Cross { .. } = threshold.as_ref() {
+                return Err(anyhow!(
+                    "Strategy {}: cross-product conditions can't be nested",
+                    self.name
+                ));
+            }
+
+            if let Some(product) = other
+                .products()
+                .into_iter()
+                .find(|product| !product.is_known(kucoin))
+            {
+                return Err(anyhow!(
+                    "Strategy {}: condition product {product} not found",
+                    self.name
+                ));
+            }
+        }
+
+        for (index, action) in self.actions.iter().enumerate() {
+            action
+                .validate(kucoin)
+                .map_err(|e| anyhow!("Strategy {}: action {}: {e}", self.name, index + 1))?;
+
+            let is_after_spot = index
+                .checked_sub(1)
+                .and_then(|index| self.actions.get(index))
+                .is_some_and(|previous| previous.is_tracked());
+
+            if action.wait().is_some() && !is_after_spot {
+                return Err(anyhow!(
+                    "Strategy {}: action {}: can only wait for a previous spot order",
+                    self.name,
+                    index + 1
+                ));
             }
-            None => false,
         }
+
+        Ok(())
+    }
+
+    /// Latest value of the product the condition is checked against.
+    pub fn value(&self, kucoin: &KuCoin) -> Option<f64> {
+        let product = self.product.as_ref()?;
+
+        Snapshot::new(kucoin, &self.name).value(product)
+    }
+
+    pub fn can_execute(&self, kucoin: &KuCoin) -> bool {
+        let Some((product, condition)) = self.product.as_ref().zip(self.condition.as_ref()) else {
+            return false;
+        };
+
+        let mut snapshot = Snapshot::new(kucoin, &self.name);
+
+        snapshot
+            .value(product)
+            .is_some_and(|value| condition.is_met(value, &mut snapshot))
     }
 }
 
@@ -96,6 +209,17 @@ impl fmt::Display for Strategy {
             actions.push_str(&format!("{}: {action}\n", index + 1));
         }
 
+        if self.require_approval {
+            actions.push_str("\n<b>Requires approval:</b> ✅\n");
+        }
+
+        if let Some(pending) = &self.pending {
+            actions.push_str(&format!(
+                "\n<b>Waiting for fill:</b> {} {}\n",
+                pending.symbol, pending.order_id
+            ));
+        }
+
         write!(
             f,
             "<b>Name:</b> {}\n<b>Product:</b> {}\n<b>Condition:</b> {}\n<b>Actions:</b> {}",
@@ -109,6 +233,13 @@ impl fmt::Display for Strategy {
                         Product::SpotTradingPair(_) => "PRICE",
                         Product::LendingCurrency(_) => "APY",
                         Product::BalanceCurrency { .. } => "BALANCE",
+                        Product::MarginDebtRatio => "DEBT RATIO %",
+                        Product::FuturesContract { metric, .. } => match metric {
+                            FuturesMetric::MarkPrice => "MARK PRICE",
+                            FuturesMetric::FundingRate => "FUNDING RATE %",
+                            FuturesMetric::UnrealisedPnl => "UNREALISED PNL",
+                        },
+                        Product::DepositReceived(_) => "RECEIVED",
                     };
 
                     format!("{reference} {condition}")
@@ -1,8 +1,11 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use strum::{Display, EnumString, VariantNames};
 
-use crate::kucoin::account::AccountType;
+use crate::kucoin::{account::AccountType, KuCoin};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Product {
     SpotTradingPair(String),
     LendingCurrency(String),
@@ -10,6 +13,42 @@ pub enum Product {
         r#type: AccountType,
         currency: String,
     },
+    MarginDebtRatio,
+    FuturesContract {
+        symbol: String,
+        metric: FuturesMetric,
+    },
+    DepositReceived(String),
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, Display, EnumString, VariantNames, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FuturesMetric {
+    #[default]
+    #[strum(to_string = "Mark Price")]
+    MarkPrice,
+    #[strum(to_string = "Funding Rate")]
+    FundingRate,
+    #[strum(to_string = "Unrealised PnL")]
+    UnrealisedPnl,
+}
+
+impl Product {
+    pub fn is_known(&self, kucoin: &KuCoin) -> bool {
+        match self {
+            Self::SpotTradingPair(symbol) => kucoin.spot().tickers().get(symbol, false).is_some(),
+            Self::LendingCurrency(currency) => {
+                kucoin.lending().currencies().get(currency, false).is_some()
+            }
+            Self::FuturesContract { symbol, .. } => {
+                kucoin.futures().contracts().contains_key(symbol)
+            }
+            Self::DepositReceived(currency) => kucoin.spot().currencies().contains_key(currency),
+            _ => true,
+        }
+    }
 }
 
 impl fmt::Display for Product {
@@ -24,6 +63,9 @@ impl fmt::Display for Product {
                     r#type.to_string().to_uppercase()
                 )
             }
+            Self::MarginDebtRatio => write!(f, "CROSS MARGIN"),
+            Self::FuturesContract { symbol, .. } => write!(f, "{symbol} (FUTURES)"),
+            Self::DepositReceived(currency) => write!(f, "{currency} (DEPOSITS)"),
         }
     }
 }
@@ -0,0 +1,64 @@
+use std::sync::MutexGuard;
+
+use crate::kucoin::{lending, trading::spot::Tickers, KuCoin};
+
+use super::{FuturesMetric, Product};
+
+/// Ticker and lending caches locked once, so every product compared by a condition is read
+/// from the same poll.
+pub struct Snapshot<'a> {
+    kucoin: &'a KuCoin,
+    strategy: &'a str,
+    tickers: MutexGuard<'a, Tickers>,
+    lending: MutexGuard<'a, lending::Currencies>,
+}
+
+impl<'a> Snapshot<'a> {
+    pub fn new(kucoin: &'a KuCoin, strategy: &'a str) -> Self {
+        Self {
+            kucoin,
+            strategy,
+            tickers: kucoin.spot().tickers(),
+            lending: kucoin.lending().currencies(),
+        }
+    }
+
+    pub fn value(&mut self, product: &Product) -> Option<f64> {
+        let kucoin = self.kucoin;
+
+        match product {
+            Product::SpotTradingPair(symbol) => self
+                .tickers
+                .get(symbol, false)
+                .and_then(|ticker| ticker.last()),
+            Product::LendingCurrency(currency) => self
+                .lending
+                .get(currency, false)
+                .map(|currency| currency.market_interest_rate()),
+            Product::BalanceCurrency { r#type, currency } => {
+                kucoin.accounts().available(r#type, currency)
+            }
+            Product::MarginDebtRatio => kucoin.margin().account().debt_ratio(),
+            Product::FuturesContract { symbol, metric } => match metric {
+                FuturesMetric::MarkPrice => kucoin
+                    .futures()
+                    .contracts()
+                    .get(symbol)
+                    .and_then(|contract| contract.mark_price()),
+                FuturesMetric::FundingRate => kucoin
+                    .futures()
+                    .contracts()
+                    .get(symbol)
+                    .and_then(|contract| contract.funding_rate()),
+                FuturesMetric::UnrealisedPnl => kucoin
+                    .futures()
+                    .positions()
+                    .get(symbol)
+                    .map(|position| position.unrealised_pnl()),
+            },
+            Product::DepositReceived(currency) => {
+                Some(kucoin.deposits().received(self.strategy, currency))
+            }
+        }
+    }
+}
@@ -0,0 +1,544 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    kucoin::{
+        futures::AddFutures,
+        lending::{Lend, Redeem},
+        margin::{AddMargin, Borrow, Repay},
+        trading::spot::{oco::AddOco, order::Add, stop::AddStop},
+        withdrawal::Withdraw,
+        KuCoin, Request,
+    },
+    strategies::strategy::{Action, ActionKind},
+};
+
+const PATH: &str = match option_env!("SUBMISSIONS_PATH") {
+    Some(path) => path,
+    None => "submissions.json",
+};
+
+/// Requests that can't be looked up are retried only after pollers refresh the balances.
+const SETTLE: u64 = 30 * 1000;
+
+/// Allowance for the difference between the local clock and KuCoin's when searching history.
+const SKEW: u64 = 60 * 1000;
+
+/// Request sent without knowing whether KuCoin accepted it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    strategy: String,
+    index: usize,
+    action: Action,
+    client_oid: String,
+    exposure: Option<(String, f64)>,
+    payload: String,
+    /// Currency and size sent, for requests found by searching history.
+    amount: Option<(String, f64)>,
+    sent: u64,
+}
+
+pub enum Reconciliation {
+    Placed(String),
+    Retry,
+    Abandon,
+    Pending,
+}
+
+impl Submission {
+    pub fn new(
+        strategy: String,
+        index: usize,
+        action: Action,
+        client_oid: String,
+        exposure: Option<(String, f64)>,
+        request: &Request,
+        sent: u64,
+    ) -> Self {
+        let amount = request.body::<Value>().ok().and_then(|body| {
+            let currency = body.get("currency")?.as_str()?.to_string();
+            let size = number(body.get("size").or_else(|| body.get("amount"))?)?;
+
+            Some((currency, size))
+        });
+
+        Self {
+            strategy,
+            index,
+            action,
+            client_oid,
+            exposure,
+            payload: request.to_string(),
+            amount,
+            sent,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn exposure(&self) -> Option<(String, f64)> {
+        self.exposure.clone()
+    }
+
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    /// Looks the request up by its client order ID, or in the history of its kind,
+    /// before it may be sent again.
+    pub async fn reconcile(&self, kucoin: &KuCoin) -> Reconciliation {
+        let settled = kucoin.now() >= self.sent + SETTLE;
+
+        if let Some(request) = lookup(&self.action, &self.client_oid) {
+            return match kucoin.send::<Value>(request).await {
+                Ok(value) => {
+                    let order = match value {
+                        Value::Array(orders) => orders.into_iter().next(),
+                        value => Some(value),
+                    };
+
+                    match order
+                        .as_ref()
+                        .and_then(|order| order.get("id").or_else(|| order.get("orderId")))
+                        .and_then(Value::as_str)
+                    {
+                        Some(order_id) => Reconciliation::Placed(order_id.to_string()),
+                        None => Reconciliation::Retry,
+                    }
+                }
+                Err(e) if e.downcast_ref::<reqwest::Error>().is_some() => Reconciliation::Pending,
+                Err(_) => Reconciliation::Retry,
+            };
+        }
+
+        let since = self.sent.saturating_sub(SKEW);
+
+        let history = self.amount.as_ref().and_then(|(currency, size)| {
+            history(&self.action, currency, since).map(|history| (history, *size))
+        });
+
+        if let Some(((requests, fields), size)) = history {
+            for request in requests {
+                match kucoin.send::<Value>(request).await {
+                    Ok(value) => {
+                        if let Some(id) = fields.find(&value, size, since) {
+                            return Reconciliation::Placed(id);
+                        }
+                    }
+                    Err(e) if e.downcast_ref::<reqwest::Error>().is_some() => {
+                        return Reconciliation::Pending
+                    }
+                    // Without the history it's unknown whether the request went through.
+                    Err(_) => {
+                        return match settled {
+                            true => Reconciliation::Abandon,
+                            false => Reconciliation::Pending,
+                        }
+                    }
+                }
+            }
+
+            // History may lag behind the request, so it's only trusted once settled.
+            return match settled {
+                true => Reconciliation::Retry,
+                false => Reconciliation::Pending,
+            };
+        }
+
+        match (settled, self.action.size().is_fixed()) {
+            (false, _) => Reconciliation::Pending,
+            (true, true) => Reconciliation::Abandon,
+            (true, false) => Reconciliation::Retry,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Submissions {
+    state: Arc<Mutex<State>>,
+    persistent: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct State {
+    /// Set once when the file is created, so IDs aren't reused if it's ever lost.
+    salt: String,
+    sequences: HashMap<String, HashMap<usize, u64>>,
+    pending: HashMap<String, Submission>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            salt: Uuid::new_v4().simple().to_string(),
+            sequences: Default::default(),
+            pending: Default::default(),
+        }
+    }
+}
+
+impl State {
+    fn sequence(&self, strategy: &str, index: usize) -> u64 {
+        self.sequences
+            .get(strategy)
+            .and_then(|sequences| sequences.get(&index))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Submissions {
+    fn default() -> Self {
+        let state = fs::read_to_string(PATH)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            persistent: true,
+        }
+    }
+}
+
+impl Submissions {
+    /// Submissions that are never written to disk, for backtests.
+    pub fn in_memory() -> Self {
+        Self {
+            state: Default::default(),
+            persistent: false,
+        }
+    }
+
+    /// Client order ID of the next submission of the action, reused until it's resolved.
+    pub fn client_oid(&self, strategy: &str, index: usize) -> String {
+        let state = self.lock();
+
+        client_oid(
+            &state.salt,
+            strategy,
+            index,
+            state.sequence(strategy, index),
+        )
+    }
+
+    /// Client order ID of a transfer made for the action's next submission, such as its funding.
+    pub fn transfer_client_oid(&self, strategy: &str, index: usize, purpose: &str) -> String {
+        let state = self.lock();
+
+        client_oid(
+            &state.salt,
+            &format!("{strategy}:{purpose}"),
+            index,
+            state.sequence(strategy, index),
+        )
+    }
+
+    pub fn accepts_client_oid(action: &Action) -> bool {
+        matches!(
+            action.kind(),
+            ActionKind::SpotOrder { .. }
+                | ActionKind::TrailingStop { .. }
+                | ActionKind::StopOrder { .. }
+                | ActionKind::Oco { .. }
+                | ActionKind::MarginOrder { .. }
+                | ActionKind::FuturesOrder { .. }
+                | ActionKind::ClosePosition
+                | ActionKind::Transfer { .. }
+                | ActionKind::SubTransfer { .. }
+        )
+    }
+
+    pub fn hold(&self, submission: Submission) {
+        let mut state = self.lock();
+
+        state
+            .pending
+            .insert(submission.strategy.clone(), submission);
+        self.save(&state);
+    }
+
+    pub fn pending(&self, strategy: &str) -> Option<Submission> {
+        self.lock().pending.get(strategy).cloned()
+    }
+
+    /// Lets the action be sent again with the same client order ID.
+    pub fn release(&self, strategy: &str) {
+        let mut state = self.lock();
+
+        if state.pending.remove(strategy).is_some() {
+            self.save(&state);
+        }
+    }
+
+    /// Moves the action on to a new client order ID.
+    pub fn complete(&self, strategy: &str, index: usize) {
+        let mut state = self.lock();
+
+        state.pending.remove(strategy);
+        *state
+            .sequences
+            .entry(strategy.to_string())
+            .or_default()
+            .entry(index)
+            .or_default() += 1;
+        self.save(&state);
+    }
+
+    fn save(&self, state: &State) {
+        if !self.persistent {
+            return;
+        }
+
+        let result = serde_json::to_string(state)
+            .map_err(Into::into)
+            .and_then(|content| fs::write(PATH, content));
+
+        if let Err(e) = result {
+            error!("{PATH}: {e}");
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+fn client_oid(salt: &str, strategy: &str, index: usize, sequence: u64) -> String {
+    Sha256::digest(format!("{salt}:{strategy}:{index}:{sequence}"))
+        .iter()
+        .take(16)
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn lookup(action: &Action, client_oid: &str) -> Option<Request> {
+    let symbol = action.symbol();
+
+    match action.kind() {
+        ActionKind::SpotOrder { .. } | ActionKind::TrailingStop { .. } => {
+            Some(Add::by_client_oid(client_oid, symbol))
+        }
+        ActionKind::MarginOrder { .. } => Some(AddMargin::by_client_oid(client_oid, symbol)),
+        ActionKind::StopOrder { .. } => Some(AddStop::by_client_oid(client_oid, symbol)),
+        ActionKind::Oco { .. } => Some(AddOco::by_client_oid(client_oid)),
+        ActionKind::FuturesOrder { .. } | ActionKind::ClosePosition => {
+            Some(AddFutures::by_client_oid(client_oid))
+        }
+        _ => None,
+    }
+}
+
+/// Fields of the history records of requests that take no client order ID.
+struct Fields {
+    id: &'static str,
+    size: &'static str,
+    time: &'static str,
+}
+
+impl Fields {
+    /// ID of the first record of the size made since the given time.
+    fn find(&self, value: &Value, size: f64, since: u64) -> Option<String> {
+        let records = match value {
+            Value::Array(records) => records,
+            value => value.get("items")?.as_array()?,
+        };
+
+        records
+            .iter()
+            .filter(|record| {
+                record
+                    .get(self.time)
+                    .and_then(number)
+                    .is_some_and(|time| time >= since as f64)
+            })
+            .filter(|record| {
+                record
+                    .get(self.size)
+                    .and_then(number)
+                    .is_some_and(|recorded| (recorded - size).abs() <= size.abs() * 1e-9)
+            })
+            .find_map(|record| match record.get(self.id)? {
+                Value::String(id) => Some(id.clone()),
+                Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            })
+    }
+}
+
+fn history(action: &Action, currency: &str, since: u64) -> Option<(Vec<Request>, Fields)> {
+    let statuses = ["PENDING", "DONE"];
+
+    match action.kind() {
+        ActionKind::Borrow => Some((
+            vec![Borrow::history(currency, since)],
+            Fields {
+                id: "orderNo",
+                size: "size",
+                time: "createdTime",
+            },
+        )),
+        ActionKind::Repay => Some((
+            vec![Repay::history(currency, since)],
+            Fields {
+                id: "orderNo",
+                size: "size",
+                time: "createdTime",
+            },
+        )),
+        ActionKind::Lend { .. } => Some((
+            statuses
+                .iter()
+                .map(|status| Lend::history(currency, status))
+                .collect(),
+            Fields {
+                id: "purchaseOrderNo",
+                size: "purchaseSize",
+                time: "applyTime",
+            },
+        )),
+        ActionKind::Redeem => Some((
+            statuses
+                .iter()
+                .map(|status| Redeem::history(currency, status))
+                .collect(),
+            Fields {
+                id: "redeemOrderNo",
+                size: "redeemSize",
+                time: "applyTime",
+            },
+        )),
+        ActionKind::Withdraw { .. } => Some((
+            vec![Withdraw::history(currency, since)],
+            Fields {
+                id: "id",
+                size: "amount",
+                time: "createdAt",
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Number sent either as a JSON number or as a string, as KuCoin does for sizes.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Whether the request may have reached KuCoin even though no response was received.
+pub fn is_unknown_outcome(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| !e.is_connect() && !e.is_builder())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn client_oid_is_derived_from_its_inputs() {
+        let id = client_oid("salt", "dca", 0, 0);
+
+        assert_eq!(id, client_oid("salt", "dca", 0, 0));
+        assert_eq!(id.len(), 32);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert_ne!(id, client_oid("other", "dca", 0, 0));
+        assert_ne!(id, client_oid("salt", "grid", 0, 0));
+        assert_ne!(id, client_oid("salt", "dca", 1, 0));
+        assert_ne!(id, client_oid("salt", "dca", 0, 1));
+    }
+
+    #[test]
+    fn client_oid_advances_only_once_completed() {
+        let submissions = Submissions::in_memory();
+        let first = submissions.client_oid("dca", 0);
+
+        submissions.release("dca");
+        assert_eq!(submissions.client_oid("dca", 0), first);
+
+        submissions.complete("dca", 0);
+        let second = submissions.client_oid("dca", 0);
+        assert_ne!(second, first);
+        assert_eq!(
+            submissions.client_oid("dca", 1),
+            client_oid_at(&submissions, 1, 0)
+        );
+
+        submissions.complete("dca", 0);
+        assert_ne!(submissions.client_oid("dca", 0), second);
+    }
+
+    #[test]
+    fn transfer_client_oid_differs_from_the_order() {
+        let submissions = Submissions::in_memory();
+        let funding = submissions.transfer_client_oid("dca", 0, "funding");
+
+        assert_ne!(funding, submissions.client_oid("dca", 0));
+        assert_ne!(funding, submissions.transfer_client_oid("dca", 0, "refund"));
+        assert_eq!(
+            funding,
+            submissions.transfer_client_oid("dca", 0, "funding")
+        );
+    }
+
+    #[test]
+    fn client_oid_survives_reload() {
+        let submissions = Submissions::in_memory();
+        submissions.complete("dca", 2);
+        let id = submissions.client_oid("dca", 2);
+
+        let content = serde_json::to_string(&*submissions.lock()).unwrap();
+        let reloaded = Submissions {
+            state: Arc::new(Mutex::new(serde_json::from_str(&content).unwrap())),
+            persistent: false,
+        };
+
+        assert_eq!(reloaded.client_oid("dca", 2), id);
+    }
+
+    #[test]
+    fn find_matches_size_and_time() {
+        let fields = Fields {
+            id: "orderNo",
+            size: "size",
+            time: "createdTime",
+        };
+        let history = json!({
+            "items": [
+                { "orderNo": "old", "size": "10", "createdTime": 500 },
+                { "orderNo": "other", "size": "12", "createdTime": 1500 },
+                { "orderNo": "new", "size": "10", "createdTime": 1500 },
+            ]
+        });
+
+        assert_eq!(fields.find(&history, 10.0, 1000), Some("new".to_string()));
+        assert_eq!(fields.find(&history, 11.0, 1000), None);
+        assert_eq!(fields.find(&history, 10.0, 2000), None);
+        assert_eq!(fields.find(&json!([]), 10.0, 0), None);
+    }
+
+    fn client_oid_at(submissions: &Submissions, index: usize, sequence: u64) -> String {
+        client_oid(&submissions.lock().salt, "dca", index, sequence)
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client, Url};
+use sha2::Sha256;
+use std::{
+    net::IpAddr,
+    sync::LazyLock,
+    time::{Duration, UNIX_EPOCH},
+};
+use tracing::warn;
+
+const URL: Option<&str> = option_env!("WEBHOOK_URL");
+const SECRET: Option<&str> = option_env!("WEBHOOK_SECRET");
+
+const ATTEMPTS: u32 = 3;
+
+/// Kept apart from the exchange client so API key headers never leave for the webhook.
+static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap()
+});
+
+/// Configured endpoint, which must point to this machine or the local network.
+pub fn endpoint() -> Result<Url> {
+    let url: Url = URL
+        .ok_or_else(|| anyhow!("webhook endpoint is not configured"))?
+        .parse()?;
+
+    let is_local = match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| match ip {
+                IpAddr::V4(ip) => ip.is_loopback() || ip.is_private(),
+                IpAddr::V6(ip) => ip.is_loopback() || ip.is_unique_local(),
+            }),
+        None => false,
+    };
+
+    match is_local {
+        true => Ok(url),
+        false => Err(anyhow!("webhook endpoint must be a local address")),
+    }
+}
+
+pub async fn post(body: &str) -> Result<()> {
+    let url = endpoint()?;
+    let secret = SECRET.ok_or_else(|| anyhow!("webhook secret is not configured"))?;
+
+    let mut last_error = anyhow!("webhook was not sent");
+
+    for attempt in 1..=ATTEMPTS {
+        let timestamp = UNIX_EPOCH.elapsed().unwrap().as_millis().to_string();
+
+        let mut signature: Hmac<Sha256> = Hmac::new_from_slice(secret.as_bytes()).unwrap();
+        signature.update((timestamp.clone() + body).as_bytes());
+
+        let response = CLIENT
+            .post(url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Timestamp", &timestamp)
+            .header(
+                "X-Webhook-Signature",
+                STANDARD.encode(signature.finalize().into_bytes()),
+            )
+            .body(body.to_string())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match response {
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                warn!("webhook attempt {attempt}/{ATTEMPTS} failed: {e}");
+
+                last_error = e.into();
+            }
+        }
+
+        if attempt < ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(2_u64.pow(attempt - 1))).await;
+        }
+    }
+
+    Err(last_error)
+}
@@ -0,0 +1,140 @@
+use anyhow::{anyhow, bail};
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters as _, EditMessageTextSetters as _},
+    prelude::Requester as _,
+    types::{CallbackQuery, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::{response::Order, KuCoin, Request},
+    strategies::{confirmations::Confirmation, journal::Entry, Strategies},
+};
+
+/// Approved amounts may grow by this fraction before the request has to be approved again.
+const TOLERANCE: f64 = 0.01;
+
+pub async fn resolve(
+    bot: Bot,
+    query: CallbackQuery,
+    kucoin: KuCoin,
+    strategies: Strategies,
+    id: &str,
+    confirmed: bool,
+) -> Result<(), RequestError> {
+    let Some(confirmation) = strategies.confirmations().take(id) else {
+        bot.answer_callback_query(query.id)
+            .text("Request has already been handled")
+            .await?;
+
+        return Ok(());
+    };
+
+    let mut text = confirmation.text().to_string();
+
+    let result = match confirmed {
+        true => match execute(&kucoin, &strategies, &confirmation).await {
+            Ok(order) => {
+                text.push_str(&format!("✅\n{order}"));
+
+                Ok(order.order_id().to_string())
+            }
+            Err(e) => {
+                text.push_str(&format!("❌\n{e}"));
+
+                Err(e.to_string())
+            }
+        },
+        false => {
+            strategies.confirmations().suppress(confirmation.strategy());
+            text.push_str("🚫 rejected");
+
+            Err("rejected".to_string())
+        }
+    };
+
+    let symbol = confirmation.action().symbol().to_string();
+
+    let balances = {
+        let balances = kucoin.accounts().balances();
+
+        symbol
+            .split('-')
+            .map(|currency| {
+                (
+                    currency.to_string(),
+                    balances.get(currency).copied().unwrap_or_default(),
+                )
+            })
+            .collect()
+    };
+
+    strategies.journal().record(
+        Entry::new(
+            &kucoin,
+            confirmation.strategy().to_string(),
+            confirmation.action().label(),
+            confirmation.amount(),
+            confirmation.payload().to_string(),
+            result,
+            balances,
+        )
+        .with_fee(confirmation.fee()),
+    );
+
+    if let Some(msg) = &query.message {
+        bot.edit_message_text(msg.chat().id, msg.id(), text)
+            .parse_mode(Html)
+            .await?;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+/// Rebuilds the request from current balances and prices before sending it.
+async fn execute(
+    kucoin: &KuCoin,
+    strategies: &Strategies,
+    confirmation: &Confirmation,
+) -> anyhow::Result<Order> {
+    if kucoin.now() >= confirmation.deadline() {
+        bail!("approval timed out");
+    }
+
+    if strategies.get(confirmation.strategy()).is_none() {
+        bail!("strategy no longer exists");
+    }
+
+    let action = confirmation.action();
+    action.validate(kucoin)?;
+
+    let request = Request::try_from((action, kucoin))
+        .map_err(|_| anyhow!("action can no longer be executed"))?;
+
+    if let Some((approved, amount)) = confirmation.amount().zip(action.amount(kucoin)) {
+        if amount > approved * (1.0 + TOLERANCE) {
+            bail!("amount changed from {approved} to {amount}, approval required again");
+        }
+    }
+
+    action
+        .check_trade(kucoin)
+        .map_err(|e| anyhow!("rejected by trading rules\n{e}"))?;
+
+    let exposure = strategies
+        .risk()
+        .check(action, kucoin)
+        .map_err(|e| anyhow!("blocked by risk limits\n{e}"))?;
+    let (name, index) = (confirmation.strategy(), confirmation.index());
+    let request = strategies.prepare(name, index, action, request);
+
+    let order = strategies
+        .submit(kucoin, name, index, action, request, exposure)
+        .await?;
+
+    strategies.executed(kucoin, name, index, action, Some(order.order_id()));
+
+    Ok(order)
+}
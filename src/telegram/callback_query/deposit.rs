@@ -0,0 +1,117 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::EditMessageTextSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{CallbackQuery, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::{
+        deposit::{Deposit, DepositAddress},
+        response::Paginated,
+        KuCoin,
+    },
+    telegram::{
+        callback_query::{cancel, wrong_button},
+        constants::CANCEL,
+        State,
+    },
+};
+
+pub async fn receive_currency(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+) -> Result<(), RequestError> {
+    match query.data.as_deref() {
+        Some(CANCEL) => cancel(bot, query, dialogue).await,
+        _ => wrong_button(bot, query).await,
+    }
+}
+
+pub async fn receive_chain(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    currency: String,
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if data == CANCEL {
+            return cancel(bot, query, dialogue).await;
+        }
+
+        let Some(confirmations) = kucoin
+            .spot()
+            .currencies()
+            .get(&currency)
+            .and_then(|currency| currency.chain(data))
+            .filter(|chain| chain.is_deposit_enabled())
+            .map(|chain| {
+                format!(
+                    "<b>Confirmations:</b> credited after {}, withdrawable after {}\n\n",
+                    chain.pre_confirms(),
+                    chain.confirms()
+                )
+            })
+        else {
+            return wrong_button(bot, query).await;
+        };
+
+        let client = kucoin.client();
+
+        let address = match DepositAddress::list(&currency, data)
+            .send::<Vec<DepositAddress>>(client)
+            .await
+            .map(|addresses| addresses.into_iter().next())
+        {
+            Ok(Some(address)) => Ok(address),
+            Ok(None) => {
+                DepositAddress::create(&currency, data)
+                    .send::<DepositAddress>(client)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let address = match address {
+            Ok(address) => address.to_string(),
+            Err(e) => format!("❌\n{e}\n\n"),
+        };
+
+        let history = match Deposit::history(&currency)
+            .send::<Paginated<Deposit>>(client)
+            .await
+        {
+            Ok(paginated) => {
+                let history: String = paginated
+                    .into_iter()
+                    .map(|deposit| deposit.to_string())
+                    .collect();
+
+                match history.is_empty() {
+                    true => "🚫".to_string(),
+                    false => history,
+                }
+            }
+            Err(e) => format!("❌\n{e}"),
+        };
+
+        bot.edit_message_text(
+            msg.chat().id,
+            msg.id(),
+            format!(
+                "<b>{currency} deposit via {data}</b>\n\n{address}{confirmations}<b>Recent deposits</b>\n\n{history}"
+            ),
+        )
+        .parse_mode(Html)
+        .await?;
+
+        let _ = dialogue.reset().await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
@@ -8,19 +8,29 @@ use teloxide::{
 
 use crate::{
     kucoin::KuCoin,
-    strategies::{strategy::Strategy, Strategies},
+    strategies::{
+        confirmations::{CONFIRM, REJECT},
+        strategy::Strategy,
+        Strategies,
+    },
     telegram::{
         constants::{
-            BACK_TO_STRATEGIES, CANCEL, CREATE_STRATEGY, DELETE_STRATEGY, EDIT_ACTIONS,
-            EDIT_CONDITION, EDIT_NAME, EDIT_PRODUCT,
+            BACK_TO_STRATEGIES, CANCEL, CLEAR_KILL_SWITCH, CREATE_STRATEGY, DELETE_STRATEGY,
+            EDIT_ACTIONS, EDIT_CONDITION, EDIT_NAME, EDIT_PRODUCT, ENGAGE_KILL_SWITCH,
+            EXPORT_STRATEGIES, EXPORT_STRATEGY, HISTORY, HISTORY_PAGE, IMPORT_STRATEGIES,
+            TOGGLE_APPROVAL,
         },
         State,
     },
 };
 
 pub mod announcements;
+pub mod confirmations;
+pub mod deposit;
 pub mod lending;
+pub mod orders;
 pub mod pair;
+pub mod risk;
 pub mod strategy;
 use strategy::{action, back_to_strategies, condition, product};
 
@@ -35,7 +45,12 @@ pub async fn handler(
         match dialogue.get().await.unwrap().unwrap() {
             State::Default => match data.as_str() {
                 CREATE_STRATEGY => return strategy::create(bot, query, dialogue).await,
-                EDIT_NAME | EDIT_PRODUCT | EDIT_CONDITION | EDIT_ACTIONS | DELETE_STRATEGY => {
+                IMPORT_STRATEGIES => return strategy::import(bot, query, dialogue).await,
+                EXPORT_STRATEGIES => {
+                    return strategy::export(bot, query, dialogue, strategies.all()).await
+                }
+                EDIT_NAME | EDIT_PRODUCT | EDIT_CONDITION | EDIT_ACTIONS | HISTORY
+                | EXPORT_STRATEGY | TOGGLE_APPROVAL | DELETE_STRATEGY => {
                     match parse_strategy(msg, &strategies) {
                         Some(strategy) => match data.as_str() {
                             EDIT_NAME => {
@@ -50,15 +65,53 @@ pub async fn handler(
                             EDIT_ACTIONS => {
                                 return action::edit(bot, query, dialogue, strategy).await
                             }
+                            EXPORT_STRATEGY => {
+                                return strategy::export(bot, query, dialogue, vec![strategy]).await
+                            }
+                            HISTORY => {
+                                return strategy::history(bot, query, strategies, strategy, None)
+                                    .await
+                            }
+                            TOGGLE_APPROVAL => {
+                                return strategy::toggle_approval(bot, query, strategies, strategy)
+                                    .await
+                            }
                             _ => return strategy::delete(bot, query, dialogue, strategy).await,
                         },
                         None => return wrong_button(bot, query).await,
                     }
                 }
                 BACK_TO_STRATEGIES => return back_to_strategies(bot, query, strategies).await,
+                ENGAGE_KILL_SWITCH => {
+                    return risk::kill_switch(bot, query, kucoin, strategies, true).await
+                }
+                CLEAR_KILL_SWITCH => {
+                    return risk::kill_switch(bot, query, kucoin, strategies, false).await
+                }
                 _ => {
-                    if let Some(strategy) = strategies.get(data) {
+                    if let Some(id) = data.strip_prefix(CONFIRM).map(str::to_string) {
+                        return confirmations::resolve(bot, query, kucoin, strategies, &id, true)
+                            .await;
+                    } else if let Some(id) = data.strip_prefix(REJECT).map(str::to_string) {
+                        return confirmations::resolve(bot, query, kucoin, strategies, &id, false)
+                            .await;
+                    } else if let Some(page) = data
+                        .strip_prefix(HISTORY_PAGE)
+                        .and_then(|page| page.parse().ok())
+                    {
+                        return match parse_strategy(msg, &strategies) {
+                            Some(strategy) => {
+                                strategy::history(bot, query, strategies, strategy, Some(page))
+                                    .await
+                            }
+                            None => wrong_button(bot, query).await,
+                        };
+                    } else if let Some(strategy) = strategies.get(data) {
                         return strategy::edit_by_name(bot, query, strategy).await;
+                    } else if let Some(order) = kucoin.spot().active().get(data) {
+                        return orders::cancel(bot, query, kucoin, order).await;
+                    } else if let Some(order) = kucoin.spot().stops().get(data) {
+                        return orders::cancel_stop(bot, query, kucoin, order).await;
                     } else if let Ok(r#type) = data.parse() {
                         return announcements::toggle(bot, query, kucoin, r#type).await;
                     } else {
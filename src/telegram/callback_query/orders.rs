@@ -0,0 +1,79 @@
+use serde::de::IgnoredAny;
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters as _, EditMessageTextSetters as _},
+    prelude::Requester as _,
+    types::{CallbackQuery, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::{
+        response::Order,
+        trading::spot::{
+            order::{self, Details},
+            stop::{self, StopOrder},
+        },
+        KuCoin,
+    },
+    telegram::keyboard,
+};
+
+pub async fn cancel(
+    bot: Bot,
+    query: CallbackQuery,
+    kucoin: KuCoin,
+    details: Details,
+) -> Result<(), RequestError> {
+    let text = match kucoin
+        .send::<Order>(order::cancel(details.id(), details.symbol()))
+        .await
+    {
+        Ok(_) => {
+            kucoin.spot().active().remove(details.id());
+            refresh(&bot, &query, &kucoin).await?;
+
+            "Order canceled".to_string()
+        }
+        Err(e) => e.to_string(),
+    };
+
+    bot.answer_callback_query(query.id).text(text).await?;
+
+    Ok(())
+}
+
+pub async fn cancel_stop(
+    bot: Bot,
+    query: CallbackQuery,
+    kucoin: KuCoin,
+    order: StopOrder,
+) -> Result<(), RequestError> {
+    let text = match kucoin.send::<IgnoredAny>(stop::cancel(order.id())).await {
+        Ok(_) => {
+            kucoin.spot().stops().remove(order.id());
+            refresh(&bot, &query, &kucoin).await?;
+
+            "Stop order canceled".to_string()
+        }
+        Err(e) => e.to_string(),
+    };
+
+    bot.answer_callback_query(query.id).text(text).await?;
+
+    Ok(())
+}
+
+async fn refresh(bot: &Bot, query: &CallbackQuery, kucoin: &KuCoin) -> Result<(), RequestError> {
+    if let Some(msg) = query.message.as_ref() {
+        let spot = kucoin.spot();
+
+        let orders = kucoin.mode_tag().to_string() + &spot.orders();
+
+        bot.edit_message_text(msg.chat().id, msg.id(), orders)
+            .reply_markup(keyboard::open_orders(spot))
+            .parse_mode(Html)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use teloxide::{
+    payloads::{AnswerCallbackQuerySetters as _, EditMessageTextSetters as _},
+    prelude::Requester as _,
+    types::{CallbackQuery, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{kucoin::KuCoin, strategies::Strategies, telegram::keyboard};
+
+pub async fn kill_switch(
+    bot: Bot,
+    query: CallbackQuery,
+    kucoin: KuCoin,
+    strategies: Strategies,
+    engage: bool,
+) -> Result<(), RequestError> {
+    let risk = strategies.risk();
+    risk.set_killed(engage);
+
+    if let Some(msg) = query.message.as_ref() {
+        let text = kucoin.mode_tag().to_string() + &risk.status(kucoin.now());
+
+        bot.edit_message_text(msg.chat().id, msg.id(), text)
+            .reply_markup(keyboard::risk(risk))
+            .parse_mode(Html)
+            .await?;
+    }
+
+    let text = match engage {
+        true => "Kill switch engaged",
+        false => "Kill switch cleared",
+    };
+
+    bot.answer_callback_query(query.id).text(text).await?;
+
+    Ok(())
+}
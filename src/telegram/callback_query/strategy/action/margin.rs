@@ -0,0 +1,70 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::EditMessageTextSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{CallbackQuery, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    strategies::{
+        strategy::{Action, ActionKind, Strategy},
+        Strategies,
+    },
+    telegram::{
+        callback_query::{cancel, wrong_button},
+        constants::{AUTO_BORROW, AUTO_BORROW_REPAY, AUTO_REPAY, CANCEL, SKIP},
+        message::strategy::action::next,
+        State,
+    },
+};
+
+pub async fn receive_mode(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let mode = match data.as_str() {
+            AUTO_BORROW => (true, false),
+            AUTO_REPAY => (false, true),
+            AUTO_BORROW_REPAY => (true, true),
+            SKIP => (false, false),
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        if let ActionKind::MarginOrder {
+            auto_borrow,
+            auto_repay,
+            ..
+        } = action.kind_mut()
+        {
+            (*auto_borrow, *auto_repay) = mode;
+        }
+
+        bot.edit_message_text(
+            msg.chat().id,
+            msg.id(),
+            format!("Margin mode: <b>{data}</b>"),
+        )
+        .parse_mode(Html)
+        .await?;
+
+        next(
+            bot.clone(),
+            msg.chat().id,
+            dialogue,
+            strategies,
+            strategy,
+            action,
+        )
+        .await?;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
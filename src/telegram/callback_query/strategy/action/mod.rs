@@ -10,6 +10,7 @@ use teloxide::{
 };
 
 use crate::{
+    kucoin::trading::spot::order::Side,
     strategies::{
         strategy::{Action, ActionKind, Strategy},
         Strategies,
@@ -17,21 +18,29 @@ use crate::{
     telegram::{
         callback_query::{cancel, wrong_button},
         constants::{
-            ADD_ACTION, BACK_TO_ACTIONS, BUY, CANCEL, DELETE_ACTION, LEND, LIMIT, MARKET,
-            MOVE_DOWN, MOVE_UP, NO, REDEEM, SELL, TRANSFER, YES,
+            ADD_ACTION, BACK_TO_ACTIONS, BORROW, BUY, CANCEL, CANCEL_ORDERS, CLOSE_POSITION,
+            DELETE_ACTION, FUTURES_LONG, FUTURES_SHORT, LEND, LIMIT, MARGIN_BUY, MARGIN_SELL,
+            MARKET, MOVE_DOWN, MOVE_UP, NO, NOTIFY, OCO, REDEEM, REPAY, SELL, SKIP, STOP_ORDER,
+            SUB_TRANSFER, TRAILING_STOP, TRANSFER, WEBHOOK, WITHDRAW, YES,
         },
         keyboard::{self, KeyboardMarkupBuilder as _},
+        message::strategy::action::next,
         ActionState::{
-            Receive, ReceiveActionModif, ReceiveActionNumber, ReceiveDeleteConfirm, ReceiveOrder,
-            ReceiveSymbol,
+            Receive, ReceiveActionModif, ReceiveActionNumber, ReceiveCancelScope,
+            ReceiveDeleteConfirm, ReceiveNotifyTemplate, ReceiveOcoSide, ReceiveOrder,
+            ReceiveStopSide, ReceiveSymbol, ReceiveTrailingSide, ReceiveWebhookEvent,
         },
         State,
         StrategyState::Action as ActionState,
     },
 };
 
+pub mod margin;
 pub mod order;
+pub mod size;
+pub mod sub;
 pub mod transfer;
+pub mod withdraw;
 
 pub async fn edit(
     bot: Bot,
@@ -215,14 +224,32 @@ pub async fn receive(
 ) -> Result<(), RequestError> {
     if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
         let (text, markup) = match data.as_str() {
-            BUY | SELL => (
+            BUY | SELL | MARGIN_BUY | MARGIN_SELL => (
                 "Choose type:",
                 InlineKeyboardMarkup::from_str_items([vec![LIMIT, MARKET], vec![CANCEL]]),
             ),
-            LEND | REDEEM | TRANSFER => (
+            FUTURES_LONG | FUTURES_SHORT | CLOSE_POSITION => (
+                "Enter futures contract symbol (e.g. XBTUSDTM):",
+                InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+            ),
+            LEND | REDEEM | TRANSFER | SUB_TRANSFER | WITHDRAW | BORROW | REPAY => (
                 "Enter currency ticker:",
                 InlineKeyboardMarkup::from_str_items([[CANCEL]]),
             ),
+            TRAILING_STOP | STOP_ORDER | OCO => (
+                "Choose side:",
+                InlineKeyboardMarkup::from_str_items([vec![BUY, SELL], vec![CANCEL]]),
+            ),
+            CANCEL_ORDERS => ("Choose orders to cancel:", keyboard::choose_cancel_scope()),
+            NOTIFY => (
+                "Enter message template (placeholders: {strategy}, {product}, {value}, \
+                {balance:USDT}):",
+                InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+            ),
+            WEBHOOK => (
+                "Enter webhook event name:",
+                InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+            ),
             CANCEL => return cancel(bot, query, dialogue).await,
             _ => return wrong_button(bot, query).await,
         };
@@ -235,16 +262,46 @@ pub async fn receive(
             .reply_markup(markup)
             .await?;
 
+        if let TRAILING_STOP | STOP_ORDER | OCO | CANCEL_ORDERS = data.as_str() {
+            let state = match data.as_str() {
+                TRAILING_STOP => ReceiveTrailingSide { strategy },
+                STOP_ORDER => ReceiveStopSide { strategy },
+                OCO => ReceiveOcoSide { strategy },
+                _ => ReceiveCancelScope { strategy },
+            };
+
+            let _ = dialogue.update(State::Strategy(ActionState(state))).await;
+
+            bot.answer_callback_query(query.id).await?;
+
+            return Ok(());
+        }
+
         let action = match data.as_str() {
             BUY => Action::buy(),
             SELL => Action::sell(),
             LEND => Action::lend(),
             REDEEM => Action::redeem(),
+            MARGIN_BUY => Action::margin_order(Side::Buy),
+            MARGIN_SELL => Action::margin_order(Side::Sell),
+            BORROW => Action::borrow(),
+            REPAY => Action::repay(),
+            FUTURES_LONG => Action::futures_order(Side::Buy),
+            FUTURES_SHORT => Action::futures_order(Side::Sell),
+            CLOSE_POSITION => Action::close_position(),
+            SUB_TRANSFER => Action::sub_transfer(),
+            WITHDRAW => Action::withdraw(),
+            NOTIFY => Action::notify(),
+            WEBHOOK => Action::webhook(),
             _ => Action::transfer(),
         };
 
         let state = match action.kind() {
-            ActionKind::SpotOrder { .. } => ReceiveOrder { strategy, action },
+            ActionKind::SpotOrder { .. } | ActionKind::MarginOrder { .. } => {
+                ReceiveOrder { strategy, action }
+            }
+            ActionKind::Notify { .. } => ReceiveNotifyTemplate { strategy, action },
+            ActionKind::Webhook { .. } => ReceiveWebhookEvent { strategy, action },
             _ => ReceiveSymbol { strategy, action },
         };
 
@@ -255,3 +312,36 @@ pub async fn receive(
 
     Ok(())
 }
+
+pub async fn skip(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        match data.as_str() {
+            SKIP => {
+                bot.edit_message_reply_markup(msg.chat().id, msg.id())
+                    .await?;
+
+                next(
+                    bot.clone(),
+                    msg.chat().id,
+                    dialogue,
+                    strategies,
+                    strategy,
+                    action,
+                )
+                .await?;
+            }
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        }
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
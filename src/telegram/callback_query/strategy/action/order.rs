@@ -7,13 +7,20 @@ use teloxide::{
 };
 
 use crate::{
-    kucoin::trading::spot::order::Type,
-    strategies::strategy::{Action, ActionKind, Strategy},
+    kucoin::trading::spot::{
+        order::{Side, Type},
+        stop::Stop,
+    },
+    strategies::strategy::{Action, ActionKind, CancelScope, Strategy},
     telegram::{
         callback_query::{cancel, wrong_button},
-        constants::{CANCEL, LIMIT, MARKET},
+        constants::{
+            BUY, CANCEL, CLIENT_OID, ENTRY, LIMIT, LOSS, MARKET, PAIR_ORDERS, SELL, STRATEGY_ORDERS,
+        },
         keyboard::KeyboardMarkupBuilder as _,
-        ActionState::ReceiveSymbol,
+        ActionState::{
+            ReceiveOcoSide, ReceiveOrder, ReceiveStopSide, ReceiveStopType, ReceiveSymbol,
+        },
         State,
         StrategyState::Action as ActionState,
     },
@@ -26,7 +33,11 @@ pub async fn receive(
     (strategy, mut action): (Strategy, Action),
 ) -> Result<(), RequestError> {
     if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
-        if let ActionKind::SpotOrder { r#type, .. } = action.kind_mut() {
+        if let ActionKind::SpotOrder { r#type, .. }
+        | ActionKind::TrailingStop { r#type, .. }
+        | ActionKind::StopOrder { r#type, .. }
+        | ActionKind::MarginOrder { r#type, .. } = action.kind_mut()
+        {
             *r#type = match data.as_str() {
                 LIMIT => Type::Limit,
                 MARKET => Type::Market,
@@ -51,3 +62,124 @@ pub async fn receive(
 
     Ok(())
 }
+
+pub async fn receive_side(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategy: Strategy,
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let side = match data.as_str() {
+            BUY => Side::Buy,
+            SELL => Side::Sell,
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        let (text, markup, state) = match dialogue.get().await.unwrap().unwrap() {
+            State::Strategy(ActionState(ReceiveStopSide { .. })) => (
+                "Choose stop type:",
+                InlineKeyboardMarkup::from_str_items([vec![LOSS, ENTRY], vec![CANCEL]]),
+                ReceiveStopType {
+                    strategy,
+                    action: Action::stop_order(side),
+                },
+            ),
+            State::Strategy(ActionState(ReceiveOcoSide { .. })) => (
+                "Enter spot trading pair name:",
+                InlineKeyboardMarkup::from_str_items([vec![CANCEL]]),
+                ReceiveSymbol {
+                    strategy,
+                    action: Action::oco(side),
+                },
+            ),
+            _ => (
+                "Choose type:",
+                InlineKeyboardMarkup::from_str_items([vec![LIMIT, MARKET], vec![CANCEL]]),
+                ReceiveOrder {
+                    strategy,
+                    action: Action::trailing_stop(side),
+                },
+            ),
+        };
+
+        bot.send_message(msg.chat().id, text)
+            .reply_markup(markup)
+            .await?;
+
+        let _ = dialogue.update(State::Strategy(ActionState(state))).await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_cancel_scope(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategy: Strategy,
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let action = match data.as_str() {
+            STRATEGY_ORDERS => Action::cancel_order(CancelScope::Strategy),
+            PAIR_ORDERS => Action::cancel_order(CancelScope::Symbol),
+            CLIENT_OID => Action::cancel_order(CancelScope::ClientOid(Default::default())),
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        bot.send_message(msg.chat().id, "Enter spot trading pair name:")
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ActionState(ReceiveSymbol {
+                strategy,
+                action,
+            })))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_stop_type(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if let ActionKind::StopOrder { stop, .. } = action.kind_mut() {
+            *stop = match data.as_str() {
+                LOSS => Stop::Loss,
+                ENTRY => Stop::Entry,
+                CANCEL => return cancel(bot, query, dialogue).await,
+                _ => return wrong_button(bot, query).await,
+            };
+
+            bot.send_message(msg.chat().id, "Choose type:")
+                .reply_markup(InlineKeyboardMarkup::from_str_items([
+                    vec![LIMIT, MARKET],
+                    vec![CANCEL],
+                ]))
+                .await?;
+
+            let _ = dialogue
+                .update(State::Strategy(ActionState(ReceiveOrder {
+                    strategy,
+                    action,
+                })))
+                .await;
+        }
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
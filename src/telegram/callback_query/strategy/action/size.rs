@@ -0,0 +1,157 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::{EditMessageTextSetters as _, SendMessageSetters as _},
+    prelude::{Dialogue, Requester as _},
+    types::{CallbackQuery, InlineKeyboardMarkup, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::account::AccountType,
+    strategies::{
+        strategy::{Action, Size, Strategy},
+        Strategies,
+    },
+    telegram::{
+        callback_query::{cancel, wrong_button},
+        constants::{
+            CANCEL, FIXED_AMOUNT, KEEP_RESERVE, PERCENT_OF_AVAILABLE, PERCENT_OF_TOTAL,
+            QUOTE_VALUE, RESERVE_FEES, SKIP,
+        },
+        keyboard::KeyboardMarkupBuilder as _,
+        message::strategy::action::next,
+        ActionState::{ReceivePercentage, ReceiveSizeAmount},
+        State,
+        StrategyState::Action as ActionState,
+    },
+};
+
+pub async fn receive_mode(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let (size, text) = match data.as_str() {
+            PERCENT_OF_AVAILABLE => (Size::Available, "Enter percentage:"),
+            PERCENT_OF_TOTAL => (Size::Total, "Enter percentage:"),
+            FIXED_AMOUNT if action.is_futures() => {
+                (Size::Base(Default::default()), "Enter number of lots:")
+            }
+            FIXED_AMOUNT => (Size::Base(Default::default()), "Enter amount:"),
+            QUOTE_VALUE => (Size::Quote(Default::default()), "Enter quote value:"),
+            KEEP_RESERVE => (Size::Reserve(Default::default()), "Enter amount to keep:"),
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        bot.edit_message_text(msg.chat().id, msg.id(), format!("Size: <b>{data}</b>"))
+            .parse_mode(Html)
+            .await?;
+
+        bot.send_message(msg.chat().id, text)
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+
+        *action.size_mut() = size;
+
+        let state = match size.is_percentage() {
+            true => ReceivePercentage { strategy, action },
+            false => ReceiveSizeAmount { strategy, action },
+        };
+
+        let _ = dialogue.update(State::Strategy(ActionState(state))).await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_fee_reserve(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let reserve_fees = match data.as_str() {
+            RESERVE_FEES => true,
+            SKIP => false,
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        bot.edit_message_text(
+            msg.chat().id,
+            msg.id(),
+            format!(
+                "Reserve fees: <b>{}</b>",
+                if reserve_fees { "Yes" } else { "No" }
+            ),
+        )
+        .parse_mode(Html)
+        .await?;
+
+        *action.reserve_fees_mut() = reserve_fees;
+
+        next(
+            bot.clone(),
+            msg.chat().id,
+            dialogue,
+            strategies,
+            strategy,
+            action,
+        )
+        .await?;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_account(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let account = match data.as_str() {
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => match data.parse() {
+                Ok(AccountType::Trade) => None,
+                Ok(account @ AccountType::Main) => Some(account),
+                _ => return wrong_button(bot, query).await,
+            },
+        };
+
+        bot.edit_message_text(
+            msg.chat().id,
+            msg.id(),
+            format!("Source account: <b>{data}</b>"),
+        )
+        .parse_mode(Html)
+        .await?;
+
+        *action.account_mut() = account;
+
+        next(
+            bot.clone(),
+            msg.chat().id,
+            dialogue,
+            strategies,
+            strategy,
+            action,
+        )
+        .await?;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
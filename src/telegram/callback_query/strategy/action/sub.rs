@@ -0,0 +1,107 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::{EditMessageTextSetters as _, SendMessageSetters as _},
+    prelude::{Dialogue, Requester as _},
+    types::{CallbackQuery, InlineKeyboardMarkup, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::KuCoin,
+    strategies::strategy::{Action, ActionKind, Direction, Strategy},
+    telegram::{
+        callback_query::{cancel, wrong_button},
+        constants::{CANCEL, TO_PARENT, TO_SUB},
+        keyboard::{self, KeyboardMarkupBuilder as _},
+        ActionState::{ReceiveSubDirection, ReceiveTransferFrom},
+        State,
+        StrategyState::Action as ActionState,
+    },
+};
+
+pub async fn receive_sub_account(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if data == CANCEL {
+            return cancel(bot, query, dialogue).await;
+        }
+
+        if kucoin.sub_accounts().user_id(data).is_none() {
+            return wrong_button(bot, query).await;
+        }
+
+        if let ActionKind::SubTransfer { sub_account, .. } = action.kind_mut() {
+            *sub_account = data.clone();
+        }
+
+        bot.edit_message_text(
+            msg.chat().id,
+            msg.id(),
+            format!("Sub-account: <b>{data}</b>"),
+        )
+        .parse_mode(Html)
+        .await?;
+
+        bot.send_message(msg.chat().id, "Choose direction:")
+            .reply_markup(InlineKeyboardMarkup::from_str_items([
+                vec![TO_SUB, TO_PARENT],
+                vec![CANCEL],
+            ]))
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ActionState(ReceiveSubDirection {
+                strategy,
+                action,
+            })))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_direction(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let value = match data.as_str() {
+            TO_SUB => Direction::ToSub,
+            TO_PARENT => Direction::ToParent,
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        if let ActionKind::SubTransfer { direction, .. } = action.kind_mut() {
+            *direction = value;
+        }
+
+        bot.edit_message_text(msg.chat().id, msg.id(), format!("Direction: <b>{data}</b>"))
+            .parse_mode(Html)
+            .await?;
+
+        bot.send_message(msg.chat().id, "Choose FROM:")
+            .reply_markup(keyboard::choose_sub_account_type())
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ActionState(ReceiveTransferFrom {
+                strategy,
+                action,
+            })))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
@@ -7,15 +7,15 @@ use teloxide::{
 };
 
 use crate::{
-    kucoin::account::AccountType::{Isolated, IsolatedV2},
+    kucoin::account::AccountType::{Isolated, IsolatedV2, Main, Margin, Trade},
     strategies::strategy::{Action, ActionKind, Strategy},
     telegram::{
         callback_query::{cancel, wrong_button},
         constants::CANCEL,
         keyboard::{self, KeyboardMarkupBuilder as _},
         ActionState::{
-            ReceivePercentage, ReceiveTransferFrom, ReceiveTransferFromAccountTag,
-            ReceiveTransferTo, ReceiveTransferToAccountTag,
+            ReceiveSizeMode, ReceiveTransferFrom, ReceiveTransferFromAccountTag, ReceiveTransferTo,
+            ReceiveTransferToAccountTag,
         },
         State,
         StrategyState::Action as ActionState,
@@ -29,7 +29,11 @@ pub async fn receive(
     (strategy, mut action): (Strategy, Action),
 ) -> Result<(), RequestError> {
     if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
-        if let ActionKind::Transfer { from, to, .. } = action.kind_mut() {
+        let is_sub = matches!(action.kind(), ActionKind::SubTransfer { .. });
+
+        if let ActionKind::Transfer { from, to, .. } | ActionKind::SubTransfer { from, to, .. } =
+            action.kind_mut()
+        {
             let Ok(r#type) = (match data.as_str() {
                 CANCEL => return cancel(bot, query, dialogue).await,
                 _ => data.parse(),
@@ -37,6 +41,10 @@ pub async fn receive(
                 return wrong_button(bot, query).await;
             };
 
+            if is_sub && !matches!(r#type, Main | Trade | Margin) {
+                return wrong_button(bot, query).await;
+            }
+
             let is_from = matches!(
                 dialogue.get().await.unwrap().unwrap(),
                 State::Strategy(ActionState(ReceiveTransferFrom { .. }))
@@ -55,7 +63,7 @@ pub async fn receive(
                 .await?;
 
             let (text, markup, state) = match r#type {
-                Isolated | IsolatedV2 => (
+                Isolated | IsolatedV2 if !is_sub => (
                     "Enter symbol:",
                     InlineKeyboardMarkup::from_str_items([[CANCEL]]),
                     if is_from {
@@ -68,14 +76,17 @@ pub async fn receive(
                     if is_from {
                         (
                             "Choose TO:",
-                            keyboard::choose_account_type(Some(&r#type)),
+                            match is_sub {
+                                true => keyboard::choose_sub_account_type(),
+                                false => keyboard::choose_account_type(Some(&r#type)),
+                            },
                             ReceiveTransferTo { strategy, action },
                         )
                     } else {
                         (
-                            "Enter percentage:",
-                            InlineKeyboardMarkup::from_str_items([[CANCEL]]),
-                            ReceivePercentage { strategy, action },
+                            "Choose size:",
+                            keyboard::choose_size(&action),
+                            ReceiveSizeMode { strategy, action },
                         )
                     }
                 }
@@ -0,0 +1,135 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::{EditMessageTextSetters as _, SendMessageSetters as _},
+    prelude::{Dialogue, Requester as _},
+    types::{CallbackQuery, ParseMode::Html},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::KuCoin,
+    strategies::strategy::{Action, ActionKind, Strategy},
+    telegram::{
+        callback_query::{cancel, wrong_button},
+        constants::CANCEL,
+        keyboard,
+        ActionState::{ReceiveSizeMode, ReceiveWithdrawAddress},
+        State,
+        StrategyState::Action as ActionState,
+    },
+};
+
+pub async fn receive_chain(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if data == CANCEL {
+            return cancel(bot, query, dialogue).await;
+        }
+
+        let is_enabled = kucoin
+            .spot()
+            .currencies()
+            .get(action.symbol())
+            .and_then(|currency| currency.chain(data))
+            .is_some_and(|chain| chain.is_withdraw_enabled());
+
+        if !is_enabled {
+            return wrong_button(bot, query).await;
+        }
+
+        if let ActionKind::Withdraw { chain, .. } = action.kind_mut() {
+            *chain = data.clone();
+        }
+
+        bot.edit_message_text(msg.chat().id, msg.id(), format!("Chain: <b>{data}</b>"))
+            .parse_mode(Html)
+            .await?;
+
+        let names = kucoin.whitelist().names(action.symbol(), data);
+
+        match names.is_empty() {
+            true => {
+                bot.send_message(
+                    msg.chat().id,
+                    format!(
+                        "No whitelisted {} addresses on {data}\n\nAdd one to the whitelist file and restart",
+                        action.symbol()
+                    ),
+                )
+                .await?;
+
+                let _ = dialogue.reset().await;
+            }
+            false => {
+                bot.send_message(msg.chat().id, "Choose address:")
+                    .reply_markup(keyboard::choose_whitelisted(names))
+                    .await?;
+
+                let _ = dialogue
+                    .update(State::Strategy(ActionState(ReceiveWithdrawAddress {
+                        strategy,
+                        action,
+                    })))
+                    .await;
+            }
+        }
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_address(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if data == CANCEL {
+            return cancel(bot, query, dialogue).await;
+        }
+
+        let is_whitelisted = match action.kind() {
+            ActionKind::Withdraw { chain, .. } => kucoin
+                .whitelist()
+                .names(action.symbol(), chain)
+                .contains(data),
+            _ => false,
+        };
+
+        if !is_whitelisted {
+            return wrong_button(bot, query).await;
+        }
+
+        if let ActionKind::Withdraw { address, .. } = action.kind_mut() {
+            *address = data.clone();
+        }
+
+        bot.edit_message_text(msg.chat().id, msg.id(), format!("Address: <b>{data}</b>"))
+            .parse_mode(Html)
+            .await?;
+
+        bot.send_message(msg.chat().id, "Choose size:")
+            .reply_markup(keyboard::choose_size(&action))
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ActionState(ReceiveSizeMode {
+                strategy,
+                action,
+            })))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
@@ -11,9 +11,9 @@ use crate::{
     strategies::strategy::{Condition, Strategy},
     telegram::{
         callback_query::{cancel, wrong_button},
-        constants::{ABOVE, BELOW, CANCEL},
+        constants::{ABOVE, BELOW, CANCEL, VS_PRODUCT},
         keyboard::KeyboardMarkupBuilder as _,
-        ConditionState::{Receive, ReceiveValue},
+        ConditionState::{Receive, ReceiveBound, ReceiveOperand, ReceiveValue},
         State,
         StrategyState::Condition as ConditionState,
     },
@@ -26,7 +26,7 @@ pub async fn edit(
     strategy: Strategy,
 ) -> Result<(), RequestError> {
     if let Some(msg) = &query.message {
-        let inline_keyboard = [vec![ABOVE, BELOW], vec![CANCEL]];
+        let inline_keyboard = [vec![ABOVE, BELOW], vec![VS_PRODUCT], vec![CANCEL]];
 
         bot.send_message(msg.chat().id, "Choose condition:")
             .reply_markup(InlineKeyboardMarkup::from_str_items(inline_keyboard))
@@ -52,6 +52,27 @@ pub async fn receive(
         let condition = match data.as_str() {
             ABOVE => Condition::GreaterThan(Default::default()),
             BELOW => Condition::LessThan(Default::default()),
+            VS_PRODUCT => {
+                bot.edit_message_text(msg.chat().id, msg.id(), format!("Condition: <b>{data}</b>"))
+                    .parse_mode(Html)
+                    .await?;
+
+                bot.send_message(
+                    msg.chat().id,
+                    "Enter product to compare with (pair e.g. BTC-USDT, lending currency e.g. USDC \
+                    or implied price e.g. ETH-USDT/BTC-USDT):",
+                )
+                .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+                .await?;
+
+                let _ = dialogue
+                    .update(State::Strategy(ConditionState(ReceiveOperand { strategy })))
+                    .await;
+
+                bot.answer_callback_query(query.id).await?;
+
+                return Ok(());
+            }
             CANCEL => return cancel(bot, query, dialogue).await,
             _ => return wrong_button(bot, query).await,
         };
@@ -76,3 +97,98 @@ pub async fn receive(
 
     Ok(())
 }
+
+pub async fn receive_relation(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    (strategy, condition): (Strategy, Condition),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let Condition::Cross {
+            other, threshold, ..
+        } = condition
+        else {
+            return wrong_button(bot, query).await;
+        };
+
+        let Ok(relation) = (match data.as_str() {
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => data.parse(),
+        }) else {
+            return wrong_button(bot, query).await;
+        };
+
+        bot.edit_message_text(msg.chat().id, msg.id(), format!("Relation: <b>{data}</b>"))
+            .parse_mode(Html)
+            .await?;
+
+        bot.send_message(msg.chat().id, "Choose condition:")
+            .reply_markup(InlineKeyboardMarkup::from_str_items([
+                vec![ABOVE, BELOW],
+                vec![CANCEL],
+            ]))
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ConditionState(ReceiveBound {
+                strategy,
+                condition: Condition::Cross {
+                    other,
+                    relation,
+                    threshold,
+                },
+            })))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_bound(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    (strategy, condition): (Strategy, Condition),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let Condition::Cross {
+            other, relation, ..
+        } = condition
+        else {
+            return wrong_button(bot, query).await;
+        };
+
+        let threshold = match data.as_str() {
+            ABOVE => Condition::GreaterThan(Default::default()),
+            BELOW => Condition::LessThan(Default::default()),
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => return wrong_button(bot, query).await,
+        };
+
+        bot.edit_message_text(msg.chat().id, msg.id(), format!("Condition: <b>{data}</b>"))
+            .parse_mode(Html)
+            .await?;
+
+        bot.send_message(msg.chat().id, "Enter value:")
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ConditionState(ReceiveValue {
+                strategy,
+                condition: Condition::Cross {
+                    other,
+                    relation,
+                    threshold: Box::new(threshold),
+                },
+            })))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
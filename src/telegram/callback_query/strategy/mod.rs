@@ -4,18 +4,24 @@ use teloxide::{
         AnswerCallbackQuerySetters as _, EditMessageTextSetters as _, SendMessageSetters as _,
     },
     prelude::{Dialogue, Requester as _},
-    types::{CallbackQuery, InlineKeyboardMarkup, ParseMode::Html},
+    types::{CallbackQuery, InlineKeyboardMarkup, InputFile, ParseMode::Html},
+    utils::html,
     Bot, RequestError,
 };
 
 use crate::{
-    strategies::{strategy::Strategy, Strategies},
+    strategies::{
+        document::{self, Format},
+        journal::PAGE_SIZE,
+        strategy::Strategy,
+        Strategies,
+    },
     telegram::{
-        callback_query::wrong_button,
+        callback_query::{cancel, wrong_button},
         constants::{CANCEL, NO, YES},
         keyboard::{self, KeyboardMarkupBuilder as _},
         State,
-        StrategyState::{ReceiveDeleteConfirm, ReceiveName},
+        StrategyState::{ReceiveDeleteConfirm, ReceiveExportFormat, ReceiveImport, ReceiveName},
     },
 };
 
@@ -97,6 +103,172 @@ pub async fn delete(
     Ok(())
 }
 
+pub async fn history(
+    bot: Bot,
+    query: CallbackQuery,
+    strategies: Strategies,
+    strategy: Strategy,
+    page: Option<usize>,
+) -> Result<(), RequestError> {
+    if let Some(msg) = &query.message {
+        let (entries, pages) =
+            strategies
+                .journal()
+                .history(strategy.name(), page.unwrap_or_default(), PAGE_SIZE);
+
+        // The name goes first so paging can find the strategy again.
+        let name = html::escape(strategy.name());
+        let text = match entries.is_empty() {
+            true => format!("Name: {name}\nNo executions recorded"),
+            false => format!(
+                "Name: {name}\nPage {} of {pages}\n\n{}",
+                page.unwrap_or_default() + 1,
+                entries
+                    .iter()
+                    .map(|entry| entry.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            ),
+        };
+        let markup = keyboard::history(page.unwrap_or_default(), pages);
+
+        match page {
+            Some(_) => {
+                bot.edit_message_text(msg.chat().id, msg.id(), text)
+                    .parse_mode(Html)
+                    .reply_markup(markup)
+                    .await?;
+            }
+            None => {
+                bot.send_message(msg.chat().id, text)
+                    .parse_mode(Html)
+                    .reply_markup(markup)
+                    .await?;
+            }
+        }
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn toggle_approval(
+    bot: Bot,
+    query: CallbackQuery,
+    strategies: Strategies,
+    mut strategy: Strategy,
+) -> Result<(), RequestError> {
+    strategy.set_require_approval(!strategy.require_approval());
+    strategies.add(strategy.clone());
+
+    if let Some(msg) = &query.message {
+        bot.edit_message_text(msg.chat().id, msg.id(), strategy.to_string())
+            .reply_markup(keyboard::edit_strategy())
+            .parse_mode(Html)
+            .await?;
+    }
+
+    let text = match strategy.require_approval() {
+        true => "Actions now require approval",
+        false => "Actions no longer require approval",
+    };
+
+    bot.answer_callback_query(query.id).text(text).await?;
+
+    Ok(())
+}
+
+pub async fn import(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+) -> Result<(), RequestError> {
+    if let Some(msg) = query.message {
+        bot.send_message(msg.chat().id, "Send JSON or YAML file with strategies:")
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+
+        let _ = dialogue.update(State::Strategy(ReceiveImport)).await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn export(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Vec<Strategy>,
+) -> Result<(), RequestError> {
+    if strategies.is_empty() {
+        bot.answer_callback_query(query.id)
+            .text("No strategies to export")
+            .await?;
+
+        return Ok(());
+    }
+
+    if let Some(msg) = &query.message {
+        bot.send_message(msg.chat().id, "Choose format:")
+            .reply_markup(keyboard::choose_format())
+            .await?;
+
+        let _ = dialogue
+            .update(State::Strategy(ReceiveExportFormat { strategies }))
+            .await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
+pub async fn receive_export_format(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Vec<Strategy>,
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        let Ok(format) = (match data.as_str() {
+            CANCEL => return cancel(bot, query, dialogue).await,
+            _ => data.parse::<Format>(),
+        }) else {
+            return wrong_button(bot, query).await;
+        };
+
+        let file_name = match strategies.as_slice() {
+            [strategy] => format!("{}.{}", strategy.name(), format.extension()),
+            _ => format!("strategies.{}", format.extension()),
+        };
+
+        match document::export(strategies, format) {
+            Ok(content) => {
+                bot.delete_message(msg.chat().id, msg.id()).await?;
+
+                bot.send_document(
+                    msg.chat().id,
+                    InputFile::memory(content.into_bytes()).file_name(file_name),
+                )
+                .await?;
+            }
+            Err(e) => {
+                bot.edit_message_text(msg.chat().id, msg.id(), format!("Export error\n\n{e}"))
+                    .await?;
+            }
+        }
+
+        let _ = dialogue.reset().await;
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
 pub async fn back_to_strategies(
     bot: Bot,
     query: CallbackQuery,
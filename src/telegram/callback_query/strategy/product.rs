@@ -13,7 +13,7 @@ use crate::{
     },
     telegram::{
         callback_query::{cancel, wrong_button},
-        constants::{BALANCE, CANCEL, LENDING, SPOT_TRADING},
+        constants::{BALANCE, CANCEL, DEPOSIT, FUTURES, LENDING, MARGIN_DEBT_RATIO, SPOT_TRADING},
         keyboard::{self, KeyboardMarkupBuilder as _},
         ProductState::{Receive, ReceiveSymbol},
         State,
@@ -28,7 +28,11 @@ pub async fn edit(
     strategy: Strategy,
 ) -> Result<(), RequestError> {
     if let Some(msg) = &query.message {
-        let inline_keyboard = [vec![SPOT_TRADING, LENDING, BALANCE], vec![CANCEL]];
+        let inline_keyboard = [
+            vec![SPOT_TRADING, LENDING, BALANCE],
+            vec![MARGIN_DEBT_RATIO, FUTURES, DEPOSIT],
+            vec![CANCEL],
+        ];
 
         bot.send_message(msg.chat().id, "Choose product:")
             .reply_markup(InlineKeyboardMarkup::from_str_items(inline_keyboard))
@@ -48,12 +52,31 @@ pub async fn receive(
     bot: Bot,
     query: CallbackQuery,
     dialogue: Dialogue<State, InMemStorage<State>>,
-    strategy: Strategy,
+    strategies: Strategies,
+    mut strategy: Strategy,
 ) -> Result<(), RequestError> {
     if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if data == MARGIN_DEBT_RATIO {
+            strategy.set_product(Product::MarginDebtRatio);
+
+            bot.edit_message_text(msg.chat().id, msg.id(), strategy.to_string())
+                .reply_markup(keyboard::edit_strategy())
+                .parse_mode(Html)
+                .await?;
+
+            strategies.add(strategy);
+
+            let _ = dialogue.reset().await;
+
+            bot.answer_callback_query(query.id).await?;
+
+            return Ok(());
+        }
+
         let text = match data.as_str() {
             SPOT_TRADING => "Enter spot trading pair name:",
-            LENDING | BALANCE => "Enter currency ticker:",
+            FUTURES => "Enter futures contract symbol (e.g. XBTUSDTM):",
+            LENDING | BALANCE | DEPOSIT => "Enter currency ticker:",
             CANCEL => return cancel(bot, query, dialogue).await,
             _ => return wrong_button(bot, query).await,
         };
@@ -69,6 +92,11 @@ pub async fn receive(
         let product = match data.as_str() {
             SPOT_TRADING => Product::SpotTradingPair(Default::default()),
             LENDING => Product::LendingCurrency(Default::default()),
+            FUTURES => Product::FuturesContract {
+                symbol: Default::default(),
+                metric: Default::default(),
+            },
+            DEPOSIT => Product::DepositReceived(Default::default()),
             _ => Product::BalanceCurrency {
                 r#type: Default::default(),
                 currency: Default::default(),
@@ -88,6 +116,40 @@ pub async fn receive(
     Ok(())
 }
 
+pub async fn receive_futures_metric(
+    bot: Bot,
+    query: CallbackQuery,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (mut strategy, product): (Strategy, Product),
+) -> Result<(), RequestError> {
+    if let Some((data, msg)) = query.data.as_ref().zip(query.message.as_ref()) {
+        if let Product::FuturesContract { symbol, .. } = product {
+            let Ok(metric) = (match data.as_str() {
+                CANCEL => return cancel(bot, query, dialogue).await,
+                _ => data.parse(),
+            }) else {
+                return wrong_button(bot, query).await;
+            };
+
+            strategy.set_product(Product::FuturesContract { symbol, metric });
+
+            bot.edit_message_text(msg.chat().id, msg.id(), strategy.to_string())
+                .reply_markup(keyboard::edit_strategy())
+                .parse_mode(Html)
+                .await?;
+
+            strategies.add(strategy);
+
+            let _ = dialogue.reset().await;
+        }
+    }
+
+    bot.answer_callback_query(query.id).await?;
+
+    Ok(())
+}
+
 pub async fn receive_balance_account_type(
     bot: Bot,
     query: CallbackQuery,
@@ -1,11 +1,20 @@
 pub const NOTIFICATIONS: &str = "Notifications";
 pub const BALANCE: &str = "Balance";
+pub const DEPOSIT: &str = "Deposit";
 pub const LENDING: &str = "Lending";
 pub const PAIRS: &str = "Pairs";
 pub const SPOT_TRADING: &str = "Spot Trading";
+pub const MARGIN_DEBT_RATIO: &str = "Margin Debt Ratio";
+pub const FUTURES: &str = "Futures";
+pub const OPEN_ORDERS: &str = "Open Orders";
+pub const RISK: &str = "Risk";
+pub const ENGAGE_KILL_SWITCH: &str = "🛑 Engage Kill Switch";
+pub const CLEAR_KILL_SWITCH: &str = "✅ Clear Kill Switch";
 
 pub const STRATEGIES: &str = "Strategies";
 pub const CREATE_STRATEGY: &str = "Create";
+pub const IMPORT_STRATEGIES: &str = "Import";
+pub const EXPORT_STRATEGIES: &str = "Export All";
 
 pub const CANCEL: &str = "Cancel";
 
@@ -13,6 +22,12 @@ pub const EDIT_NAME: &str = "Edit Name";
 pub const EDIT_PRODUCT: &str = "Edit Product";
 pub const EDIT_CONDITION: &str = "Edit Condition";
 pub const DELETE_STRATEGY: &str = "Delete Strategy";
+pub const HISTORY: &str = "History";
+pub const HISTORY_PAGE: &str = "history:";
+pub const NEWER: &str = "⬅️ Newer";
+pub const OLDER: &str = "Older ➡️";
+pub const TOGGLE_APPROVAL: &str = "Toggle Approval";
+pub const EXPORT_STRATEGY: &str = "Export";
 pub const BACK_TO_STRATEGIES: &str = "« Back to Strategies";
 pub const EDIT_ACTIONS: &str = "Edit Actions";
 pub const ADD_ACTION: &str = "Add";
@@ -21,18 +36,59 @@ pub const BACK_TO_ACTIONS: &str = "« Back to Actions";
 
 pub const YES: &str = "Yes";
 pub const NO: &str = "No";
+pub const SKIP: &str = "Skip";
 
 pub const MOVE_UP: &str = "🔼";
 pub const MOVE_DOWN: &str = "🔽";
 
 pub const ABOVE: &str = "Above";
 pub const BELOW: &str = "Below";
+pub const VS_PRODUCT: &str = "Vs Product";
 
 pub const BUY: &str = "Buy";
 pub const SELL: &str = "Sell";
 pub const LEND: &str = "Lend";
 pub const REDEEM: &str = "Redeem";
 pub const TRANSFER: &str = "Transfer";
+pub const SUB_TRANSFER: &str = "Sub-account Transfer";
+pub const WITHDRAW: &str = "Withdraw";
+pub const TRAILING_STOP: &str = "Trailing Stop";
+pub const CANCEL_ORDERS: &str = "Cancel Orders";
+pub const STOP_ORDER: &str = "Stop Order";
+pub const OCO: &str = "OCO";
+pub const MARGIN_BUY: &str = "Margin Buy";
+pub const MARGIN_SELL: &str = "Margin Sell";
+pub const BORROW: &str = "Borrow";
+pub const REPAY: &str = "Repay";
+pub const FUTURES_LONG: &str = "Futures Long";
+pub const FUTURES_SHORT: &str = "Futures Short";
+pub const CLOSE_POSITION: &str = "Close Position";
+pub const NOTIFY: &str = "Notify";
+pub const WEBHOOK: &str = "Webhook";
+
+pub const TO_SUB: &str = "To Sub-account";
+pub const TO_PARENT: &str = "To Parent";
+
+pub const AUTO_BORROW: &str = "Auto-Borrow";
+pub const AUTO_REPAY: &str = "Auto-Repay";
+pub const AUTO_BORROW_REPAY: &str = "Auto-Borrow & Repay";
+
+pub const LOSS: &str = "Loss";
+pub const ENTRY: &str = "Entry";
+
+pub const STRATEGY_ORDERS: &str = "Strategy Orders";
+pub const PAIR_ORDERS: &str = "All Pair Orders";
+pub const CLIENT_OID: &str = "By Client OID";
+
+pub const PERCENT_OF_AVAILABLE: &str = "% of Available";
+pub const PERCENT_OF_TOTAL: &str = "% of Total";
+pub const FIXED_AMOUNT: &str = "Fixed Amount";
+pub const QUOTE_VALUE: &str = "Quote Value";
+pub const KEEP_RESERVE: &str = "Keep Reserve";
+pub const RESERVE_FEES: &str = "Reserve Fees";
+
+pub const JSON: &str = "JSON";
+pub const YAML: &str = "YAML";
 
 pub const LIMIT: &str = "Limit";
 pub const MARKET: &str = "Market";
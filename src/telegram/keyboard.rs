@@ -3,11 +3,21 @@ use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, KeyboardButton
 
 use crate::{
     kucoin::{account::AccountType, announcements::AnnouncementType, Lending, SpotTrading},
-    strategies::{strategy::Actions, Strategies},
+    strategies::{
+        risk::Risk,
+        strategy::{Action, Actions, FuturesMetric, Relation},
+        Strategies,
+    },
     telegram::constants::{
-        ADD_ACTION, BACK_TO_ACTIONS, BACK_TO_STRATEGIES, BUY, CANCEL, CREATE_STRATEGY,
-        DELETE_ACTION, DELETE_STRATEGY, EDIT_ACTIONS, EDIT_CONDITION, EDIT_NAME, EDIT_PRODUCT,
-        LEND, MOVE_DOWN, MOVE_UP, REDEEM, SELL, TRANSFER,
+        ADD_ACTION, AUTO_BORROW, AUTO_BORROW_REPAY, AUTO_REPAY, BACK_TO_ACTIONS,
+        BACK_TO_STRATEGIES, BORROW, BUY, CANCEL, CANCEL_ORDERS, CLEAR_KILL_SWITCH, CLIENT_OID,
+        CLOSE_POSITION, CREATE_STRATEGY, DELETE_ACTION, DELETE_STRATEGY, EDIT_ACTIONS,
+        EDIT_CONDITION, EDIT_NAME, EDIT_PRODUCT, ENGAGE_KILL_SWITCH, EXPORT_STRATEGIES,
+        EXPORT_STRATEGY, FIXED_AMOUNT, FUTURES_LONG, FUTURES_SHORT, HISTORY, HISTORY_PAGE,
+        IMPORT_STRATEGIES, JSON, KEEP_RESERVE, LEND, MARGIN_BUY, MARGIN_SELL, MOVE_DOWN, MOVE_UP,
+        NEWER, NOTIFY, OCO, OLDER, PAIR_ORDERS, PERCENT_OF_AVAILABLE, PERCENT_OF_TOTAL,
+        QUOTE_VALUE, REDEEM, REPAY, RESERVE_FEES, SELL, SKIP, STOP_ORDER, STRATEGY_ORDERS,
+        SUB_TRANSFER, TOGGLE_APPROVAL, TRAILING_STOP, TRANSFER, WEBHOOK, WITHDRAW, YAML,
     },
 };
 
@@ -49,17 +59,47 @@ impl KeyboardMarkupBuilder for InlineKeyboardMarkup {
 }
 
 pub fn strategies(strategies: &Strategies) -> InlineKeyboardMarkup {
-    InlineKeyboardMarkup::from_str_items([strategies.names(), vec![CREATE_STRATEGY.to_string()]])
+    InlineKeyboardMarkup::from_str_items([
+        strategies.names(),
+        vec![CREATE_STRATEGY.to_string()],
+        vec![IMPORT_STRATEGIES.to_string(), EXPORT_STRATEGIES.to_string()],
+    ])
 }
 
 pub fn edit_strategy() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::from_str_items([
         vec![EDIT_NAME, EDIT_PRODUCT],
         vec![EDIT_CONDITION, EDIT_ACTIONS],
+        vec![HISTORY, EXPORT_STRATEGY],
+        vec![TOGGLE_APPROVAL],
         vec![DELETE_STRATEGY, BACK_TO_STRATEGIES],
     ])
 }
 
+pub fn history(page: usize, pages: usize) -> InlineKeyboardMarkup {
+    let mut row = Vec::new();
+
+    if page > 0 {
+        row.push(InlineKeyboardButton::callback(
+            NEWER,
+            format!("{HISTORY_PAGE}{}", page - 1),
+        ));
+    }
+
+    if page + 1 < pages {
+        row.push(InlineKeyboardButton::callback(
+            OLDER,
+            format!("{HISTORY_PAGE}{}", page + 1),
+        ));
+    }
+
+    InlineKeyboardMarkup::new([row])
+}
+
+pub fn choose_format() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([vec![JSON, YAML], vec![CANCEL]])
+}
+
 pub fn announcement_types() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::from_str_items(
         AnnouncementType::VARIANTS
@@ -69,6 +109,13 @@ pub fn announcement_types() -> InlineKeyboardMarkup {
     )
 }
 
+pub fn risk(risk: &Risk) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([[match risk.is_killed() {
+        true => CLEAR_KILL_SWITCH,
+        false => ENGAGE_KILL_SWITCH,
+    }]])
+}
+
 pub fn choose_action_number(actions: &Actions) -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::from_str_items([
         (1..actions.len() + 1)
@@ -97,11 +144,96 @@ pub fn choose_action() -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::from_str_items([
         vec![BUY, SELL],
         vec![LEND, REDEEM],
-        vec![TRANSFER],
+        vec![TRANSFER, TRAILING_STOP],
+        vec![SUB_TRANSFER, WITHDRAW],
+        vec![STOP_ORDER, OCO],
+        vec![MARGIN_BUY, MARGIN_SELL],
+        vec![BORROW, REPAY],
+        vec![FUTURES_LONG, FUTURES_SHORT],
+        vec![CLOSE_POSITION, CANCEL_ORDERS],
+        vec![NOTIFY, WEBHOOK],
         vec![CANCEL],
     ])
 }
 
+pub fn choose_cancel_scope() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([
+        vec![STRATEGY_ORDERS, PAIR_ORDERS],
+        vec![CLIENT_OID],
+        vec![CANCEL],
+    ])
+}
+
+pub fn choose_size(action: &Action) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([
+        vec![PERCENT_OF_AVAILABLE, PERCENT_OF_TOTAL],
+        match action.is_spot() || action.is_margin() {
+            true => vec![FIXED_AMOUNT, QUOTE_VALUE],
+            false => vec![FIXED_AMOUNT],
+        },
+        vec![KEEP_RESERVE],
+        vec![CANCEL],
+    ])
+}
+
+pub fn choose_futures_metric() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([FuturesMetric::VARIANTS.to_vec(), vec![CANCEL]])
+}
+
+pub fn choose_relation() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([Relation::VARIANTS.to_vec(), vec![CANCEL]])
+}
+
+pub fn choose_margin_mode() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([
+        vec![AUTO_BORROW, AUTO_REPAY],
+        vec![AUTO_BORROW_REPAY],
+        vec![SKIP, CANCEL],
+    ])
+}
+
+pub fn choose_fee_reserve() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([vec![RESERVE_FEES, SKIP], vec![CANCEL]])
+}
+
+pub fn choose_source_account() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([
+        vec![
+            AccountType::Trade.to_string(),
+            AccountType::Main.to_string(),
+        ],
+        vec![CANCEL.to_string()],
+    ])
+}
+
+pub fn choose_sub_account(names: Vec<String>) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([names, vec![CANCEL.to_string()]])
+}
+
+pub fn choose_chain(chains: Vec<String>) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([chains, vec![CANCEL.to_string()]])
+}
+
+pub fn choose_whitelisted(names: Vec<String>) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items(
+        names
+            .into_iter()
+            .map(|name| vec![name])
+            .chain([vec![CANCEL.to_string()]]),
+    )
+}
+
+pub fn choose_sub_account_type() -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::from_str_items([
+        vec![
+            AccountType::Main.to_string(),
+            AccountType::Trade.to_string(),
+            AccountType::Margin.to_string(),
+        ],
+        vec![CANCEL.to_string()],
+    ])
+}
+
 pub fn choose_account_type(skip: Option<&AccountType>) -> InlineKeyboardMarkup {
     let mut inline_keyboard = [
         AccountType::VARIANTS[..3].to_vec(),
@@ -126,3 +258,44 @@ pub fn recent_pairs(spot: &SpotTrading) -> InlineKeyboardMarkup {
 pub fn recent_currencies(lending: &Lending) -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::from_str_items([lending.currencies().recent(), vec![CANCEL.to_string()]])
 }
+
+pub fn open_orders(spot: &SpotTrading) -> InlineKeyboardMarkup {
+    let active = spot
+        .active()
+        .all()
+        .into_iter()
+        .enumerate()
+        .map(|(index, order)| {
+            [InlineKeyboardButton::callback(
+                format!(
+                    "❌ {}. {} {} @ {}",
+                    index + 1,
+                    order.side(),
+                    order.symbol(),
+                    order.price()
+                ),
+                order.id(),
+            )]
+        });
+
+    let stops = spot
+        .stops()
+        .all()
+        .into_iter()
+        .enumerate()
+        .map(|(index, order)| {
+            [InlineKeyboardButton::callback(
+                format!(
+                    "❌ S{}. {} {} {} {}",
+                    index + 1,
+                    order.side(),
+                    order.symbol(),
+                    order.stop(),
+                    order.stop_price()
+                ),
+                order.id(),
+            )]
+        });
+
+    InlineKeyboardMarkup::new(active.chain(stops))
+}
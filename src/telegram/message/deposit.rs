@@ -0,0 +1,64 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::SendMessageSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{InlineKeyboardMarkup, Message, MessageId},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::KuCoin,
+    telegram::{
+        constants::CANCEL,
+        keyboard::{self, KeyboardMarkupBuilder as _},
+        State,
+    },
+};
+
+pub async fn receive_currency(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let currency = msg.text().unwrap_or_default().to_uppercase();
+
+    let maybe_chains: Option<Vec<String>> = kucoin
+        .spot()
+        .currencies()
+        .get(&currency)
+        .map(|currency| {
+            currency
+                .chains()
+                .iter()
+                .filter(|chain| chain.is_deposit_enabled())
+                .map(|chain| chain.chain_id().to_string())
+                .collect()
+        })
+        .filter(|chains: &Vec<String>| !chains.is_empty());
+
+    match maybe_chains {
+        Some(chains) => {
+            bot.send_message(msg.chat.id, "Choose chain:")
+                .reply_markup(keyboard::choose_chain(chains))
+                .await?;
+
+            let _ = dialogue
+                .update(State::ReceiveDepositChain { currency })
+                .await;
+        }
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                "Currency not found or deposits disabled\n\nEnter currency ticker:",
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use teloxide::{
     dispatching::dialogue::InMemStorage,
     payloads::SendMessageSetters as _,
     prelude::{Dialogue, Request, Requester as _},
-    types::{KeyboardMarkup, Message, ParseMode::Html},
+    types::{InlineKeyboardMarkup, KeyboardMarkup, Message, ParseMode::Html},
     Bot, RequestError,
 };
 
@@ -10,12 +10,15 @@ use crate::{
     kucoin::KuCoin,
     strategies::Strategies,
     telegram::{
-        constants::{BALANCE, LENDING, NOTIFICATIONS, PAIRS, STRATEGIES},
+        constants::{
+            BALANCE, CANCEL, DEPOSIT, LENDING, NOTIFICATIONS, OPEN_ORDERS, PAIRS, RISK, STRATEGIES,
+        },
         keyboard::{self, KeyboardMarkupBuilder as _},
         State,
     },
 };
 
+pub mod deposit;
 pub mod lending;
 pub mod pair;
 pub mod strategy;
@@ -34,6 +37,16 @@ pub async fn handler(
                     .reply_markup(keyboard::strategies(&strategies))
                     .await?;
             }
+            OPEN_ORDERS => {
+                let spot = kucoin.spot();
+
+                let text = kucoin.mode_tag().to_string() + &spot.orders();
+
+                bot.send_message(msg.chat.id, text)
+                    .reply_markup(keyboard::open_orders(spot))
+                    .parse_mode(Html)
+                    .await?;
+            }
             NOTIFICATIONS => {
                 let text = "Toggle notifications for different announcement types:";
 
@@ -42,16 +55,36 @@ pub async fn handler(
                     .send()
                     .await?;
             }
+            RISK => {
+                let text = kucoin.mode_tag().to_string() + &strategies.risk().status(kucoin.now());
+
+                bot.send_message(msg.chat.id, text)
+                    .reply_markup(keyboard::risk(strategies.risk()))
+                    .parse_mode(Html)
+                    .await?;
+            }
             BALANCE => {
-                let text = match kucoin.lending().to_string() + &kucoin.accounts().to_string() {
+                let text = match kucoin.lending().to_string()
+                    + &kucoin.accounts().to_string()
+                    + &kucoin.sub_accounts().to_string()
+                    + &kucoin.margin().to_string()
+                    + &kucoin.futures().to_string()
+                {
                     text if text.is_empty() => {
                         "Account balance is empty or wasn't fetched yet".to_string()
                     }
-                    text => text,
+                    text => kucoin.mode_tag().to_string() + &text,
                 };
 
                 bot.send_message(msg.chat.id, text).parse_mode(Html).await?;
             }
+            DEPOSIT => {
+                bot.send_message(msg.chat.id, "Enter currency ticker:")
+                    .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+                    .await?;
+
+                let _ = dialogue.update(State::ReceiveDepositCurrency).await;
+            }
             PAIRS => {
                 bot.send_message(msg.chat.id, "Enter spot trading pair name:")
                     .reply_markup(keyboard::recent_pairs(kucoin.spot()))
@@ -69,15 +102,18 @@ pub async fn handler(
             _ => {
                 let text = format!(
                     "<b>{STRATEGIES}</b> — create and manage strategies\n\
+                    <b>{OPEN_ORDERS}</b> — show and cancel open spot and stop orders\n\
                     <b>{NOTIFICATIONS}</b> — customize notifications\n\
+                    <b>{RISK}</b> — show risk limits and toggle the kill switch\n\
                     <b>{BALANCE}</b> — show overall balance\n\
+                    <b>{DEPOSIT}</b> — show deposit address and recent deposits\n\
                     <b>{LENDING}</b> — show lending currencies info\n\
                     <b>{PAIRS}</b> — show spot pairs info"
                 );
 
                 let keyboard = [
-                    vec![STRATEGIES, NOTIFICATIONS],
-                    vec![BALANCE, LENDING, PAIRS],
+                    vec![STRATEGIES, OPEN_ORDERS, NOTIFICATIONS, RISK],
+                    vec![BALANCE, DEPOSIT, LENDING, PAIRS],
                 ];
 
                 let markup = KeyboardMarkup::from_str_items(keyboard)
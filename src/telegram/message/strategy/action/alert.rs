@@ -0,0 +1,84 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::SendMessageSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{InlineKeyboardMarkup, Message, MessageId},
+    Bot, RequestError,
+};
+
+use crate::{
+    strategies::{
+        strategy::{Action, ActionKind, Strategy},
+        Strategies,
+    },
+    telegram::{constants::CANCEL, keyboard::KeyboardMarkupBuilder as _, State},
+};
+
+use super::next;
+
+pub async fn receive_template(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    match msg.text().map(str::trim).filter(|text| !text.is_empty()) {
+        Some(text) => {
+            if let ActionKind::Notify { template } = action.kind_mut() {
+                *template = text.to_string();
+            }
+
+            *action.skip_mut() = false;
+
+            next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        None => {
+            bot.send_message(msg.chat.id, "Template is empty\n\nEnter message template:")
+                .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn receive_event(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    match msg
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty() && !text.contains(char::is_whitespace))
+    {
+        Some(text) => {
+            if let ActionKind::Webhook { event } = action.kind_mut() {
+                *event = text.to_string();
+            }
+
+            *action.skip_mut() = false;
+
+            next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                "Event name must be a single word\n\nEnter webhook event name:",
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
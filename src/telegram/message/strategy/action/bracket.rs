@@ -0,0 +1,68 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::SendMessageSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{InlineKeyboardMarkup, Message, MessageId},
+    Bot, RequestError,
+};
+
+use crate::{
+    strategies::{
+        brackets::Level,
+        strategy::{Action, ActionKind, Strategy},
+        Strategies,
+    },
+    telegram::{
+        constants::{CANCEL, SKIP},
+        keyboard::KeyboardMarkupBuilder as _,
+        ActionState::ReceiveTakeProfit,
+        State,
+        StrategyState::Action as ActionState,
+    },
+};
+
+pub async fn receive_level(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let is_take_profit = matches!(
+        dialogue.get().await.unwrap().unwrap(),
+        State::Strategy(ActionState(ReceiveTakeProfit { .. }))
+    );
+
+    let maybe_level = msg
+        .text()
+        .unwrap_or_default()
+        .parse::<Level>()
+        .ok()
+        .filter(|level| level.validate(is_take_profit).is_ok());
+
+    match (maybe_level, action.kind_mut()) {
+        (Some(level), ActionKind::SpotOrder { bracket, .. }) => {
+            let bracket = bracket.get_or_insert_with(Default::default);
+
+            match is_take_profit {
+                true => *bracket.take_profit_mut() = Some(level),
+                false => *bracket.stop_loss_mut() = Some(level),
+            }
+
+            super::next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        _ => {
+            bot.send_message(
+                msg.chat.id,
+                "Value parse error\n\nEnter price or percentage (e.g. 5%):",
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::SendMessageSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{InlineKeyboardMarkup, Message, MessageId},
+    Bot, RequestError,
+};
+
+use crate::{
+    kucoin::KuCoin,
+    strategies::strategy::{Action, ActionKind, Strategy},
+    telegram::{
+        constants::CANCEL,
+        keyboard::{self, KeyboardMarkupBuilder as _},
+        ActionState::ReceiveSizeMode,
+        State,
+        StrategyState::Action as ActionState,
+    },
+};
+
+pub async fn receive_leverage(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let max_leverage = kucoin
+        .futures()
+        .contracts()
+        .get(action.symbol())
+        .map(|contract| contract.max_leverage())
+        .unwrap_or(1);
+
+    match msg
+        .text()
+        .unwrap_or_default()
+        .trim_end_matches(['x', 'X'])
+        .parse()
+        .ok()
+        .filter(|value| (1..=max_leverage).contains(value))
+    {
+        Some(value) => {
+            if let ActionKind::FuturesOrder { leverage, .. } = action.kind_mut() {
+                *leverage = value;
+            }
+
+            bot.send_message(msg.chat.id, "Choose size:")
+                .reply_markup(keyboard::choose_size(&action))
+                .await?;
+
+            let _ = dialogue
+                .update(State::Strategy(ActionState(ReceiveSizeMode {
+                    strategy,
+                    action,
+                })))
+                .await;
+        }
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                format!("Value parse error\n\nEnter leverage between 1-{max_leverage}:"),
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -10,8 +10,11 @@ use crate::{
     kucoin::KuCoin,
     strategies::strategy::{Action, ActionKind, Strategy},
     telegram::{
-        constants::CANCEL, keyboard::KeyboardMarkupBuilder as _, ActionState::ReceivePercentage,
-        State, StrategyState::Action as ActionState,
+        constants::CANCEL,
+        keyboard::{self, KeyboardMarkupBuilder as _},
+        ActionState::ReceiveSizeMode,
+        State,
+        StrategyState::Action as ActionState,
     },
 };
 
@@ -39,6 +42,7 @@ pub async fn receive_interest_rate(
         .unwrap_or_default();
 
     let mut maybe_state = None;
+    let mut markup = InlineKeyboardMarkup::from_str_items([[CANCEL]]);
 
     let text = match msg
         .text()
@@ -52,12 +56,14 @@ pub async fn receive_interest_rate(
                 *interest_rate = value;
             }
 
-            maybe_state = Some(State::Strategy(ActionState(ReceivePercentage {
+            markup = keyboard::choose_size(&action);
+
+            maybe_state = Some(State::Strategy(ActionState(ReceiveSizeMode {
                 strategy,
                 action,
             })));
 
-            "Enter percentage:"
+            "Choose size:"
         }
         Some(_) => &format!(
             "Value parse error\n\nEnter Min Lending APY with increment of {}",
@@ -70,7 +76,7 @@ pub async fn receive_interest_rate(
     };
 
     bot.send_message(msg.chat.id, text)
-        .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+        .reply_markup(markup)
         .await?;
 
     if let Some(state) = maybe_state {
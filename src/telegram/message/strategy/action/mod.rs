@@ -2,29 +2,40 @@ use teloxide::{
     dispatching::dialogue::InMemStorage,
     payloads::SendMessageSetters as _,
     prelude::{Dialogue, Requester as _},
-    types::{InlineKeyboardMarkup, Message, MessageId, ParseMode::Html},
+    types::{ChatId, InlineKeyboardMarkup, Message, MessageId, ParseMode::Html},
     Bot, RequestError,
 };
 
 use crate::{
-    kucoin::{trading::spot::order::Type, KuCoin},
+    kucoin::{
+        trading::spot::order::{Side, Type},
+        KuCoin,
+    },
     strategies::{
-        strategy::{Action, ActionKind, Strategy},
+        strategy::{Action, ActionKind, CancelScope, Strategy},
         Strategies,
     },
     telegram::{
-        constants::CANCEL,
+        constants::{CANCEL, SKIP},
         keyboard::{self, KeyboardMarkupBuilder as _},
         ActionState::{
-            ReceiveLendInterestRate, ReceiveOrderPrice, ReceivePercentage, ReceiveTransferFrom,
+            ReceiveClientOid, ReceiveFeeReserve, ReceiveLendInterestRate, ReceiveLeverage,
+            ReceiveMarginMode, ReceiveOrderOptions, ReceiveOrderPrice, ReceivePercentage,
+            ReceiveSizeAmount, ReceiveSizeMode, ReceiveSourceAccount, ReceiveStopLoss,
+            ReceiveStopPrice, ReceiveSubAccount, ReceiveTakeProfit, ReceiveTrailingCallbackRate,
+            ReceiveTransferFrom, ReceiveWait, ReceiveWithdrawChain,
         },
         State,
         StrategyState::Action as ActionState,
     },
 };
 
+pub mod alert;
+pub mod bracket;
+pub mod futures;
 pub mod lend;
 pub mod order;
+pub mod size;
 pub mod transfer;
 
 pub async fn receive_symbol(
@@ -32,6 +43,7 @@ pub async fn receive_symbol(
     msg: Message,
     dialogue: Dialogue<State, InMemStorage<State>>,
     kucoin: KuCoin,
+    strategies: Strategies,
     (strategy, mut action): (Strategy, Action),
 ) -> Result<(), RequestError> {
     bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
@@ -40,7 +52,12 @@ pub async fn receive_symbol(
     let symbol = msg.text().unwrap_or_default().to_uppercase();
 
     let maybe_error_text = match action.kind() {
-        ActionKind::SpotOrder { .. } => kucoin
+        ActionKind::SpotOrder { .. }
+        | ActionKind::TrailingStop { .. }
+        | ActionKind::StopOrder { .. }
+        | ActionKind::Oco { .. }
+        | ActionKind::MarginOrder { .. }
+        | ActionKind::CancelOrder { .. } => kucoin
             .spot()
             .tickers()
             .get(&symbol, false)
@@ -52,7 +69,22 @@ pub async fn receive_symbol(
             .get(&symbol, false)
             .is_none()
             .then_some("Currency not found\n\nEnter currency ticker:"),
-        ActionKind::Transfer { .. } => None,
+        ActionKind::Borrow
+        | ActionKind::Repay
+        | ActionKind::SubTransfer { .. }
+        | ActionKind::Withdraw { .. } => kucoin
+            .spot()
+            .currencies()
+            .get(&symbol)
+            .is_none()
+            .then_some("Currency not found\n\nEnter currency ticker:"),
+        ActionKind::FuturesOrder { .. } | ActionKind::ClosePosition => {
+            (!kucoin.futures().contracts().contains_key(&symbol))
+                .then_some("Contract not found\n\nEnter contract symbol:")
+        }
+        ActionKind::Transfer { .. } | ActionKind::Notify { .. } | ActionKind::Webhook { .. } => {
+            None
+        }
     };
 
     match maybe_error_text {
@@ -64,29 +96,97 @@ pub async fn receive_symbol(
         None => {
             *action.symbol_mut() = symbol;
 
+            if let ActionKind::CancelOrder {
+                scope: CancelScope::Strategy | CancelScope::Symbol,
+            } = action.kind()
+            {
+                *action.skip_mut() = false;
+
+                return next(bot, msg.chat.id, dialogue, strategies, strategy, action).await;
+            }
+
+            if let ActionKind::ClosePosition = action.kind() {
+                *action.skip_mut() = false;
+
+                return next(bot, msg.chat.id, dialogue, strategies, strategy, action).await;
+            }
+
             let (text, markup, state) = match action.kind() {
                 ActionKind::SpotOrder {
                     r#type: Type::Limit,
                     ..
+                }
+                | ActionKind::MarginOrder {
+                    r#type: Type::Limit,
+                    ..
                 } => (
-                    "Enter price:",
+                    "Enter price (e.g. 30000, last-1%, bid+5t):",
+                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+                    ReceiveOrderPrice { strategy, action },
+                ),
+                ActionKind::Oco { .. } => (
+                    "Enter limit price (e.g. 32000, last+5%):",
                     InlineKeyboardMarkup::from_str_items([[CANCEL]]),
                     ReceiveOrderPrice { strategy, action },
                 ),
+                ActionKind::StopOrder { .. } => (
+                    "Enter stop price (e.g. 30000, last-5%):",
+                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+                    ReceiveStopPrice { strategy, action },
+                ),
+                ActionKind::TrailingStop { .. } => (
+                    "Enter callback rate (%):",
+                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+                    ReceiveTrailingCallbackRate { strategy, action },
+                ),
+                ActionKind::FuturesOrder { .. } => (
+                    "Enter leverage:",
+                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+                    ReceiveLeverage { strategy, action },
+                ),
                 ActionKind::Lend { .. } => (
                     "Enter Min lending APY:",
                     InlineKeyboardMarkup::from_str_items([[CANCEL]]),
                     ReceiveLendInterestRate { strategy, action },
                 ),
+                ActionKind::CancelOrder { .. } => (
+                    "Enter client order ID:",
+                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
+                    ReceiveClientOid { strategy, action },
+                ),
+                ActionKind::SubTransfer { .. } => (
+                    "Choose sub-account:",
+                    keyboard::choose_sub_account(kucoin.sub_accounts().names()),
+                    ReceiveSubAccount { strategy, action },
+                ),
+                ActionKind::Withdraw { .. } => (
+                    "Choose chain:",
+                    keyboard::choose_chain(
+                        kucoin
+                            .spot()
+                            .currencies()
+                            .get(action.symbol())
+                            .map(|currency| {
+                                currency
+                                    .chains()
+                                    .iter()
+                                    .filter(|chain| chain.is_withdraw_enabled())
+                                    .map(|chain| chain.chain_id().to_string())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    ),
+                    ReceiveWithdrawChain { strategy, action },
+                ),
                 ActionKind::Transfer { .. } => (
                     "Choose FROM:",
                     keyboard::choose_account_type(None),
                     ReceiveTransferFrom { strategy, action },
                 ),
                 _ => (
-                    "Enter percentage:",
-                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
-                    ReceivePercentage { strategy, action },
+                    "Choose size:",
+                    keyboard::choose_size(&action),
+                    ReceiveSizeMode { strategy, action },
                 ),
             };
 
@@ -106,7 +206,7 @@ pub async fn receive_percentage(
     msg: Message,
     dialogue: Dialogue<State, InMemStorage<State>>,
     strategies: Strategies,
-    (mut strategy, mut action): (Strategy, Action),
+    (strategy, mut action): (Strategy, Action),
 ) -> Result<(), RequestError> {
     bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
         .await?;
@@ -122,16 +222,7 @@ pub async fn receive_percentage(
             *action.percentage_mut() = percentage;
             *action.skip_mut() = false;
 
-            strategy.actions_mut().add(action);
-
-            bot.send_message(msg.chat.id, strategy.to_string())
-                .reply_markup(keyboard::edit_strategy())
-                .parse_mode(Html)
-                .await?;
-
-            strategies.add(strategy);
-
-            let _ = dialogue.reset().await;
+            next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
         }
         None => {
             bot.send_message(
@@ -145,3 +236,115 @@ pub async fn receive_percentage(
 
     Ok(())
 }
+
+pub async fn next(
+    bot: Bot,
+    chat_id: ChatId,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    mut strategy: Strategy,
+    action: Action,
+) -> Result<(), RequestError> {
+    let is_spot = action.is_spot();
+
+    let follows_spot = strategy
+        .actions()
+        .last()
+        .is_some_and(|previous| previous.is_tracked());
+
+    let is_oco = matches!(action.kind(), ActionKind::Oco { .. });
+    let is_fixed = action.size().is_fixed();
+    let is_margin = action.is_margin();
+
+    let is_buy = matches!(
+        action.kind(),
+        ActionKind::SpotOrder {
+            side: Side::Buy,
+            ..
+        }
+    );
+
+    let (text, markup, state) =
+        match dialogue.get().await.unwrap().unwrap() {
+            State::Strategy(ActionState(ReceivePercentage { .. } | ReceiveSizeAmount { .. }))
+                if is_spot =>
+            {
+                (
+                    "Choose source account:",
+                    keyboard::choose_source_account(),
+                    ReceiveSourceAccount { strategy, action },
+                )
+            }
+            State::Strategy(ActionState(ReceiveSourceAccount { .. })) if !is_fixed => (
+                "Reserve fees charged in the spent currency from the size?",
+                keyboard::choose_fee_reserve(),
+                ReceiveFeeReserve { strategy, action },
+            ),
+            State::Strategy(ActionState(
+                ReceiveSourceAccount { .. } | ReceiveFeeReserve { .. },
+            )) if !is_oco => (
+                "Enter order options (e.g. GTT CANCEL_AFTER=60, POST_ONLY, HIDDEN, ICEBERG=0.5, \
+                STP=CN, REMARK=text, TAGS=text):",
+                InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]),
+                ReceiveOrderOptions { strategy, action },
+            ),
+            State::Strategy(ActionState(ReceivePercentage { .. } | ReceiveSizeAmount { .. }))
+                if is_margin =>
+            {
+                (
+                    "Choose margin mode:",
+                    keyboard::choose_margin_mode(),
+                    ReceiveMarginMode { strategy, action },
+                )
+            }
+            State::Strategy(ActionState(
+                ReceivePercentage { .. }
+                | ReceiveSizeAmount { .. }
+                | ReceiveSourceAccount { .. }
+                | ReceiveFeeReserve { .. }
+                | ReceiveOrderOptions { .. }
+                | ReceiveMarginMode { .. },
+            )) if follows_spot => (
+                "Enter seconds to wait for the previous order to fill before executing:",
+                InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]),
+                ReceiveWait { strategy, action },
+            ),
+            State::Strategy(ActionState(
+                ReceivePercentage { .. }
+                | ReceiveSizeAmount { .. }
+                | ReceiveOrderOptions { .. }
+                | ReceiveWait { .. },
+            )) if is_buy => (
+                "Enter take profit price or percentage from fill price (e.g. 5%):",
+                InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]),
+                ReceiveTakeProfit { strategy, action },
+            ),
+            State::Strategy(ActionState(ReceiveTakeProfit { .. })) => (
+                "Enter stop loss price or percentage from fill price (e.g. 5%).\n\n\
+                The stop loss isn't placed on KuCoin: the bot sells at market once the last price \
+                reaches it, checking every 5 seconds while it's running:",
+                InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]),
+                ReceiveStopLoss { strategy, action },
+            ),
+            _ => {
+                strategy.actions_mut().add(action);
+
+                bot.send_message(chat_id, strategy.to_string())
+                    .reply_markup(keyboard::edit_strategy())
+                    .parse_mode(Html)
+                    .await?;
+
+                strategies.add(strategy);
+
+                let _ = dialogue.reset().await;
+
+                return Ok(());
+            }
+        };
+
+    bot.send_message(chat_id, text).reply_markup(markup).await?;
+
+    let _ = dialogue.update(State::Strategy(ActionState(state))).await;
+
+    Ok(())
+}
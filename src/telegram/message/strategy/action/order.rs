@@ -7,11 +7,22 @@ use teloxide::{
 };
 
 use crate::{
-    kucoin::KuCoin,
-    strategies::strategy::{Action, ActionKind, Strategy},
+    kucoin::{
+        trading::spot::order::{Options, Type},
+        KuCoin,
+    },
+    strategies::{
+        strategy::{Action, ActionKind, CancelScope, Price, Strategy},
+        Strategies,
+    },
     telegram::{
-        constants::CANCEL, keyboard::KeyboardMarkupBuilder as _, ActionState::ReceivePercentage,
-        State, StrategyState::Action as ActionState,
+        constants::{CANCEL, SKIP},
+        keyboard::{self, KeyboardMarkupBuilder as _},
+        ActionState::{
+            ReceiveOrderPrice, ReceiveSizeMode, ReceiveStopLimitPrice, ReceiveStopPrice,
+        },
+        State,
+        StrategyState::Action as ActionState,
     },
 };
 
@@ -25,15 +36,22 @@ pub async fn receive_price(
     bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
         .await?;
 
+    let current = dialogue.get().await.unwrap().unwrap();
+
+    let is_stop_price = matches!(
+        current,
+        State::Strategy(ActionState(ReceiveStopPrice { .. }))
+    );
+
+    let is_stop_limit_price = matches!(
+        current,
+        State::Strategy(ActionState(ReceiveStopLimitPrice { .. }))
+    );
+
     let mut maybe_state = None;
+    let mut markup = InlineKeyboardMarkup::from_str_items([[CANCEL]]);
 
-    let text = match msg
-        .text()
-        .unwrap_or_default()
-        .parse()
-        .ok()
-        .filter(|value: &f64| *value > 0.0)
-    {
+    let text = match msg.text().unwrap_or_default().parse::<Price>().ok() {
         Some(value) => {
             let maybe_increment = kucoin
                 .spot()
@@ -42,17 +60,50 @@ pub async fn receive_price(
                 .map(|symbol| symbol.price_increment());
 
             match maybe_increment {
-                Some(increment) if (value / increment).fract() == 0.0 => {
-                    if let ActionKind::SpotOrder { price, .. } = action.kind_mut() {
-                        *price = Some(value);
+                Some(increment) if value.validate(increment).is_ok() => {
+                    match action.kind_mut() {
+                        ActionKind::StopOrder { stop_price, .. }
+                        | ActionKind::Oco { stop_price, .. }
+                            if is_stop_price =>
+                        {
+                            *stop_price = value
+                        }
+                        ActionKind::Oco { limit_price, .. } if is_stop_limit_price => {
+                            *limit_price = value
+                        }
+                        ActionKind::Oco { price, .. } => *price = value,
+                        ActionKind::SpotOrder { price, .. }
+                        | ActionKind::StopOrder { price, .. }
+                        | ActionKind::MarginOrder { price, .. } => *price = Some(value),
+                        _ => (),
                     }
 
-                    maybe_state = Some(State::Strategy(ActionState(ReceivePercentage {
-                        strategy,
-                        action,
-                    })));
+                    let (text, state) = match action.kind() {
+                        ActionKind::StopOrder {
+                            r#type: Type::Limit,
+                            ..
+                        } if is_stop_price => (
+                            "Enter limit price (e.g. 30000, last-1%, bid+5t):",
+                            ReceiveOrderPrice { strategy, action },
+                        ),
+                        ActionKind::Oco { .. } if is_stop_price => (
+                            "Enter stop limit price (e.g. 29900, last-6%):",
+                            ReceiveStopLimitPrice { strategy, action },
+                        ),
+                        ActionKind::Oco { .. } if !is_stop_limit_price => (
+                            "Enter stop price (e.g. 30000, last-5%):",
+                            ReceiveStopPrice { strategy, action },
+                        ),
+                        _ => {
+                            markup = keyboard::choose_size(&action);
 
-                    "Enter percentage:"
+                            ("Choose size:", ReceiveSizeMode { strategy, action })
+                        }
+                    };
+
+                    maybe_state = Some(State::Strategy(ActionState(state)));
+
+                    text
                 }
                 Some(increment) => {
                     &format!("Value parse error\n\nEnter price with increment of {increment}:")
@@ -60,11 +111,11 @@ pub async fn receive_price(
                 None => "Internal error\n\nEnter price:",
             }
         }
-        None => "Value parse error\n\nEnter price:",
+        None => "Value parse error\n\nEnter price (e.g. 30000, last-1%, bid+5t):",
     };
 
     bot.send_message(msg.chat.id, text)
-        .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+        .reply_markup(markup)
         .await?;
 
     if let Some(state) = maybe_state {
@@ -73,3 +124,156 @@ pub async fn receive_price(
 
     Ok(())
 }
+
+pub async fn receive_callback_rate(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    match msg
+        .text()
+        .unwrap_or_default()
+        .parse()
+        .ok()
+        .filter(|value: &f64| *value > 0.0 && *value < 100.0)
+    {
+        Some(value) => {
+            if let ActionKind::TrailingStop { callback_rate, .. } = action.kind_mut() {
+                *callback_rate = value;
+            }
+
+            bot.send_message(msg.chat.id, "Choose size:")
+                .reply_markup(keyboard::choose_size(&action))
+                .await?;
+
+            let _ = dialogue
+                .update(State::Strategy(ActionState(ReceiveSizeMode {
+                    strategy,
+                    action,
+                })))
+                .await;
+        }
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                "Value parse error\n\nEnter callback rate between 0-100:",
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn receive_wait(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    match msg
+        .text()
+        .unwrap_or_default()
+        .parse()
+        .ok()
+        .filter(|value: &u64| *value > 0)
+    {
+        Some(value) => {
+            *action.wait_mut() = Some(value);
+
+            super::next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        None => {
+            bot.send_message(
+                msg.chat.id,
+                "Value parse error\n\nEnter number of seconds greater than 0:",
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn receive_client_oid(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let client_oid = msg.text().unwrap_or_default().trim();
+
+    let is_valid = (1..=40).contains(&client_oid.len())
+        && client_oid
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    match (is_valid, action.kind_mut()) {
+        (true, ActionKind::CancelOrder { scope }) => {
+            *scope = CancelScope::ClientOid(client_oid.to_string());
+            *action.skip_mut() = false;
+
+            super::next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        _ => {
+            bot.send_message(
+                msg.chat.id,
+                "Value parse error\n\nEnter client order ID (up to 40 letters, digits, - or _):",
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn receive_options(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let r#type = match action.kind() {
+        ActionKind::SpotOrder { r#type, .. } | ActionKind::TrailingStop { r#type, .. } => *r#type,
+        _ => Default::default(),
+    };
+
+    match msg
+        .text()
+        .unwrap_or_default()
+        .parse::<Options>()
+        .and_then(|options| options.validate(r#type).map(|_| options))
+    {
+        Ok(options) => {
+            *action.options_mut() = options;
+
+            super::next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        Err(e) => {
+            bot.send_message(msg.chat.id, format!("{e}\n\nEnter order options:"))
+                .reply_markup(InlineKeyboardMarkup::from_str_items([[SKIP], [CANCEL]]))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
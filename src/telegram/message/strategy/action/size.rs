@@ -0,0 +1,55 @@
+use teloxide::{
+    dispatching::dialogue::InMemStorage,
+    payloads::SendMessageSetters as _,
+    prelude::{Dialogue, Requester as _},
+    types::{InlineKeyboardMarkup, Message, MessageId},
+    Bot, RequestError,
+};
+
+use crate::{
+    strategies::{
+        strategy::{Action, Size, Strategy},
+        Strategies,
+    },
+    telegram::{constants::CANCEL, keyboard::KeyboardMarkupBuilder as _, State},
+};
+
+pub async fn receive_amount(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    strategies: Strategies,
+    (strategy, mut action): (Strategy, Action),
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let maybe_size =
+        msg.text()
+            .unwrap_or_default()
+            .parse()
+            .ok()
+            .and_then(|value: f64| match action.size() {
+                Size::Base(_) if value > 0.0 => Some(Size::Base(value)),
+                Size::Quote(_) if value > 0.0 => Some(Size::Quote(value)),
+                Size::Reserve(_) if value >= 0.0 => Some(Size::Reserve(value)),
+                _ => None,
+            });
+
+    match maybe_size {
+        Some(size) => {
+            *action.size_mut() = size;
+            *action.percentage_mut() = 100;
+            *action.skip_mut() = false;
+
+            super::next(bot, msg.chat.id, dialogue, strategies, strategy, action).await?;
+        }
+        None => {
+            bot.send_message(msg.chat.id, "Value parse error\n\nEnter amount:")
+                .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -11,7 +11,7 @@ use crate::{
     telegram::{
         constants::CANCEL,
         keyboard::{self, KeyboardMarkupBuilder as _},
-        ActionState::{ReceivePercentage, ReceiveTransferFromAccountTag, ReceiveTransferTo},
+        ActionState::{ReceiveSizeMode, ReceiveTransferFromAccountTag, ReceiveTransferTo},
         State,
         StrategyState::Action as ActionState,
     },
@@ -54,9 +54,9 @@ pub async fn receive_account_tag(
                     ReceiveTransferTo { strategy, action },
                 ),
                 false => (
-                    "Enter percentage:",
-                    InlineKeyboardMarkup::from_str_items([[CANCEL]]),
-                    ReceivePercentage { strategy, action },
+                    "Choose size:",
+                    keyboard::choose_size(&action),
+                    ReceiveSizeMode { strategy, action },
                 ),
             };
 
@@ -7,14 +7,17 @@ use teloxide::{
 };
 
 use crate::{
+    kucoin::KuCoin,
     strategies::{
-        strategy::{Condition, Strategy},
+        strategy::{Condition, Operand, Strategy},
         Strategies,
     },
     telegram::{
         constants::CANCEL,
         keyboard::{self, KeyboardMarkupBuilder as _},
+        ConditionState::ReceiveRelation,
         State,
+        StrategyState::Condition as ConditionState,
     },
 };
 
@@ -30,12 +33,7 @@ pub async fn receive_value(
 
     match msg.text().map(|text| text.parse()) {
         Some(Ok(value)) => {
-            let condition = match condition {
-                Condition::GreaterThan(_) => Condition::GreaterThan(value),
-                Condition::LessThan(_) => Condition::LessThan(value),
-            };
-
-            strategy.set_condition(condition);
+            strategy.set_condition(condition.with_value(value));
 
             bot.send_message(msg.chat.id, strategy.to_string())
                 .reply_markup(keyboard::edit_strategy())
@@ -55,3 +53,52 @@ pub async fn receive_value(
 
     Ok(())
 }
+
+pub async fn receive_operand(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    strategy: Strategy,
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let maybe_other = msg
+        .text()
+        .unwrap_or_default()
+        .parse::<Operand>()
+        .ok()
+        .filter(|other| {
+            other
+                .products()
+                .into_iter()
+                .all(|product| product.is_known(&kucoin))
+        });
+
+    match maybe_other {
+        Some(other) => {
+            bot.send_message(msg.chat.id, "Choose relation:")
+                .reply_markup(keyboard::choose_relation())
+                .await?;
+
+            let _ = dialogue
+                .update(State::Strategy(ConditionState(ReceiveRelation {
+                    strategy,
+                    condition: Condition::Cross {
+                        other,
+                        relation: Default::default(),
+                        threshold: Box::new(Condition::GreaterThan(Default::default())),
+                    },
+                })))
+                .await;
+        }
+        None => {
+            bot.send_message(msg.chat.id, "Product not found\n\nEnter product:")
+                .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
+use anyhow::anyhow;
 use teloxide::{
     dispatching::dialogue::InMemStorage,
+    net::Download as _,
     payloads::SendMessageSetters as _,
     prelude::{Dialogue, Requester as _},
     types::{InlineKeyboardMarkup, Message, MessageId, ParseMode::Html},
@@ -7,7 +9,12 @@ use teloxide::{
 };
 
 use crate::{
-    strategies::{strategy::Strategy, Strategies},
+    kucoin::KuCoin,
+    strategies::{
+        document::{self, Format},
+        strategy::Strategy,
+        Strategies,
+    },
     telegram::{
         constants::CANCEL,
         keyboard::{self, KeyboardMarkupBuilder as _},
@@ -65,3 +72,83 @@ pub async fn receive_name(
 
     Ok(())
 }
+
+pub async fn receive_import(
+    bot: Bot,
+    msg: Message,
+    dialogue: Dialogue<State, InMemStorage<State>>,
+    kucoin: KuCoin,
+    strategies: Strategies,
+) -> Result<(), RequestError> {
+    bot.edit_message_reply_markup(msg.chat.id, MessageId(msg.id.0 - 1))
+        .await?;
+
+    let Some(file) = msg.document() else {
+        bot.send_message(msg.chat.id, "File expected\n\nSend JSON or YAML file:")
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+
+        return Ok(());
+    };
+
+    let format = Format::from_file_name(file.file_name.as_deref().unwrap_or_default());
+
+    let mut content = vec![];
+
+    let path = bot.get_file(&file.file.id).await?.path;
+
+    let result = match bot.download_file(&path, &mut content).await {
+        Ok(_) => String::from_utf8(content)
+            .map_err(Into::into)
+            .and_then(|content| document::import(&content, format))
+            .and_then(|imported| {
+                let mut names: Vec<&str> = vec![];
+
+                for strategy in &imported {
+                    if strategies.get(strategy.name()).is_some() || names.contains(&strategy.name())
+                    {
+                        return Err(anyhow!(
+                            "Strategy with the name {} already exists",
+                            strategy.name()
+                        ));
+                    }
+
+                    strategy.validate(&kucoin)?;
+
+                    names.push(strategy.name());
+                }
+
+                Ok(imported)
+            }),
+        Err(e) => Err(e.into()),
+    };
+
+    match result {
+        Ok(imported) => {
+            let len = imported.len();
+
+            for strategy in imported {
+                strategies.add(strategy);
+            }
+
+            bot.send_message(
+                msg.chat.id,
+                format!("Imported {len} strategies\n\n{strategies}"),
+            )
+            .reply_markup(keyboard::strategies(&strategies))
+            .await?;
+
+            let _ = dialogue.reset().await;
+        }
+        Err(e) => {
+            bot.send_message(
+                msg.chat.id,
+                format!("Import error: {e}\n\nSend JSON or YAML file:"),
+            )
+            .reply_markup(InlineKeyboardMarkup::from_str_items([[CANCEL]]))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
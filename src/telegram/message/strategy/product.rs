@@ -15,7 +15,7 @@ use crate::{
     telegram::{
         constants::CANCEL,
         keyboard::{self, KeyboardMarkupBuilder as _},
-        ProductState::ReceiveBalanceAccountType,
+        ProductState::{ReceiveBalanceAccountType, ReceiveFuturesMetric},
         State,
         StrategyState::Product as ProductState,
     },
@@ -49,6 +49,17 @@ pub async fn receive_symbol(
             r#type,
             currency: symbol,
         }),
+        Product::MarginDebtRatio => Some(Product::MarginDebtRatio),
+        Product::FuturesContract { metric, .. } => kucoin
+            .futures()
+            .contracts()
+            .contains_key(&symbol)
+            .then_some(Product::FuturesContract { symbol, metric }),
+        Product::DepositReceived(_) => kucoin
+            .spot()
+            .currencies()
+            .contains_key(&symbol)
+            .then_some(Product::DepositReceived(symbol)),
     };
 
     match maybe_product {
@@ -65,6 +76,18 @@ pub async fn receive_symbol(
                     })))
                     .await;
             }
+            Product::FuturesContract { .. } => {
+                bot.send_message(msg.chat.id, "Choose metric:")
+                    .reply_markup(keyboard::choose_futures_metric())
+                    .await?;
+
+                let _ = dialogue
+                    .update(State::Strategy(ProductState(ReceiveFuturesMetric {
+                        strategy,
+                        product,
+                    })))
+                    .await;
+            }
             _ => {
                 strategy.set_product(product);
 
@@ -81,6 +104,7 @@ pub async fn receive_symbol(
         None => {
             let text = match product {
                 Product::SpotTradingPair(_) => "Pair not found\n\nEnter pair name:",
+                Product::FuturesContract { .. } => "Contract not found\n\nEnter contract symbol:",
                 _ => "Currency not found\n\nEnter currency ticker:",
             };
 
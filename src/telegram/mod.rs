@@ -24,6 +24,7 @@ mod constants;
 mod keyboard;
 mod message;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Default, Clone)]
 enum State {
     #[default]
@@ -31,12 +32,18 @@ enum State {
     Strategy(StrategyState),
     ReceivePairSymbol,
     ReceiveLendingCurrency,
+    ReceiveDepositCurrency,
+    ReceiveDepositChain {
+        currency: String,
+    },
 }
 
 #[derive(Clone)]
 enum StrategyState {
     ReceiveName { maybe_strategy: Option<Strategy> },
     ReceiveDeleteConfirm { strategy: Strategy },
+    ReceiveImport,
+    ReceiveExportFormat { strategies: Vec<Strategy> },
     Product(ProductState),
     Condition(ConditionState),
     Action(ActionState),
@@ -55,6 +62,10 @@ enum ProductState {
         strategy: Strategy,
         product: Product,
     },
+    ReceiveFuturesMetric {
+        strategy: Strategy,
+        product: Product,
+    },
 }
 
 #[derive(Clone)]
@@ -66,6 +77,17 @@ enum ConditionState {
         strategy: Strategy,
         condition: Condition,
     },
+    ReceiveOperand {
+        strategy: Strategy,
+    },
+    ReceiveRelation {
+        strategy: Strategy,
+        condition: Condition,
+    },
+    ReceiveBound {
+        strategy: Strategy,
+        condition: Condition,
+    },
 }
 
 #[derive(Clone)]
@@ -76,13 +98,38 @@ enum ActionState {
     Receive { strategy: Strategy },
     ReceiveOrder { strategy: Strategy, action: Action },
     ReceiveOrderPrice { strategy: Strategy, action: Action },
+    ReceiveTrailingSide { strategy: Strategy },
+    ReceiveStopSide { strategy: Strategy },
+    ReceiveStopType { strategy: Strategy, action: Action },
+    ReceiveStopPrice { strategy: Strategy, action: Action },
+    ReceiveStopLimitPrice { strategy: Strategy, action: Action },
+    ReceiveOcoSide { strategy: Strategy },
+    ReceiveCancelScope { strategy: Strategy },
+    ReceiveClientOid { strategy: Strategy, action: Action },
+    ReceiveTrailingCallbackRate { strategy: Strategy, action: Action },
     ReceiveSymbol { strategy: Strategy, action: Action },
     ReceiveLendInterestRate { strategy: Strategy, action: Action },
+    ReceiveLeverage { strategy: Strategy, action: Action },
+    ReceiveNotifyTemplate { strategy: Strategy, action: Action },
+    ReceiveWebhookEvent { strategy: Strategy, action: Action },
+    ReceiveSubAccount { strategy: Strategy, action: Action },
+    ReceiveSubDirection { strategy: Strategy, action: Action },
+    ReceiveWithdrawChain { strategy: Strategy, action: Action },
+    ReceiveWithdrawAddress { strategy: Strategy, action: Action },
     ReceiveTransferFrom { strategy: Strategy, action: Action },
     ReceiveTransferTo { strategy: Strategy, action: Action },
     ReceiveTransferFromAccountTag { strategy: Strategy, action: Action },
     ReceiveTransferToAccountTag { strategy: Strategy, action: Action },
+    ReceiveSizeMode { strategy: Strategy, action: Action },
+    ReceiveSizeAmount { strategy: Strategy, action: Action },
+    ReceiveSourceAccount { strategy: Strategy, action: Action },
+    ReceiveFeeReserve { strategy: Strategy, action: Action },
+    ReceiveOrderOptions { strategy: Strategy, action: Action },
+    ReceiveMarginMode { strategy: Strategy, action: Action },
+    ReceiveWait { strategy: Strategy, action: Action },
     ReceivePercentage { strategy: Strategy, action: Action },
+    ReceiveTakeProfit { strategy: Strategy, action: Action },
+    ReceiveStopLoss { strategy: Strategy, action: Action },
 }
 
 pub async fn run(bot: Bot, kucoin: KuCoin, strategies: Strategies) {
@@ -115,6 +162,9 @@ fn schema() -> UpdateHandler<RequestError> {
                     case![StrategyState::ReceiveName { maybe_strategy }]
                         .endpoint(message::strategy::receive_name),
                 )
+                .branch(
+                    case![StrategyState::ReceiveImport].endpoint(message::strategy::receive_import),
+                )
                 .branch(
                     case![StrategyState::Product(state)].branch(
                         case![ProductState::ReceiveSymbol { strategy, product }]
@@ -122,13 +172,18 @@ fn schema() -> UpdateHandler<RequestError> {
                     ),
                 )
                 .branch(
-                    case![StrategyState::Condition(state)].branch(
-                        case![ConditionState::ReceiveValue {
-                            strategy,
-                            condition
-                        }]
-                        .endpoint(message::strategy::condition::receive_value),
-                    ),
+                    case![StrategyState::Condition(state)]
+                        .branch(
+                            case![ConditionState::ReceiveValue {
+                                strategy,
+                                condition
+                            }]
+                            .endpoint(message::strategy::condition::receive_value),
+                        )
+                        .branch(
+                            case![ConditionState::ReceiveOperand { strategy }]
+                                .endpoint(message::strategy::condition::receive_operand),
+                        ),
                 )
                 .branch(
                     case![StrategyState::Action(state)]
@@ -140,10 +195,34 @@ fn schema() -> UpdateHandler<RequestError> {
                             case![ActionState::ReceiveOrderPrice { strategy, action }]
                                 .endpoint(message::strategy::action::order::receive_price),
                         )
+                        .branch(
+                            case![ActionState::ReceiveStopPrice { strategy, action }]
+                                .endpoint(message::strategy::action::order::receive_price),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveStopLimitPrice { strategy, action }]
+                                .endpoint(message::strategy::action::order::receive_price),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveTrailingCallbackRate { strategy, action }]
+                                .endpoint(message::strategy::action::order::receive_callback_rate),
+                        )
                         .branch(
                             case![ActionState::ReceiveLendInterestRate { strategy, action }]
                                 .endpoint(message::strategy::action::lend::receive_interest_rate),
                         )
+                        .branch(
+                            case![ActionState::ReceiveLeverage { strategy, action }]
+                                .endpoint(message::strategy::action::futures::receive_leverage),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveNotifyTemplate { strategy, action }]
+                                .endpoint(message::strategy::action::alert::receive_template),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveWebhookEvent { strategy, action }]
+                                .endpoint(message::strategy::action::alert::receive_event),
+                        )
                         .branch(
                             case![ActionState::ReceiveTransferFromAccountTag { strategy, action }]
                                 .endpoint(message::strategy::action::transfer::receive_account_tag),
@@ -152,14 +231,39 @@ fn schema() -> UpdateHandler<RequestError> {
                             case![ActionState::ReceiveTransferToAccountTag { strategy, action }]
                                 .endpoint(message::strategy::action::transfer::receive_account_tag),
                         )
+                        .branch(
+                            case![ActionState::ReceiveSizeAmount { strategy, action }]
+                                .endpoint(message::strategy::action::size::receive_amount),
+                        )
                         .branch(
                             case![ActionState::ReceivePercentage { strategy, action }]
                                 .endpoint(message::strategy::action::receive_percentage),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveOrderOptions { strategy, action }]
+                                .endpoint(message::strategy::action::order::receive_options),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveWait { strategy, action }]
+                                .endpoint(message::strategy::action::order::receive_wait),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveClientOid { strategy, action }]
+                                .endpoint(message::strategy::action::order::receive_client_oid),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveTakeProfit { strategy, action }]
+                                .endpoint(message::strategy::action::bracket::receive_level),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveStopLoss { strategy, action }]
+                                .endpoint(message::strategy::action::bracket::receive_level),
                         ),
                 ),
         )
         .branch(case![State::ReceivePairSymbol].endpoint(message::pair::receive_symbol))
         .branch(case![State::ReceiveLendingCurrency].endpoint(message::lending::receive_currency))
+        .branch(case![State::ReceiveDepositCurrency].endpoint(message::deposit::receive_currency))
         .branch(dptree::endpoint(message::handler));
 
     let callback_query_handler = Update::filter_callback_query()
@@ -170,6 +274,10 @@ fn schema() -> UpdateHandler<RequestError> {
                     case![StrategyState::ReceiveDeleteConfirm { strategy }]
                         .endpoint(callback_query::strategy::receive_delete_confirm),
                 )
+                .branch(
+                    case![StrategyState::ReceiveExportFormat { strategies }]
+                        .endpoint(callback_query::strategy::receive_export_format),
+                )
                 .branch(
                     case![StrategyState::Product(state)]
                         .branch(
@@ -181,13 +289,34 @@ fn schema() -> UpdateHandler<RequestError> {
                                 .endpoint(
                                     callback_query::strategy::product::receive_balance_account_type,
                                 ),
+                        )
+                        .branch(
+                            case![ProductState::ReceiveFuturesMetric { strategy, product }]
+                                .endpoint(
+                                    callback_query::strategy::product::receive_futures_metric,
+                                ),
                         ),
                 )
                 .branch(
-                    case![StrategyState::Condition(state)].branch(
-                        case![ConditionState::Receive { strategy }]
-                            .endpoint(callback_query::strategy::condition::receive),
-                    ),
+                    case![StrategyState::Condition(state)]
+                        .branch(
+                            case![ConditionState::Receive { strategy }]
+                                .endpoint(callback_query::strategy::condition::receive),
+                        )
+                        .branch(
+                            case![ConditionState::ReceiveRelation {
+                                strategy,
+                                condition
+                            }]
+                            .endpoint(callback_query::strategy::condition::receive_relation),
+                        )
+                        .branch(
+                            case![ConditionState::ReceiveBound {
+                                strategy,
+                                condition
+                            }]
+                            .endpoint(callback_query::strategy::condition::receive_bound),
+                        ),
                 )
                 .branch(
                     case![StrategyState::Action(state)]
@@ -211,6 +340,48 @@ fn schema() -> UpdateHandler<RequestError> {
                             case![ActionState::ReceiveOrder { strategy, action }]
                                 .endpoint(callback_query::strategy::action::order::receive),
                         )
+                        .branch(
+                            case![ActionState::ReceiveTrailingSide { strategy }]
+                                .endpoint(callback_query::strategy::action::order::receive_side),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveStopSide { strategy }]
+                                .endpoint(callback_query::strategy::action::order::receive_side),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveOcoSide { strategy }]
+                                .endpoint(callback_query::strategy::action::order::receive_side),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveStopType { strategy, action }].endpoint(
+                                callback_query::strategy::action::order::receive_stop_type,
+                            ),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveCancelScope { strategy }].endpoint(
+                                callback_query::strategy::action::order::receive_cancel_scope,
+                            ),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveSubAccount { strategy, action }].endpoint(
+                                callback_query::strategy::action::sub::receive_sub_account,
+                            ),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveSubDirection { strategy, action }]
+                                .endpoint(callback_query::strategy::action::sub::receive_direction),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveWithdrawChain { strategy, action }].endpoint(
+                                callback_query::strategy::action::withdraw::receive_chain,
+                            ),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveWithdrawAddress { strategy, action }]
+                                .endpoint(
+                                    callback_query::strategy::action::withdraw::receive_address,
+                                ),
+                        )
                         .branch(
                             case![ActionState::ReceiveTransferFrom { strategy, action }]
                                 .endpoint(callback_query::strategy::action::transfer::receive),
@@ -218,6 +389,39 @@ fn schema() -> UpdateHandler<RequestError> {
                         .branch(
                             case![ActionState::ReceiveTransferTo { strategy, action }]
                                 .endpoint(callback_query::strategy::action::transfer::receive),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveSizeMode { strategy, action }]
+                                .endpoint(callback_query::strategy::action::size::receive_mode),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveSourceAccount { strategy, action }]
+                                .endpoint(callback_query::strategy::action::size::receive_account),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveFeeReserve { strategy, action }].endpoint(
+                                callback_query::strategy::action::size::receive_fee_reserve,
+                            ),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveMarginMode { strategy, action }]
+                                .endpoint(callback_query::strategy::action::margin::receive_mode),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveOrderOptions { strategy, action }]
+                                .endpoint(callback_query::strategy::action::skip),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveWait { strategy, action }]
+                                .endpoint(callback_query::strategy::action::skip),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveTakeProfit { strategy, action }]
+                                .endpoint(callback_query::strategy::action::skip),
+                        )
+                        .branch(
+                            case![ActionState::ReceiveStopLoss { strategy, action }]
+                                .endpoint(callback_query::strategy::action::skip),
                         ),
                 ),
         )
@@ -226,6 +430,14 @@ fn schema() -> UpdateHandler<RequestError> {
             case![State::ReceiveLendingCurrency]
                 .endpoint(callback_query::lending::receive_currency),
         )
+        .branch(
+            case![State::ReceiveDepositCurrency]
+                .endpoint(callback_query::deposit::receive_currency),
+        )
+        .branch(
+            case![State::ReceiveDepositChain { currency }]
+                .endpoint(callback_query::deposit::receive_chain),
+        )
         .branch(dptree::endpoint(callback_query::handler));
 
     dialogue::enter::<Update, InMemStorage<State>, State, _>()